@@ -29,6 +29,7 @@ pub struct CreatureAgent {
     pub cooldowns: HashMap<String, f32>,
     pub disabling: HashSet<String>,
     pub tether: Option<TetherId>,
+    pub path: Vec<[f32; 2]>,
 }
 
 #[derive(Default, Clone, serde::Serialize)]
@@ -210,7 +211,7 @@ impl CreatureAgent {
             Ground::Distance => self.position.distance(tile.position()) / self.radius,
             Ground::Daytime => self.daytime,
             Ground::Cooldown(tag, cooldown) => self.cooldown(tag) / cooldown,
-            Ground::Feeding => context.feeding_map[tile.fit(128)],
+            Ground::Feeding => context.feeding_map[tile.fit(context.feeding_bounds[0])],
             Ground::Tethering => context.get_tethering(tile.position(), &self.tether),
             Ground::HasTethering => {
                 if self.tether.is_some() {
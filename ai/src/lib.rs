@@ -62,6 +62,7 @@ pub struct Nature {
     // game view
     crops: Vec<CropView>,
     creatures: Vec<CreatureView>,
+    bounds: HashMap<SpaceId, [usize; 2]>,
     holes_map: HashMap<SpaceId, Vec<u8>>,
     barriers_map: HashMap<SpaceId, Vec<u8>>,
     barriers: HashMap<BarrierId, BarrierView>,
@@ -74,6 +75,7 @@ pub struct Nature {
     colonization_date: f32,
     // shared
     feeding_map: Vec<f32>,
+    feeding_bounds: [usize; 2],
     //
     known: Knowledge,
 }
@@ -86,16 +88,25 @@ impl Nature {
 
         let mut holes = HashMap::new();
         for (space, view) in &self.holes_map {
+            let [width, height] = self.bounds[space];
             let mut data = view.clone();
             data.add(
-                128,
-                [0, 0, 128, 128],
+                width,
+                [0, 0, width, height],
                 &self.barriers_map.get(space).unwrap(),
             );
             holes.insert(*space, data);
         }
 
-        self.feeding_map = vec![0.0; 128 * 128];
+        // feeding map is shared between spaces, so it covers largest of them
+        let [width, height] = self
+            .bounds
+            .values()
+            .fold([0, 0], |[width, height], bounds| {
+                [width.max(bounds[0]), height.max(bounds[1])]
+            });
+        self.feeding_bounds = [width, height];
+        self.feeding_map = vec![0.0; width * height];
         for (container, items) in &self.items {
             let container = match self.containers.get(container) {
                 Some(container) => container,
@@ -112,27 +123,32 @@ impl Nature {
                 }
             }
             if is_food {
-                let rect = container.position.to_tile().rect([128, 128], [9, 9]);
+                let rect = container.position.to_tile().rect([width, height], [9, 9]);
                 let [_x, _y, w, h] = rect;
                 let patch = vec![0.5; w * h];
-                self.feeding_map.add(128, rect, &patch);
+                self.feeding_map.add(width, rect, &patch);
             }
         }
         for crop in &self.crops {
             let radius = crop.growth as usize + 6;
-            let rect = crop.position.to_tile().rect([128, 128], [radius, radius]);
+            let rect = crop
+                .position
+                .to_tile()
+                .rect([width, height], [radius, radius]);
             let [_x, _y, w, h] = rect;
             let patch = vec![crop.growth / 4.0; w * h];
-            self.feeding_map.add(128, rect, &patch);
+            self.feeding_map.add(width, rect, &patch);
         }
 
         for index in 0..self.creature_agents.len() {
             let agent = &self.creature_agents[index];
             let sets = &behaviours.creatures;
+            let bounds = self.bounds[&agent.farmland.space];
             let tiles = self.get_tiles_around(
                 agent.position.to_tile(),
                 agent.radius as usize,
                 holes.get(&agent.farmland.space).expect("agent holes"),
+                bounds,
             );
 
             let mut foods = vec![];
@@ -148,7 +164,8 @@ impl Nature {
                     continue;
                 }
                 let holes = holes.get(&agent.farmland.space).unwrap();
-                let contacts = cast_ray2(agent.position, container.position, holes, &[1]);
+                let contacts =
+                    cast_ray2(agent.position, container.position, holes, bounds[0], &[1]);
                 let is_food_reachable = contacts.is_empty();
                 if is_food_reachable {
                     for item in items.values() {
@@ -171,7 +188,7 @@ impl Nature {
                     continue;
                 }
                 let holes = holes.get(&agent.farmland.space).unwrap();
-                let contacts = cast_ray2(agent.position, crop.position, holes, &[1]);
+                let contacts = cast_ray2(agent.position, crop.position, holes, bounds[0], &[1]);
                 let is_food_reachable = contacts.is_empty();
                 if is_food_reachable {
                     // TODO: common borrowing structure
@@ -5,7 +5,7 @@ use log::error;
 use game::api::Event;
 use game::collections::Shared;
 use game::inventory::{ContainerId, Inventory, ItemId, ItemKind};
-use game::math::{ArrayIndex, Position};
+use game::math::{ArrayIndex, Position, Rotation};
 use game::model::{Creature, Crop, Farmer, Purpose, Stack, Universe};
 use game::physics::{BarrierId, BarrierKind, Physics};
 use game::raising::{Raising, TetherId};
@@ -39,6 +39,7 @@ pub struct BarrierView {
     id: BarrierId,
     kind: Shared<BarrierKind>,
     position: [f32; 2],
+    rotation: Rotation,
 }
 
 pub struct ContainerView {
@@ -85,14 +86,17 @@ impl Nature {
             Universe::FarmlandAppeared {
                 farmland, holes, ..
             } => {
-                let mut holes_map = vec![0; 128 * 128];
-                let barriers_map = vec![0; 128 * 128];
-                for y in 0..holes.len() {
-                    for x in 0..holes[0].len() {
-                        let index = [x, y].fit(128);
+                let height = holes.len();
+                let width = holes.first().map(|row| row.len()).unwrap_or(0);
+                let mut holes_map = vec![0; width * height];
+                let barriers_map = vec![0; width * height];
+                for y in 0..height {
+                    for x in 0..width {
+                        let index = [x, y].fit(width);
                         holes_map[index] = holes[y][x];
                     }
                 }
+                self.bounds.insert(farmland.space, [width, height]);
                 self.holes_map.insert(farmland.space, holes_map);
                 self.barriers_map.insert(farmland.space, barriers_map);
             }
@@ -180,6 +184,7 @@ impl Nature {
                 farmland,
                 behaviour,
                 age,
+                ..
             } => {
                 self.creatures.push(CreatureView { _entity: entity });
                 self.creature_agents.push(CreatureAgent {
@@ -202,6 +207,7 @@ impl Nature {
                     cooldowns: HashMap::new(),
                     disabling: Default::default(),
                     tether: None,
+                    path: vec![],
                 });
             }
             Universe::CreatureVanished(creature) => {
@@ -246,6 +252,8 @@ impl Nature {
             Universe::CorpseVanished(_) => {}
             Universe::TheodoliteAppeared { .. } => {}
             Universe::TheodoliteVanished { .. } => {}
+            Universe::IrrigatorAppeared { .. } => {}
+            Universe::IrrigatorVanished(_) => {}
         }
     }
 
@@ -268,6 +276,7 @@ impl Nature {
                     }
                 }
             }
+            Timing::WeatherChanged { .. } => {}
         }
     }
 
@@ -295,47 +304,70 @@ impl Nature {
                     }
                 }
             }
+            Physics::BodyPathChanged { id, path, .. } => {
+                for agent in self.creature_agents.iter_mut() {
+                    if agent.entity.body == id {
+                        agent.path = path;
+                        return;
+                    }
+                }
+            }
             Physics::BarrierCreated {
                 space,
                 position,
                 key,
                 id,
                 active,
-                ..
+                rotation,
             } => {
+                let [width, _] = self.bounds[&space];
                 let barriers_map = self.barriers_map.get_mut(&space).expect("barriers_map");
                 let kind = self.known.barriers.get(key).expect("barrier kind");
-                let tiles = kind.tiles(position);
-                self.barriers.insert(id, BarrierView { id, position, kind });
+                let tiles = kind.tiles(position, rotation);
+                self.barriers.insert(
+                    id,
+                    BarrierView {
+                        id,
+                        position,
+                        kind,
+                        rotation,
+                    },
+                );
                 for tile in tiles {
-                    let index = tile.fit(128);
+                    let index = tile.fit(width);
                     barriers_map[index] = if active { 1 } else { 0 };
                 }
             }
             Physics::BarrierChanged { id, space, active } => {
+                let [width, _] = self.bounds[&space];
                 let barriers_map = self.barriers_map.get_mut(&space).expect("barriers_map");
                 let barrier = self.barriers.get(&id).expect("barrier");
-                let tiles = barrier.kind.tiles(barrier.position);
+                let tiles = barrier.kind.tiles(barrier.position, barrier.rotation);
                 for tile in tiles {
-                    let index = tile.fit(128);
+                    let index = tile.fit(width);
                     barriers_map[index] = if active { 1 } else { 0 };
                 }
             }
             Physics::BarrierDestroyed { id, space, .. } => {
+                let [width, _] = self.bounds[&space];
                 let barriers_map = self.barriers_map.get_mut(&space).expect("barriers_map");
                 let barrier = self.barriers.get(&id).expect("barrier");
-                let tiles = barrier.kind.tiles(barrier.position);
+                let tiles = barrier.kind.tiles(barrier.position, barrier.rotation);
                 for tile in tiles {
-                    let index = tile.fit(128);
+                    let index = tile.fit(width);
                     barriers_map[index] = 0;
                 }
                 self.barriers.remove(&id);
             }
+            Physics::SensorEntered { .. }
+            | Physics::SensorExited { .. }
+            | Physics::BodyPushed { .. } => {}
             Physics::SpaceUpdated { id, holes } => {
+                let [width, _] = self.bounds[&id];
                 let holes_map = self.holes_map.get_mut(&id).expect("holes_map");
                 for y in 0..holes.len() {
-                    for x in 0..holes.len() {
-                        let index = [x, y].fit(128);
+                    for x in 0..holes[y].len() {
+                        let index = [x, y].fit(width);
                         holes_map[index] = holes[y][x];
                     }
                 }
@@ -372,6 +404,8 @@ impl Nature {
             }
             Raising::LeadershipChanged { .. } => {}
             Raising::HerdsmanChanged { .. } => {}
+            Raising::AnimalConceived { .. } => {}
+            Raising::AnimalGaveBirth { .. } => {}
             Raising::BehaviourChanged { id, behaviour } => {
                 for creature in self.creature_agents.iter_mut() {
                     if creature.entity.animal == id {
@@ -448,6 +482,7 @@ impl Nature {
                 id,
                 container,
                 quantity,
+                ..
             } => {
                 self.items.entry(container).and_modify(|items| {
                     items.entry(id).and_modify(|item| item.quantity = quantity);
@@ -18,9 +18,11 @@ impl Nature {
         center: [usize; 2],
         radius: usize,
         game_tiles: &Vec<u8>,
+        bounds: [usize; 2],
     ) -> Vec<[usize; 2]> {
+        let [width, height] = bounds;
         let mut tiles = vec![];
-        let mut map = vec![vec![0; 128]; 128];
+        let mut map = vec![vec![0; width]; height];
         let mut frontier = vec![center];
         let mut wave = 1;
         loop {
@@ -35,10 +37,10 @@ impl Nature {
                     [[cx, cy - 1], [cx - 1, cy], [cx + 1, cy], [cx, cy + 1]];
                 for next in steps {
                     let [nx, ny] = next;
-                    if nx >= 0 && nx < 128 && ny >= 0 && ny < 128 {
+                    if nx >= 0 && nx < width as isize && ny >= 0 && ny < height as isize {
                         let nx = nx as usize;
                         let ny = ny as usize;
-                        let nindex = [nx, ny].fit(128);
+                        let nindex = [nx, ny].fit(width);
                         let tile = game_tiles[nindex];
                         let not_empty = tile > 0;
                         if not_empty {
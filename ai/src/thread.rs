@@ -28,6 +28,7 @@ impl AiThread {
             let mut nature = Nature {
                 crops: vec![],
                 creatures: vec![],
+                bounds: Default::default(),
                 holes_map: Default::default(),
                 barriers_map: Default::default(),
                 creature_agents: vec![],
@@ -36,6 +37,7 @@ impl AiThread {
                 colonization_date: 0.0,
                 farmers: Default::default(),
                 feeding_map: vec![],
+                feeding_bounds: [0, 0],
                 known: Game::load_knowledge(&knowledge),
                 barriers: Default::default(),
                 tethers: Default::default(),
@@ -94,6 +96,12 @@ fn handle_server_responses(client: &mut TcpClient) -> Vec<Event> {
                 error!("Action {} error response {:?}", action_id, error);
             }
             GameResponse::Trip { id } => client.send(PlayerRequest::Trip { id }),
+            GameResponse::View { query_id, .. } => {
+                error!("Unexpected query {} view response", query_id);
+            }
+            GameResponse::QueryError { query_id, error } => {
+                error!("Query {} error response {:?}", query_id, error);
+            }
         }
     }
     all_events
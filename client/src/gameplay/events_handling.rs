@@ -19,8 +19,8 @@ use game::working::Working;
 use crate::engine::Frame;
 use crate::gameplay::representation::{
     AssemblyRep, AssemblyTargetAsset, BuildingRep, CementerRep, ComposterRep, ConstructionRep,
-    CorpseRep, CreatureRep, CropRep, DoorRep, EquipmentRep, FarmerRep, FarmlandRep, ItemRep,
-    RestRep, StackRep, TheodoliteRep, TreeRep,
+    CorpseRep, CreatureRep, CropRep, DoorRep, EquipmentRep, FarmerRep, FarmlandRep, IrrigatorRep,
+    ItemRep, RestRep, StackRep, TheodoliteRep, TreeRep,
 };
 use crate::gameplay::Gameplay;
 
@@ -123,6 +123,7 @@ impl Gameplay {
                             asset,
                             container: item.container,
                             quantity: item.quantity,
+                            quality: item.quality,
                         },
                     );
                 }
@@ -136,9 +137,13 @@ impl Gameplay {
                 id,
                 container,
                 quantity,
+                quality,
             } => {
                 self.items.entry(container).and_modify(|items| {
-                    items.entry(id).and_modify(|item| item.quantity = quantity);
+                    items.entry(id).and_modify(|item| {
+                        item.quantity = quantity;
+                        item.quality = quality;
+                    });
                 });
             }
         }
@@ -206,6 +211,7 @@ impl Gameplay {
             }
             Landscaping::SurfaceInspected {
                 surface,
+                elevation,
                 rect,
                 land,
             } => {
@@ -215,6 +221,19 @@ impl Gameplay {
                         farmland
                             .surface
                             .paste(farmland.kind.land.width, rect, &surface);
+                        farmland
+                            .elevation
+                            .paste(farmland.kind.land.width, rect, &elevation);
+                        break;
+                    }
+                }
+            }
+            Landscaping::IrrigationCreated { .. } => {}
+            Landscaping::IrrigationDestroyed { .. } => {}
+            Landscaping::IrrigationPressureChanged { id, pressure } => {
+                for irrigator in self.irrigators.values_mut() {
+                    if irrigator.entity.irrigation == id {
+                        irrigator.pressure = pressure;
                         break;
                     }
                 }
@@ -237,6 +256,12 @@ impl Gameplay {
                         crop.synchronize_thirst(thirst);
                         crop.synchronize_hunger(hunger);
                         crop.synchronize_growth(growth);
+                        return;
+                    }
+                }
+                for tree in self.trees.values_mut() {
+                    if tree.tree.plant == id {
+                        tree.synchronize_growth(growth);
                         break;
                     }
                 }
@@ -249,24 +274,61 @@ impl Gameplay {
                     }
                 }
             }
+            Planting::PlantStageChanged { id, stage } => {
+                for crop in self.crops.values_mut() {
+                    if crop.entity.plant == id {
+                        crop.synchronize_stage(stage);
+                        return;
+                    }
+                }
+                for tree in self.trees.values_mut() {
+                    if tree.tree.plant == id {
+                        tree.synchronize_stage(stage);
+                        break;
+                    }
+                }
+            }
+            Planting::PlantStressChanged { id, stress } => {
+                for crop in self.crops.values_mut() {
+                    if crop.entity.plant == id {
+                        crop.synchronize_stress(stress);
+                        break;
+                    }
+                }
+            }
+            Planting::PlantInfectionChanged { id, infection } => {
+                for crop in self.crops.values_mut() {
+                    if crop.entity.plant == id {
+                        crop.synchronize_infection(infection);
+                        break;
+                    }
+                }
+            }
+            Planting::PlantPollinated { .. } => {}
             Planting::PlantDamaged { id, health } => {
                 for crop in self.crops.values_mut() {
                     if crop.entity.plant == id {
                         crop.synchronize_health(health);
+                        return;
+                    }
+                }
+                for tree in self.trees.values_mut() {
+                    if tree.tree.plant == id {
+                        tree.synchronize_health(health);
                         break;
                     }
                 }
             }
-            Planting::SoilFertilityInspected {
+            Planting::SoilNutrientsInspected {
                 soil,
-                fertility,
+                nutrients,
                 rect,
             } => {
                 for farmland in self.farmlands.values_mut() {
                     if farmland.entity.soil == soil {
-                        farmland
-                            .fertility
-                            .paste(farmland.kind.soil.width, rect, &fertility);
+                        for (channel, nutrients) in farmland.nutrients.iter_mut().zip(nutrients) {
+                            channel.paste(farmland.kind.soil.width, rect, &nutrients);
+                        }
                         break;
                     }
                 }
@@ -329,6 +391,8 @@ impl Gameplay {
                     }
                 }
             }
+            Raising::AnimalConceived { .. } => {}
+            Raising::AnimalGaveBirth { .. } => {}
             Raising::AnimalUntied { id, tether: _ } => {
                 for creature in self.creatures.values_mut() {
                     if creature.entity.animal == id {
@@ -387,12 +451,27 @@ impl Gameplay {
                     }
                 }
             }
+            Physics::BodyPathChanged { id, path, .. } => {
+                for farmer in self.farmers.values_mut() {
+                    if farmer.entity.body == id {
+                        farmer.path = path;
+                        return;
+                    }
+                }
+                for creature in self.creatures.values_mut() {
+                    if creature.entity.body == id {
+                        creature.path = path;
+                        return;
+                    }
+                }
+            }
             Physics::BarrierCreated {
                 id,
                 position,
                 space,
                 active,
                 key,
+                rotation,
             } => {
                 let kind = self.known.barriers.get(key).unwrap();
                 self.barriers_hint.push(Barrier {
@@ -401,6 +480,7 @@ impl Gameplay {
                     position,
                     space,
                     active,
+                    rotation,
                 });
             }
             Physics::BarrierChanged { id, active, .. } => {
@@ -420,6 +500,10 @@ impl Gameplay {
                     self.barriers_hint.remove(index);
                 }
             }
+            Physics::SensorEntered { .. } | Physics::SensorExited { .. } => {}
+            Physics::BodyPushed { .. } => {
+                // pushed body movement is synchronized by position changes
+            }
             Physics::SpaceUpdated { id, holes } => {
                 for farmland in self.farmlands.values_mut() {
                     if farmland.entity.space == id {
@@ -478,6 +562,19 @@ impl Gameplay {
                     }
                 }
             }
+            Timing::WeatherChanged {
+                id,
+                weather,
+                forecast,
+            } => {
+                for farmland in self.farmlands.values_mut() {
+                    if farmland.entity.calendar == id {
+                        farmland.weather = weather;
+                        farmland.forecast = forecast;
+                        break;
+                    }
+                }
+            }
         }
     }
 
@@ -497,6 +594,7 @@ impl Gameplay {
                 );
 
                 let prefab = assets.tree(&kind.name);
+                let stage = kind.plant.get_stage(growth, 1.0);
 
                 self.trees.insert(
                     tree,
@@ -506,6 +604,9 @@ impl Gameplay {
                         asset: prefab,
                         position,
                         direction: [0.0, 0.0],
+                        growth,
+                        health: 1.0,
+                        stage,
                     },
                 );
             }
@@ -522,6 +623,8 @@ impl Gameplay {
                 season,
                 season_day,
                 times_of_day,
+                weather,
+                forecast,
             } => {
                 let kind = self.known.farmlands.get(farmland.kind).unwrap().clone();
                 info!("Appear {:?} kind='{}'", farmland, kind.name);
@@ -590,20 +693,27 @@ impl Gameplay {
                     asset: deconstruction,
                 };
 
+                let land_size = kind.land.width * kind.land.height;
+                let soil_size = kind.soil.width * kind.soil.height;
                 self.farmlands.insert(
                     farmland,
                     FarmlandRep {
                         entity: farmland,
                         kind,
                         asset,
-                        moisture: vec![0.0; 128 * 128],
-                        moisture_capacity: vec![0.0; 128 * 128],
-                        surface: vec![0; 128 * 128],
+                        moisture: vec![0.0; land_size],
+                        moisture_capacity: vec![0.0; land_size],
+                        surface: vec![0; land_size],
+                        elevation: vec![0; land_size],
                         surface_tilemap: frame.scene.instantiate_tilemap(
                             assets.texture("./assets/texture/tiles-waterbody.png"),
                             assets.sampler("pixel-perfect"),
                         ),
-                        fertility: vec![0.0; 128 * 128],
+                        nutrients: [
+                            vec![0.0; soil_size],
+                            vec![0.0; soil_size],
+                            vec![0.0; soil_size],
+                        ],
                         cells,
                         rooms,
                         holes,
@@ -614,6 +724,8 @@ impl Gameplay {
                         season,
                         season_day,
                         times_of_day,
+                        weather,
+                        forecast,
                     },
                 );
             }
@@ -643,6 +755,7 @@ impl Gameplay {
                         estimated_position: position,
                         rendering_position: position,
                         last_sync_position: position,
+                        path: vec![],
                         activity: Activity::Idle,
                     },
                 );
@@ -695,6 +808,26 @@ impl Gameplay {
             Universe::TheodoliteVanished { id } => {
                 self.theodolites.remove(&id);
             }
+            Universe::IrrigatorAppeared {
+                entity,
+                place,
+                pressure,
+            } => {
+                let kind = self.known.irrigators.get(entity.key).unwrap();
+                let item = assets.item(&kind.item.name);
+                self.irrigators.insert(
+                    entity,
+                    IrrigatorRep {
+                        entity,
+                        place,
+                        pressure,
+                        item,
+                    },
+                );
+            }
+            Universe::IrrigatorVanished(irrigator) => {
+                self.irrigators.remove(&irrigator);
+            }
             Universe::EquipmentAppeared { entity, position } => {
                 info!("Appear {:?} at {:?}", entity, position);
                 let kind = self.known.equipments.get(entity.key).unwrap();
@@ -734,6 +867,8 @@ impl Gameplay {
                 health,
                 hunger: _,
                 weight,
+                sex: _,
+                pregnancy: _,
                 position,
                 behaviour,
                 age,
@@ -834,6 +969,7 @@ impl Gameplay {
                     estimated_position: position,
                     rendering_position: position,
                     last_sync_position: position,
+                    path: vec![],
                     spine,
                     direction: [1.0, 0.0],
                     velocity: [0.0, 0.0],
@@ -855,6 +991,9 @@ impl Gameplay {
                 growth,
                 health,
                 fruits,
+                stage,
+                stress,
+                infection,
             } => {
                 let kind = self.known.crops.get(entity.key).unwrap();
                 info!(
@@ -932,6 +1071,9 @@ impl Gameplay {
                     growth,
                     health,
                     fruits,
+                    stage,
+                    stress,
+                    infection,
                 };
                 representation.synchronize_fruits(fruits);
                 self.crops.insert(entity, representation);
@@ -3,8 +3,8 @@ use game::assembling::Rotation;
 
 use game::inventory::Function;
 use game::inventory::Function::{
-    Assembly, Fertilizer, Installation, Instrumenting, Moistener, Product, Seeding, Shovel, Stone,
-    Tether,
+    Assembly, Axe, Fertilizer, Gravel, Hoe, Installation, Instrumenting, Moistener, Pesticide,
+    Product, Sapling, Seeding, Shovel, Stone, Tether,
 };
 use game::model::{Activity, CropKey};
 
@@ -12,7 +12,8 @@ use crate::gameplay::representation::FarmerRep;
 use crate::gameplay::Intention::{Aim, Move, Put, QuickSwap, Swap, Use};
 use crate::gameplay::Target::{
     Cementer, CementerContainer, Composter, ComposterContainer, Construction, Corpse, Creature,
-    Crop, Device, Door, Equipment, Ground, Rest, Stack, Theodolite, Wall, Waterbody,
+    Crop, Device, Door, Equipment, Ground, Irrigator, Rest, Stack, Theodolite, Tree, Wall,
+    Waterbody,
 };
 use crate::gameplay::{Gameplay, Intention, Target};
 
@@ -42,6 +43,8 @@ impl Gameplay {
                     Crop(crop) => {
                         self.send_action(FarmerBound::HarvestCrop { crop });
                     }
+                    Tree(_) => {}
+                    Irrigator(_) => {}
                     Door(door) => {
                         self.send_action(FarmerBound::ToggleDoor { door });
                     }
@@ -72,7 +75,9 @@ impl Gameplay {
                     Rest(rest) => {
                         self.send_action(FarmerBound::Relax { rest });
                     }
-                    Creature(_) => {}
+                    Creature(creature) => {
+                        self.send_action(FarmerBound::PushCreature { creature });
+                    }
                     Corpse(corpse) => {
                         self.send_action(FarmerBound::CollectCorpse { corpse });
                     }
@@ -86,6 +91,9 @@ impl Gameplay {
                     Theodolite(theodolite) => {
                         self.send_action(FarmerBound::UninstallTheodolite { theodolite });
                     }
+                    Irrigator(irrigator) => {
+                        self.send_action(FarmerBound::UninstallIrrigator { irrigator });
+                    }
                     Door(door) => {
                         self.send_action(FarmerBound::DisassembleDoor { door });
                     }
@@ -115,6 +123,14 @@ impl Gameplay {
                                 self.send_action(FarmerBound::PlantCrop { tile });
                                 break;
                             }
+                            (Sapling { .. }, Ground(tile)) => {
+                                self.send_action(FarmerBound::PlantTree { tile });
+                                break;
+                            }
+                            (Axe, Tree(tree)) => {
+                                self.send_action(FarmerBound::ChopTree { tree });
+                                break;
+                            }
                             (Installation { .. }, Ground(tile)) => {
                                 self.send_action(FarmerBound::Install { tile });
                                 break;
@@ -127,6 +143,14 @@ impl Gameplay {
                                 self.send_action(FarmerBound::DigPlace { place: tile });
                                 break;
                             }
+                            (Hoe, Ground(tile)) => {
+                                self.send_action(FarmerBound::PloughPlace { place: tile });
+                                break;
+                            }
+                            (Gravel, Ground(tile)) => {
+                                self.send_action(FarmerBound::PavePlace { place: tile });
+                                break;
+                            }
                             (Instrumenting, Construction(construction)) => {
                                 self.send_action(FarmerBound::Build { construction });
                                 break;
@@ -153,6 +177,9 @@ impl Gameplay {
                             (Fertilizer(_), Ground(tile)) => {
                                 self.send_action(FarmerBound::Fertilize { tile });
                             }
+                            (Pesticide(_), Crop(crop)) => {
+                                self.send_action(FarmerBound::TreatCrop { crop });
+                            }
                             (Product(kind), Crop(crop)) => {
                                 if CropKey(kind) == crop.key {
                                     self.send_action(FarmerBound::HarvestCrop { crop });
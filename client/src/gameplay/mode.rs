@@ -9,6 +9,7 @@ use game::api::{Action, Cheat, FarmerBound, GameResponse, PlayerRequest};
 use game::inventory::{ContainerId, ItemId};
 use game::math::VectorMath;
 use game::model::Farmland;
+use game::model::Irrigator;
 use game::model::Knowledge;
 use game::model::Stack;
 use game::model::Tree;
@@ -29,8 +30,8 @@ use crate::engine::rendering::TextController;
 use crate::gameplay::camera::Camera;
 use crate::gameplay::representation::{
     AssemblyRep, CementerRep, ComposterRep, ConstructionRep, CorpseRep, CreatureRep, CropRep,
-    DoorRep, EquipmentRep, FarmerRep, FarmlandRep, ItemRep, RestRep, StackRep, TheodoliteRep,
-    TreeRep,
+    DoorRep, EquipmentRep, FarmerRep, FarmlandRep, IrrigatorRep, ItemRep, RestRep, StackRep,
+    TheodoliteRep, TreeRep,
 };
 use crate::gameplay::GameplayMetrics;
 use crate::monitoring::{Timer, TimerIntegration};
@@ -67,6 +68,7 @@ pub struct Gameplay {
     pub stacks: HashMap<Stack, StackRep>,
     pub equipments: HashMap<Equipment, EquipmentRep>,
     pub theodolites: HashMap<Theodolite, TheodoliteRep>,
+    pub irrigators: HashMap<Irrigator, IrrigatorRep>,
     pub assembly: HashMap<Assembly, AssemblyRep>,
     pub doors: HashMap<Door, DoorRep>,
     pub rests: HashMap<Rest, RestRep>,
@@ -139,6 +141,7 @@ impl Gameplay {
             stacks: Default::default(),
             equipments: Default::default(),
             theodolites: Default::default(),
+            irrigators: Default::default(),
             assembly: Default::default(),
             doors: Default::default(),
             rests: Default::default(),
@@ -188,6 +191,12 @@ impl Gameplay {
                     error!("Action {action} error {error:?}");
                 }
                 GameResponse::Trip { id } => self.client.send(PlayerRequest::Trip { id }),
+                GameResponse::View { query_id, .. } => {
+                    error!("Unexpected query {query_id} view response");
+                }
+                GameResponse::QueryError { query_id, error } => {
+                    error!("Query {query_id} error {error:?}");
+                }
             }
         }
     }
@@ -4,7 +4,7 @@ use log::{error, info};
 use rand::prelude::*;
 
 use game::assembling::Rotation;
-use game::building::{Cell, Grid, Marker, Material, Room, Structure};
+use game::building::{Cell, Marker, Material, Room, Structure};
 use game::inventory::{ContainerId, ItemId};
 use game::math::{ArrayIndex, Position, Tile, TileMath, VectorMath};
 use game::model::{Activity, CementerKind, ComposterKind, Purpose};
@@ -209,7 +209,12 @@ impl Gameplay {
                     let index = [ix, iy].fit(farmland.kind.land.width);
                     let moisture = farmland.moisture[index];
                     let capacity = farmland.moisture_capacity[index];
-                    let fertility = farmland.fertility[index];
+                    // the scarcest nutrient limits plant growth
+                    let fertility = farmland
+                        .nutrients
+                        .iter()
+                        .map(|channel| channel[index])
+                        .fold(1.0, f32::min);
                     map[y][x] = [capacity, moisture, fertility, 0.0];
                 }
             }
@@ -242,8 +247,7 @@ impl Gameplay {
                         for x in 0..31 {
                             let x = x + render_offset_x;
 
-                            let interior_bit = 1 << (Grid::COLUMNS - x - 1);
-                            if row & interior_bit == interior_bit {
+                            if row.contains(x) {
                                 // TODO: room material detection
                                 let material = farmland.cells[y][x].material.index();
                                 if material == Material::PLANKS || material == Material::GLASS {
@@ -271,8 +275,7 @@ impl Gameplay {
                     if y >= render_offset_y && (y - render_offset_y) < roof_map.len() {
                         for x in 0..31 {
                             let x = x + render_offset_x;
-                            let interior_bit = 1 << (Grid::COLUMNS - x - 1);
-                            if row & interior_bit == interior_bit {
+                            if row.contains(x) {
                                 // TODO: room material detection
                                 let material = farmland.cells[y][x].material.index();
                                 if material == Material::PLANKS || material == Material::GLASS {
@@ -596,6 +599,13 @@ impl Gameplay {
             scene.render_sprite(&theodolite.item.sprite, xy(position));
         }
 
+        for irrigator in self.irrigators.values() {
+            let position = rendering_position_of(position_of(irrigator.place));
+            let brightness = 0.5 + 0.5 * irrigator.pressure;
+            let color = [brightness, brightness, 1.0, 1.0];
+            scene.render_sprite_colored(&irrigator.item.sprite, xy(position), color);
+        }
+
         for equipment in self.equipments.values() {
             match equipment.entity.purpose {
                 Purpose::Moisture { .. } => {}
@@ -741,6 +751,17 @@ impl Gameplay {
                 }
             };
 
+            let weather_text = frame
+                .translator
+                .say(&format!("Weather_{:?}", farmland.weather));
+            let forecast_text = farmland
+                .forecast
+                .iter()
+                .map(|weather| frame.translator.say(&format!("Weather_{weather:?}")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let forecast_text = frame.translator.format("Forecast_$1", [forecast_text]);
+
             // simple night overlay
             // TODO: lights system
             let alpha = match farmland.times_of_day {
@@ -758,8 +779,9 @@ impl Gameplay {
                 );
             }
 
-            self.watch_display
-                .set_text(format!("{cd_text}\n{season_text}, {time_text}"));
+            self.watch_display.set_text(format!(
+                "{cd_text}\n{season_text}, {time_text}\n{weather_text}, {forecast_text}"
+            ));
             frame.scene.render_text(&mut self.watch_display, [16, 16]);
         }
     }
@@ -1,5 +1,5 @@
 use game::assembling::Rotation;
-use log::error;
+use log::{error, info};
 use rusty_spine::Skin;
 use std::collections::HashMap;
 
@@ -10,10 +10,12 @@ use game::math::{Collider, Tile, VectorMath};
 use game::model::{
     Activity, Assembly, Cementer, CementerKind, Composter, ComposterKind, Construction, Corpse,
     Creature, CreatureKind, Crop, Door, Equipment, EquipmentKind, Farmer, FarmerKind, Farmland,
-    FarmlandKind, Rest, Stack, Theodolite, Tree, TreeKind,
+    FarmlandKind, Irrigator, Rest, Stack, Theodolite, Tree, TreeKind,
 };
 use game::physics::BodyKind;
+use game::planting::{PlantStage, PlantStress};
 use game::raising::{Behaviour, TetherId};
+use game::timing::Weather;
 
 use crate::assets::{
     BuildingMaterialAsset, ComposterAsset, CorpseAsset, CreatureAsset, SpriteAsset,
@@ -34,6 +36,7 @@ pub struct FarmerRep {
     pub estimated_position: [f32; 2],
     pub rendering_position: [f32; 2],
     pub last_sync_position: [f32; 2],
+    pub path: Vec<[f32; 2]>,
     pub activity: Activity,
 }
 
@@ -91,8 +94,9 @@ pub struct FarmlandRep {
     pub moisture: Vec<f32>,
     pub moisture_capacity: Vec<f32>,
     pub surface: Vec<u8>,
+    pub elevation: Vec<u8>,
     pub surface_tilemap: TilemapController,
-    pub fertility: Vec<f32>,
+    pub nutrients: [Vec<f32>; 3],
     pub cells: Vec<Vec<Cell>>,
     pub rooms: Vec<Room>,
     pub holes: Vec<Vec<u8>>,
@@ -103,6 +107,8 @@ pub struct FarmlandRep {
     pub season: u8,
     pub season_day: f32,
     pub times_of_day: f32,
+    pub weather: Weather,
+    pub forecast: Vec<Weather>,
 }
 
 pub struct BuildingRep {
@@ -117,6 +123,23 @@ pub struct TreeRep {
     pub asset: TreeAsset,
     pub position: [f32; 2],
     pub direction: [f32; 2],
+    pub growth: f32,
+    pub health: f32,
+    pub stage: PlantStage,
+}
+
+impl TreeRep {
+    pub fn synchronize_growth(&mut self, growth: f32) {
+        self.growth = growth;
+    }
+
+    pub fn synchronize_health(&mut self, health: f32) {
+        self.health = health;
+    }
+
+    pub fn synchronize_stage(&mut self, stage: PlantStage) {
+        self.stage = stage;
+    }
 }
 
 pub struct StackRep {
@@ -144,6 +167,13 @@ pub struct TheodoliteRep {
     pub item: ItemAsset,
 }
 
+pub struct IrrigatorRep {
+    pub entity: Irrigator,
+    pub place: [usize; 2],
+    pub pressure: f32,
+    pub item: ItemAsset,
+}
+
 pub enum AssemblyTargetAsset {
     Door {
         door: DoorAsset,
@@ -218,6 +248,7 @@ pub struct ItemRep {
     pub asset: ItemAsset,
     pub container: ContainerId,
     pub quantity: u8,
+    pub quality: f32,
 }
 
 impl ItemRep {
@@ -244,6 +275,9 @@ pub struct CropRep {
     pub growth: f32,
     pub health: f32,
     pub fruits: f32,
+    pub stage: PlantStage,
+    pub stress: PlantStress,
+    pub infection: f32,
 }
 
 impl CropRep {
@@ -269,6 +303,21 @@ impl CropRep {
         self.hunger = hunger;
     }
 
+    pub fn synchronize_stage(&mut self, stage: PlantStage) {
+        self.stage = stage;
+    }
+
+    pub fn synchronize_stress(&mut self, stress: PlantStress) {
+        if stress != PlantStress::None {
+            info!("Crop {:?} suffers from {:?}", self.entity, stress);
+        }
+        self.stress = stress;
+    }
+
+    pub fn synchronize_infection(&mut self, infection: f32) {
+        self.infection = infection;
+    }
+
     pub fn spine(&self) -> usize {
         (self.growth.floor() as usize).min(4)
     }
@@ -288,7 +337,7 @@ impl CropRep {
     }
 
     pub fn is_harvest_phase(&self) -> bool {
-        self.growth >= 3.0 && self.growth < 4.0
+        matches!(self.stage, PlantStage::Fruiting | PlantStage::Overripe)
     }
 }
 
@@ -302,6 +351,7 @@ pub struct CreatureRep {
     pub estimated_position: [f32; 2],
     pub rendering_position: [f32; 2],
     pub last_sync_position: [f32; 2],
+    pub path: Vec<[f32; 2]>,
     pub spine: SpineRenderController,
     pub direction: [f32; 2],
     pub velocity: [f32; 2],
@@ -4,8 +4,8 @@ use game::inventory::ContainerId;
 use game::landscaping::Surface;
 use game::math::{ArrayIndex, Tile, TileMath, VectorMath};
 use game::model::{
-    Cementer, Composter, Construction, Corpse, Creature, Crop, Door, Equipment, Rest, Stack,
-    Theodolite,
+    Cementer, Composter, Construction, Corpse, Creature, Crop, Door, Equipment, Irrigator, Rest,
+    Stack, Theodolite, Tree,
 };
 use game::working::DeviceId;
 
@@ -28,8 +28,10 @@ pub enum Target {
     Construction(Construction),
     Equipment(Equipment),
     Theodolite(Theodolite),
+    Irrigator(Irrigator),
     Wall([usize; 2]),
     Crop(Crop),
+    Tree(Tree),
     Door(Door),
     Rest(Rest),
     Stack(Stack),
@@ -103,6 +105,12 @@ impl Gameplay {
             }
         }
 
+        for irrigator in self.irrigators.values() {
+            if irrigator.place == tile {
+                return vec![Target::Irrigator(irrigator.entity)];
+            }
+        }
+
         for creature in self.creatures.values() {
             if creature.estimated_position.to_tile() == tile {
                 return vec![Target::Creature(creature.entity)];
@@ -115,6 +123,12 @@ impl Gameplay {
             }
         }
 
+        for tree in self.trees.values() {
+            if tree.position.to_tile() == tile {
+                return vec![Target::Tree(tree.tree)];
+            }
+        }
+
         for crop in self.crops.values() {
             if crop.position.to_tile() == tile {
                 return vec![Target::Crop(crop.entity), Target::Ground(tile)];
@@ -14,6 +14,17 @@ create table Player
     name text not null
 );
 
+create table Randomness
+(
+    id          integer primary key,
+    seed        integer not null,
+    actions     integer not null,
+    working     integer not null,
+    landscaping integer not null,
+    raising     integer not null,
+    planting    integer not null default 0
+);
+
 -- Timing
 
 create table CalendarKind
@@ -30,7 +41,8 @@ create table Calendar
     key          integer not null references CalendarKind (id),
     season       integer not null,
     season_day   real    not null,
-    times_of_day real    not null
+    times_of_day real    not null,
+    day          integer not null default 0
 );
 
 -- Physics
@@ -46,15 +58,20 @@ create table Space
 (
     id    integer primary key,
     kind  integer             not null references SpaceKind (id),
-    holes blob collate binary not null
+    holes blob collate binary not null,
+    paths blob collate binary null
 );
 
 create table BodyKind
 (
-    id     integer primary key,
-    name   text not null unique,
-    speed  real not null,
-    radius real not null
+    id              integer primary key,
+    name            text    not null unique,
+    speed           real    not null,
+    radius          real    not null,
+    collision_layer integer not null default 1,
+    collision_mask  integer not null default 1,
+    acceleration    real    not null default 0.0,
+    damping         real    not null default 4.0
 );
 
 create table Body
@@ -79,14 +96,16 @@ create table Barrier
     kind     integer not null references BarrierKind (id),
     space    integer not null references Space (id),
     position json    not null,
-    active   boolean not null
+    active   boolean not null,
+    rotation integer not null default 0
 );
 
 create table SensorKind
 (
     id     integer primary key,
     name   text not null unique,
-    radius real not null
+    radius real not null,
+    filter json not null default '[]'
 );
 
 create table Sensor
@@ -102,17 +121,20 @@ create table Sensor
 
 create table SoilKind
 (
-    id     integer primary key,
-    name   text    not null unique,
-    width  integer not null,
-    height integer not null
+    id        integer primary key,
+    name      text    not null unique,
+    width     integer not null,
+    height    integer not null,
+    nutrients json    not null default '[0.0, 0.0, 0.0]'
 );
 
 create table Soil
 (
     id        integer primary key,
     kind      integer             not null references SoilKind (id),
-    fertility blob collate binary not null
+    fertility blob collate binary not null,
+    nutrients blob collate binary null,
+    history   json                not null default '[]'
 );
 
 create table PlantKind
@@ -122,20 +144,30 @@ create table PlantKind
     growth        real not null,
     flexibility   real not null,
     transpiration real not null,
-    max_fruits    real not null
+    max_fruits    real not null,
+    consumption   json not null default '[0.0003, 0.0003, 0.0003]',
+    restoration   json not null default '[0.0, 0.0, 0.0]',
+    stages        json not null default '[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]',
+    seasons       json not null default '[]',
+    temperature   json not null default '[5.0, 35.0]',
+    resistance    real not null default 0.5,
+    pollination   real not null default 0.0
 );
 
 create table Plant
 (
-    id     integer primary key,
-    kind   integer not null references PlantKind (id),
-    soil   integer not null references Soil (id),
-    impact real    not null,
-    thirst real    not null,
-    hunger real    not null,
-    health real    not null,
-    growth real    not null,
-    fruits real    not null
+    id          integer primary key,
+    kind        integer not null references PlantKind (id),
+    soil        integer not null references Soil (id),
+    impact      real    not null,
+    thirst      real    not null,
+    hunger      real    not null,
+    health      real    not null,
+    growth      real    not null,
+    fruits      real    not null,
+    infection   real    not null default 0.0,
+    quality     real    not null default 1.0,
+    pollination real    not null default 0.0
 );
 
 -- Landscaping
@@ -154,7 +186,25 @@ create table Land
     kind              integer             not null references LandKind (id),
     moisture          blob collate binary not null,
     moisture_capacity blob collate binary not null,
-    surface           blob collate binary not null
+    surface           blob collate binary not null,
+    elevation         blob collate binary null,
+    water             real                not null default 1.0
+);
+
+create table IrrigationKind
+(
+    id   integer primary key,
+    name text not null unique,
+    part text not null
+);
+
+create table Irrigation
+(
+    id       integer primary key,
+    kind     integer not null references IrrigationKind (id),
+    land     integer not null references Land (id),
+    place    text    not null,
+    pressure real    not null default 0.0
 );
 
 -- Raising
@@ -168,7 +218,10 @@ create table AnimalKind
     hunger_damage   real not null,
     thirst_damage   real not null,
     death_threshold real not null,
-    voracity        real not null
+    voracity        real not null,
+    maturity        real not null default 600.0,
+    gestation       real not null default 1200.0,
+    fertility       real not null default 0.01
 );
 
 create table Animal
@@ -181,7 +234,9 @@ create table Animal
     health    real    not null,
     stress    real    not null,
     voracity  real    not null,
-    behaviour json    not null
+    behaviour json    not null,
+    sex       json    null,
+    pregnancy real
 );
 
 create table Tether
@@ -194,8 +249,10 @@ create table Tether
 
 create table GridKind
 (
-    id   integer primary key,
-    name text not null unique
+    id     integer primary key,
+    name   text    not null unique,
+    width  integer not null default 128,
+    height integer not null default 128
 );
 
 create table Grid
@@ -240,7 +297,8 @@ create table ItemKind
     name         text not null unique,
     max_quantity integer,
     functions    json not null,
-    stackable    bool not null
+    stackable    bool not null,
+    reach        real not null default 2.0
 );
 
 create table Item
@@ -248,7 +306,8 @@ create table Item
     id        integer primary key,
     kind      integer not null references ItemKind (id),
     container integer not null references Container (id),
-    quantity  integer not null
+    quantity  integer not null,
+    quality   real    not null default 1.0
 );
 
 -- Assembling
@@ -290,7 +349,9 @@ create table TreeKind
     id      integer primary key,
     name    text    not null unique,
     barrier integer not null references BarrierKind (id),
-    plant   integer not null references PlantKind (id)
+    plant   integer not null references PlantKind (id),
+    wood    text    not null references ItemKind (name),
+    sapling text    null references ItemKind (name)
 );
 
 create table Tree
@@ -380,7 +441,8 @@ create table TheodoliteKind
     name     text not null unique,
     item     text not null references ItemKind (name),
     barrier  text not null references BarrierKind (name),
-    surveyor text null references SurveyorKind (name)
+    surveyor text null references SurveyorKind (name),
+    reach    real not null default 2.0
 );
 
 create table Theodolite
@@ -391,6 +453,21 @@ create table Theodolite
     surveyor integer null references Surveyor (id)
 );
 
+create table IrrigatorKind
+(
+    id         integer primary key,
+    name       text not null unique,
+    irrigation text not null references IrrigationKind (name),
+    item       text not null references ItemKind (name)
+);
+
+create table Irrigator
+(
+    id         integer primary key,
+    key        integer not null references IrrigatorKind (id),
+    irrigation integer not null references Irrigation (id)
+);
+
 create table CropKind
 (
     id      integer primary key,
@@ -399,7 +476,8 @@ create table CropKind
     barrier text not null references BarrierKind (name),
     sensor  text not null references SensorKind (name),
     fruits  text not null references ItemKind (name),
-    residue text not null references ItemKind (name)
+    residue text not null references ItemKind (name),
+    seeds   text null references ItemKind (name)
 );
 
 create table Crop
@@ -429,11 +507,12 @@ create table Corpse
 
 create table CreatureKind
 (
-    id     integer primary key,
-    name   text not null unique,
-    animal text not null references AnimalKind (name),
-    body   text not null references BodyKind (name),
-    corpse text not null references CorpseKind (name)
+    id         integer primary key,
+    name       text    not null unique,
+    animal     text    not null references AnimalKind (name),
+    body       text    not null references BodyKind (name),
+    corpse     text    not null references CorpseKind (name),
+    pollinator boolean not null default false
 );
 
 create table Creature
@@ -145,6 +145,20 @@ impl Storage {
         Ok(values)
     }
 
+    /// Inserts row into table of type, replacing existing row with the same primary key.
+    pub fn save<T, P: Params>(&self, columns: &[&str], values: P) -> rusqlite::Result<()> {
+        let table = std::any::type_name::<T>().split("::").last().unwrap();
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        let sql = format!(
+            "insert or replace into \"{}\" ({}) values ({})",
+            table,
+            columns.join(", "),
+            placeholders
+        );
+        self.connection.execute(&sql, values)?;
+        Ok(())
+    }
+
     fn query_sequence<T>(&self) -> usize {
         let table = std::any::type_name::<T>().split("::").last().unwrap();
         let mut statement = self
@@ -3,6 +3,9 @@ use crate::inventory::ItemId;
 use crate::model::{Creature, Farmer, Stack};
 use crate::{emit, Game};
 
+/// Hunger satisfied by single unit of food of normal quality.
+const FOOD_PORTION: f32 = 0.1;
+
 impl Game {
     pub fn eat_food_from_stack(
         &mut self,
@@ -12,8 +15,10 @@ impl Game {
     ) -> Result<Vec<Event>, ActionError> {
         let position = self.physics.get_barrier(stack.barrier)?.position;
         self.ensure_target_reachable(creature.body, position)?;
+        let container = self.inventory.get_container(stack.container)?;
+        let food = FOOD_PORTION * container.items[container.ensure_item_at(-1)?].quality;
         let decrease_item = self.inventory.decrease_container_item(stack.container)?;
-        let feed_animal = self.raising.feed_animal(creature.animal, 0.1)?;
+        let feed_animal = self.raising.feed_animal(creature.animal, food)?;
         let stop_body = self.physics.stop_body(creature.body)?;
         emit![stop_body(), decrease_item(), feed_animal()]
     }
@@ -26,8 +31,9 @@ impl Game {
     ) -> Result<Vec<Event>, ActionError> {
         let position = self.physics.get_body(farmer.body)?.position;
         self.ensure_target_reachable(creature.body, position)?;
+        let food = FOOD_PORTION * self.inventory.get_container_item(farmer.hands)?.quality;
         let decrease_item = self.inventory.decrease_container_item(farmer.hands)?;
-        let feed_animal = self.raising.feed_animal(creature.animal, 0.1)?;
+        let feed_animal = self.raising.feed_animal(creature.animal, food)?;
         let stop_body = self.physics.stop_body(creature.body)?;
         emit![stop_body(), decrease_item(), feed_animal()]
     }
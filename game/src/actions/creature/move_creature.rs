@@ -12,7 +12,9 @@ impl Game {
         let trigger_behaviour =
             self.raising
                 .trigger_behaviour(creature.animal, Behaviour::Walking, Behaviour::Idle)?;
-        let move_body = self.physics.move_body(creature.body, destination)?;
+        let move_body = self
+            .physics
+            .move_body_along_path(creature.body, destination)?;
         emit![trigger_behaviour(), move_body()]
     }
 }
@@ -0,0 +1,56 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::{FunctionsQuery, Item, ItemId};
+use crate::model::{Activity, Farmer, Farmland, Tree, TreeKind};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn chop_tree(
+        &mut self,
+        farmer: Farmer,
+        farmland: Farmland,
+        tree: Tree,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Usage)?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        item.kind.functions.as_axe()?;
+        let position = self.physics.get_barrier(tree.barrier)?.position;
+        self.ensure_target_reachable(farmer.body, position)?;
+        let tree_kind = self.known.trees.get(tree.kind)?;
+        let growth = self.planting.get_plant(tree.plant)?.growth;
+
+        // tree barrier is destroyed within the same action, so it is the only allowed overlap
+        self.ensure_items_droppable(farmland.space, position, tree.barrier)?;
+
+        let destroy_tree_barrier = self.physics.destroy_barrier(tree.barrier)?;
+        let (_residue, destroy_plant) = self.planting.destroy_plant(tree.plant)?;
+        let mut events = occur![
+            destroy_tree_barrier(),
+            destroy_plant(),
+            self.universe.vanish_tree(tree),
+        ];
+
+        let container = self.get_drop_container(farmland.space, position);
+        let [wood, sapling] = self.inventory.items_id.introduce().many(ItemId);
+        let mut items = vec![Item {
+            id: wood,
+            kind: tree_kind.wood.clone(),
+            container,
+            quantity: tree_kind.get_wood_quantity(growth),
+            quality: 1.0,
+        }];
+        if let Some(sapling_kind) = &tree_kind.sapling {
+            if tree_kind.get_maturity(growth) >= 1.0 {
+                items.push(Item {
+                    id: sapling,
+                    kind: sapling_kind.clone(),
+                    container,
+                    quantity: TreeKind::SAPLINGS.min(sapling_kind.max_quantity),
+                    quality: 1.0,
+                });
+            }
+        }
+        events.extend(self.drop_items(farmland.space, position, container, items)?);
+
+        Ok(events)
+    }
+}
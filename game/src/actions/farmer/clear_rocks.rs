@@ -0,0 +1,50 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::{ContainerId, FunctionsQuery, Item, ItemId};
+use crate::math::{Rotation, Tile, TileMath};
+use crate::model::{Farmer, Farmland};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn clear_rocks(
+        &mut self,
+        farmer: Farmer,
+        farmland: Farmland,
+        place: Tile,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.ensure_target_reachable(farmer.body, place.position())?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        item.kind.functions.as_shovel()?;
+        let clear_rocks = self.landscaping.clear_rocks(farmland.land, place)?;
+
+        let barrier_kind = self.known.barriers.find("<drop>")?;
+        let (barrier, create_barrier) = self.physics.create_barrier(
+            farmland.space,
+            barrier_kind,
+            place.position(),
+            Rotation::A000,
+            true,
+            false,
+        )?;
+        let item_kind = self.known.items.find("stones")?;
+        let container_kind = self.known.containers.find("<drop>")?;
+        let container = self.inventory.containers_id.introduce().one(ContainerId);
+        let item = self.inventory.items_id.introduce().one(ItemId);
+        let items = vec![Item {
+            id: item,
+            kind: item_kind,
+            container,
+            quantity: 1,
+            quality: 1.0,
+        }];
+        let create_stones = self
+            .inventory
+            .add_container(container, &container_kind, items)?;
+
+        Ok(occur![
+            clear_rocks(),
+            create_barrier(),
+            create_stones(),
+            self.appear_stack(container, barrier),
+        ])
+    }
+}
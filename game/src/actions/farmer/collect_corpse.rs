@@ -19,9 +19,9 @@ impl Game {
         let corpse_kind = self.known.corpses.get(corpse.key)?;
         let destroy_barrier = self.physics.destroy_barrier(corpse.barrier)?;
         let item = self.inventory.items_id.introduce().one(ItemId);
-        let create_item = self
-            .inventory
-            .create_item(item, &corpse_kind.item, farmer.hands, 1)?;
+        let create_item =
+            self.inventory
+                .create_item(item, &corpse_kind.item, farmer.hands, 1, 1.0)?;
 
         let events = occur![
             self.universe.vanish_corpse(corpse),
@@ -1,6 +1,7 @@
 use crate::api::{ActionError, Event};
 use crate::inventory::{ContainerId, Item, ItemId};
-use crate::math::{Tile, TileMath};
+use crate::landscaping::Surface;
+use crate::math::{Rotation, Tile, TileMath};
 use crate::model::{Farmer, Farmland};
 use crate::{occur, Game};
 
@@ -14,6 +15,9 @@ impl Game {
         self.ensure_target_reachable(farmer.body, place.position())?;
         let quality = 0.05;
         let land = self.landscaping.get_land(farmland.land)?;
+        if land.ensure_surface(place, Surface::ROCKS).is_ok() {
+            return self.clear_rocks(farmer, farmland, place);
+        }
         let capacity = land.get_moisture_capacity(place)?;
         let is_stones_gathered = capacity >= 0.55 && capacity < 0.6;
         let is_basin_generated = capacity == 1.0;
@@ -24,6 +28,7 @@ impl Game {
                 farmland.space,
                 barrier_kind,
                 place.position(),
+                Rotation::A000,
                 true,
                 false,
             )?;
@@ -37,6 +42,7 @@ impl Game {
                 kind: item_kind,
                 container,
                 quantity: 1,
+                quality: 1.0,
             }];
             let create_stones = self
                 .inventory
@@ -1,5 +1,5 @@
 use crate::api::{ActionError, Event};
-use crate::inventory::{ContainerId, FunctionsQuery, Item, ItemId};
+use crate::inventory::{FunctionsQuery, Item, ItemId};
 use crate::model::{Crop, Farmer, Farmland};
 use crate::{occur, Game};
 
@@ -16,39 +16,31 @@ impl Game {
         self.ensure_target_reachable(farmer.body, position)?;
         let crop_kind = self.known.crops.get(crop.key)?;
 
-        // TODO: transactional
-        let destroy_plant_barrier = self.physics.destroy_barrier(crop.barrier)?();
-        self.physics.destroy_sensor(crop.sensor)?();
+        // crop barrier is destroyed within the same action, so it is the only allowed overlap
+        self.ensure_items_droppable(farmland.space, position, crop.barrier)?;
 
-        let (residue, destroy_plant) = self.planting.destroy_plant(crop.plant)?;
+        self.physics.get_sensor(crop.sensor)?; // ensure sensor valid before barrier destroyed
 
-        let barrier_kind = self.known.barriers.find("<drop>").unwrap();
-        let (barrier, create_barrier) =
-            self.physics
-                .create_barrier(farmland.space, barrier_kind, position, true, false)?;
+        let (residue, destroy_plant) = self.planting.destroy_plant(crop.plant)?;
+        let destroy_plant_barrier = self.physics.destroy_barrier(crop.barrier)?;
+        let mut events = occur![
+            destroy_plant_barrier(),
+            destroy_plant(),
+            self.universe.vanish_crop(crop),
+        ];
+        let destroy_plant_sensor = self.physics.destroy_sensor(crop.sensor)?;
+        events.extend(occur![destroy_plant_sensor(),]);
+        self.unsubscribe_sensor(crop.sensor);
 
-        let quantity = 1 + (9.0 * residue / 5.0) as u8;
-        let container_kind = self.known.containers.find("<drop>").unwrap();
-        let container = self.inventory.containers_id.introduce().one(ContainerId);
-        let item = self.inventory.items_id.introduce().one(ItemId);
+        let container = self.get_drop_container(farmland.space, position);
         let items = vec![Item {
-            id: item,
+            id: self.inventory.items_id.introduce().one(ItemId),
             kind: crop_kind.residue.clone(),
             container,
-            quantity,
+            quantity: 1 + (9.0 * residue / 5.0) as u8,
+            quality: 1.0,
         }];
-        let create_residue = self
-            .inventory
-            .add_container(container, &container_kind, items)?;
-
-        let events = occur![
-            destroy_plant_barrier,
-            destroy_plant(),
-            self.universe.vanish_crop(crop),
-            create_barrier(),
-            create_residue(),
-            self.appear_stack(container, barrier),
-        ];
+        events.extend(self.drop_items(farmland.space, position, container, items)?);
 
         Ok(events)
     }
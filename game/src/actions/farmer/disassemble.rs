@@ -16,9 +16,9 @@ impl Game {
 
         let destroy_barrier = self.physics.destroy_barrier(cementer.barrier)?;
         let item = self.inventory.items_id.introduce().one(ItemId);
-        let create_kit = self
-            .inventory
-            .create_item(item, &cementer_kind.kit, farmer.hands, 1)?;
+        let create_kit =
+            self.inventory
+                .create_item(item, &cementer_kind.kit, farmer.hands, 1, 1.0)?;
 
         // TODO: destroy input + output + items
 
@@ -44,9 +44,9 @@ impl Game {
 
         let destroy_barrier = self.physics.destroy_barrier(composter.barrier)?;
         let item = self.inventory.items_id.introduce().one(ItemId);
-        let create_kit = self
-            .inventory
-            .create_item(item, &composter_kind.kit, farmer.hands, 1)?;
+        let create_kit =
+            self.inventory
+                .create_item(item, &composter_kind.kit, farmer.hands, 1, 1.0)?;
 
         // TODO: destroy input + output + items
 
@@ -74,7 +74,7 @@ impl Game {
         let item = self.inventory.items_id.introduce().one(ItemId);
         let create_kit = self
             .inventory
-            .create_item(item, &rest_kind.kit, farmer.hands, 1)?;
+            .create_item(item, &rest_kind.kit, farmer.hands, 1, 1.0)?;
 
         let events = occur![
             destroy_barrier(),
@@ -100,7 +100,7 @@ impl Game {
         let item = self.inventory.items_id.introduce().one(ItemId);
         let create_kit = self
             .inventory
-            .create_item(item, &door_kind.kit, farmer.hands, 1)?;
+            .create_item(item, &door_kind.kit, farmer.hands, 1, 1.0)?;
 
         let events = occur![
             destroy_barrier(),
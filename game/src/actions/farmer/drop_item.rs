@@ -1,8 +1,9 @@
 use crate::api::{ActionError, Event};
-use crate::inventory::ContainerId;
-use crate::math::TileMath;
+use crate::inventory::{ContainerId, Item};
+use crate::math::{Position, Rotation, TileMath};
 use crate::model::{Farmer, Farmland};
-use crate::{emit, Game};
+use crate::physics::SpaceId;
+use crate::{emit, occur, Game};
 
 impl Game {
     pub(crate) fn drop_item(
@@ -15,9 +16,14 @@ impl Game {
         let body = self.physics.get_body(farmer.body)?;
         let space = body.space;
         let barrier_kind = self.known.barriers.find("<drop>")?;
-        let (barrier, create_barrier) =
-            self.physics
-                .create_barrier(space, barrier_kind, tile.position(), true, false)?;
+        let (barrier, create_barrier) = self.physics.create_barrier(
+            space,
+            barrier_kind,
+            tile.position(),
+            Rotation::A000,
+            true,
+            false,
+        )?;
         let container_kind = self.known.containers.find("<drop>")?;
         let container = self.inventory.containers_id.introduce().one(ContainerId);
         let drop_item = self
@@ -29,4 +35,47 @@ impl Game {
             self.appear_stack(container, barrier)
         ]
     }
+
+    /// Returns container of stack lying at position or new one for items to be dropped.
+    pub(crate) fn get_drop_container(&mut self, space: SpaceId, position: Position) -> ContainerId {
+        match self.find_stack_at(space, position) {
+            Some(stack) => stack.container,
+            None => self.inventory.containers_id.introduce().one(ContainerId),
+        }
+    }
+
+    /// Puts items on ground at position, into stack already lying there if any.
+    ///
+    /// Items must be placed in container given by [Game::get_drop_container]
+    /// and drop must be validated by [Game::ensure_items_droppable] beforehand.
+    pub(crate) fn drop_items(
+        &mut self,
+        space: SpaceId,
+        position: Position,
+        container: ContainerId,
+        items: Vec<Item>,
+    ) -> Result<Vec<Event>, ActionError> {
+        if self.find_stack_at(space, position).is_some() {
+            let add_items = self.inventory.add_items(container, items)?;
+            return Ok(occur![add_items(),]);
+        }
+        let barrier_kind = self.known.barriers.find("<drop>")?;
+        let (barrier, create_barrier) = self.physics.create_barrier(
+            space,
+            barrier_kind,
+            position,
+            Rotation::A000,
+            true,
+            true,
+        )?;
+        let container_kind = self.known.containers.find("<drop>")?;
+        let create_container = self
+            .inventory
+            .add_container(container, &container_kind, items)?;
+        emit![
+            create_barrier(),
+            create_container(),
+            self.appear_stack(container, barrier)
+        ]
+    }
 }
@@ -12,11 +12,11 @@ impl Game {
         tile: Tile,
     ) -> Result<Vec<Event>, ActionError> {
         self.universe.ensure_activity(farmer, Activity::Usage)?;
-        self.ensure_target_reachable(farmer.body, tile.position())?;
         let item = self.inventory.get_container_item(farmer.hands)?;
-        let quality = item.kind.functions.as_fertilizer()?;
+        self.ensure_target_within_reach(farmer.body, tile.position(), item.kind.reach)?;
+        let nutrients = item.kind.functions.as_fertilizer()?;
         let decrease_item = self.inventory.decrease_container_item(farmer.hands)?;
-        let fertilize = self.planting.fertilize(farmland.soil, tile, quality)?;
+        let fertilize = self.planting.fertilize(farmland.soil, tile, nutrients)?;
         let events = occur![decrease_item(), fertilize(),];
         Ok(events)
     }
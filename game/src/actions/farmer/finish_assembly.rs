@@ -31,6 +31,7 @@ impl Game {
                     farmland.space,
                     door.barrier.clone(),
                     position,
+                    placement.rotation,
                     closed,
                     false,
                 )?;
@@ -56,6 +57,7 @@ impl Game {
                     farmland.space,
                     cementer.barrier.clone(),
                     position,
+                    placement.rotation,
                     true,
                     false,
                 )?;
@@ -94,6 +96,7 @@ impl Game {
                     farmland.space,
                     composter.barrier.clone(),
                     position,
+                    placement.rotation,
                     true,
                     false,
                 )?;
@@ -124,6 +127,7 @@ impl Game {
                     farmland.space,
                     rest.barrier.clone(),
                     position,
+                    placement.rotation,
                     true,
                     false,
                 )?;
@@ -1,18 +1,19 @@
 use crate::api::{ActionError, Event};
-use crate::inventory::{FunctionsQuery, InventoryError, ItemId};
+use crate::inventory::{FunctionsQuery, InventoryError, Item, ItemId};
 use crate::model::{Activity, Crop, CropKey, Farmer, Farmland};
+use crate::planting::PlantStage;
 use crate::{occur, Game};
 
 impl Game {
     pub(crate) fn harvest_crop(
         &mut self,
         farmer: Farmer,
-        _farmland: Farmland,
+        farmland: Farmland,
         crop: Crop,
     ) -> Result<Vec<Event>, ActionError> {
         let destination = self.physics.get_barrier(crop.barrier)?.position;
         self.ensure_target_reachable(farmer.body, destination)?;
-        let crop_kind = self.known.crops.get(crop.key).unwrap();
+        let crop_kind = self.known.crops.get(crop.key)?;
         let item_kind = &crop_kind.fruits;
         let (new_harvest, capacity) = match self.inventory.get_container_item(farmer.hands) {
             Ok(item) => {
@@ -24,19 +25,55 @@ impl Game {
             }
             _ => (true, item_kind.max_quantity),
         };
-        let (fruits, harvest) = self.planting.harvest_plant(crop.plant, capacity)?;
-        let events = if new_harvest {
-            let item = self.inventory.items_id.introduce().one(ItemId);
-            let create_item = self
-                .inventory
-                .create_item(item, item_kind, farmer.hands, fruits)?;
+        // late harvest gives seeds falling under crop, so overlapping with crop barrier only is expected
+        let overripe = self.planting.get_plant(crop.plant)?.stage == PlantStage::Overripe;
+        let seeds_kind = match (&crop_kind.seeds, overripe) {
+            (Some(seeds_kind), true) => {
+                self.ensure_items_droppable(farmland.space, destination, crop.barrier)?;
+                Some(seeds_kind.clone())
+            }
+            _ => None,
+        };
+        let (harvest, harvest_plant) = self.planting.harvest_plant(crop.plant, capacity)?;
+        let mut items_id = self.inventory.items_id.introduce();
+        let mut events = if new_harvest {
+            let item = items_id.one(ItemId);
+            let create_item = self.inventory.create_item(
+                item,
+                item_kind,
+                farmer.hands,
+                harvest.fruits,
+                harvest.quality,
+            )?;
             let change_activity = self.universe.change_activity(farmer, Activity::Usage);
-            occur![harvest(), create_item(), change_activity,]
+            occur![harvest_plant(), create_item(), change_activity,]
         } else {
-            let increase_item = self.inventory.increase_item(farmer.hands, fruits)?;
+            let increase_item =
+                self.inventory
+                    .increase_item(farmer.hands, harvest.fruits, harvest.quality)?;
 
-            occur![harvest(), increase_item(),]
+            occur![harvest_plant(), increase_item(),]
         };
+        if let (Some(seeds_kind), true) = (seeds_kind, harvest.seeds > 0) {
+            // repeated late harvest puts seeds into the stack already lying under crop
+            let container = self.get_drop_container(farmland.space, destination);
+            // seeds exceeding item max quantity are split into several items
+            let max_quantity = seeds_kind.max_quantity.max(1);
+            let mut seeds = harvest.seeds;
+            let mut items = vec![];
+            while seeds > 0 {
+                let quantity = seeds.min(max_quantity);
+                seeds -= quantity;
+                items.push(Item {
+                    id: items_id.one(ItemId),
+                    kind: seeds_kind.clone(),
+                    container,
+                    quantity,
+                    quality: harvest.quality,
+                });
+            }
+            events.extend(self.drop_items(farmland.space, destination, container, items)?);
+        }
         Ok(events)
     }
 }
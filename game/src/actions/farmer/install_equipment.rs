@@ -1,7 +1,7 @@
 use crate::api::{ActionError, Event};
 use crate::inventory::{FunctionsQuery, Installation};
-use crate::math::TileMath;
-use crate::model::{Activity, Farmer, Farmland, TheodoliteKey};
+use crate::math::{Rotation, TileMath};
+use crate::model::{Activity, Farmer, Farmland, IrrigatorKey, TheodoliteKey};
 use crate::{emit, Game};
 
 impl Game {
@@ -27,6 +27,7 @@ impl Game {
                     farmland.space,
                     kind.barrier.clone(),
                     position,
+                    Rotation::A000,
                     true,
                     false,
                 )?;
@@ -37,6 +38,18 @@ impl Game {
                     self.appear_theodolite(kind.id, surveyor, barrier)?
                 ]
             }
+            Installation::Irrigator(key) => {
+                let use_item = self.inventory.use_items_from(farmer.hands)?;
+                let kind = self.known.irrigators.get(IrrigatorKey(key))?;
+                let (irrigation, create_irrigation) =
+                    self.landscaping
+                        .create_irrigation(farmland.land, tile, &kind.irrigation)?;
+                emit![
+                    use_item(),
+                    create_irrigation(),
+                    self.appear_irrigator(kind.id, irrigation)?
+                ]
+            }
             Installation::Peg(_key) => {
                 unimplemented!()
             }
@@ -55,6 +68,7 @@ impl Game {
         //             farmland.space,
         //             equipment_kind.barrier.clone(),
         //             position,
+        //             Rotation::A000,
         //             true,
         //             false,
         //         )?;
@@ -77,6 +91,7 @@ impl Game {
         //             farmland.space,
         //             equipment_kind.barrier.clone(),
         //             position,
+        //             Rotation::A000,
         //             true,
         //             false,
         //         )?;
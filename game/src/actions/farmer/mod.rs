@@ -1,5 +1,7 @@
 mod build;
 mod cancel_assembly;
+mod chop_tree;
+mod clear_rocks;
 mod collect_corpse;
 mod dig_place;
 mod dig_up_crop;
@@ -12,8 +14,12 @@ mod harvest_crop;
 mod install_equipment;
 mod move_assembly;
 mod move_farmer;
+mod pave_place;
 mod plant_crop;
+mod plant_tree;
+mod plough_place;
 mod pour_water;
+mod push_creature;
 mod put_item_into;
 mod relax;
 mod remove_construction;
@@ -26,6 +32,7 @@ mod toggle_backpack;
 mod toggle_door;
 mod toggle_generic_device;
 mod toggle_surveying_option;
+mod treat_crop;
 mod uninstall_equipment;
 mod untie_creature;
 mod use_equipment;
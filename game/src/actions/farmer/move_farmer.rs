@@ -8,7 +8,9 @@ impl Game {
         farmer: Farmer,
         destination: [f32; 2],
     ) -> Result<Vec<Event>, ActionError> {
-        let move_body = self.physics.move_body(farmer.body, destination)?;
+        let move_body = self
+            .physics
+            .move_body_along_path(farmer.body, destination)?;
         emit![move_body()]
     }
 }
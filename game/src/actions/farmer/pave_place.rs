@@ -0,0 +1,23 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::FunctionsQuery;
+use crate::math::{Tile, TileMath};
+use crate::model::{Activity, Farmer, Farmland};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn pave_place(
+        &mut self,
+        farmer: Farmer,
+        farmland: Farmland,
+        place: Tile,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Usage)?;
+        self.ensure_target_reachable(farmer.body, place.position())?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        item.kind.functions.as_gravel()?;
+        let decrease_item = self.inventory.decrease_container_item(farmer.hands)?;
+        let pave_place = self.landscaping.pave_place(farmland.land, place)?;
+        let create_path = self.physics.create_path(farmland.space, place)?;
+        Ok(occur![decrease_item(), pave_place(), create_path(),])
+    }
+}
@@ -1,5 +1,6 @@
 use crate::api::{ActionError, Event};
 use crate::inventory::FunctionsQuery;
+use crate::landscaping::Surface;
 use crate::math::TileMath;
 use crate::model::{Activity, CropKey, Farmer, Farmland};
 use crate::{occur, Game};
@@ -14,6 +15,8 @@ impl Game {
         self.universe.ensure_activity(farmer, Activity::Usage)?;
         let destination = tile.position();
         self.ensure_target_reachable(farmer.body, destination)?;
+        let land = self.landscaping.get_land(farmland.land)?;
+        land.ensure_surface(tile, Surface::PLOUGHED)?;
         let item = self.inventory.get_container_item(farmer.hands)?;
         let key = item.kind.functions.as_seeds(CropKey)?;
         let kind = self.known.crops.get(key)?;
@@ -26,9 +29,9 @@ impl Game {
             position,
             false,
         )?;
-        let (plant, create_plant) = self
-            .planting
-            .create_plant(farmland.soil, &kind.plant, 0.0)?;
+        let (plant, create_plant) =
+            self.planting
+                .create_plant(farmland.soil, tile, &kind.plant, 0.0)?;
         let events = occur![
             decrease_item(),
             create_barrier_sensor(),
@@ -0,0 +1,43 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::FunctionsQuery;
+use crate::landscaping::Surface;
+use crate::math::{Rotation, TileMath};
+use crate::model::{Activity, Farmer, Farmland, TreeKey};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn plant_tree(
+        &mut self,
+        farmer: Farmer,
+        farmland: Farmland,
+        tile: [usize; 2],
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Usage)?;
+        let position = tile.position();
+        self.ensure_target_reachable(farmer.body, position)?;
+        let land = self.landscaping.get_land(farmland.land)?;
+        land.ensure_surface(tile, Surface::PLOUGHED)?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        let key = item.kind.functions.as_sapling(TreeKey)?;
+        let kind = self.known.trees.get(key)?;
+        let decrease_item = self.inventory.decrease_container_item(farmer.hands)?;
+        let (barrier, create_barrier) = self.physics.create_barrier(
+            farmland.space,
+            kind.barrier.clone(),
+            position,
+            Rotation::A000,
+            true,
+            false,
+        )?;
+        let (plant, create_plant) =
+            self.planting
+                .create_plant(farmland.soil, tile, &kind.plant, 0.0)?;
+        let events = occur![
+            decrease_item(),
+            create_barrier(),
+            create_plant(),
+            self.appear_tree(kind.id, barrier, plant)?,
+        ];
+        Ok(events)
+    }
+}
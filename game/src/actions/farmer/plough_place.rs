@@ -0,0 +1,21 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::FunctionsQuery;
+use crate::math::{Tile, TileMath};
+use crate::model::{Activity, Farmer, Farmland};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn plough_place(
+        &mut self,
+        farmer: Farmer,
+        farmland: Farmland,
+        place: Tile,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Usage)?;
+        self.ensure_target_reachable(farmer.body, place.position())?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        item.kind.functions.as_hoe()?;
+        let plough_place = self.landscaping.plough_place(farmland.land, place)?;
+        Ok(occur![plough_place(),])
+    }
+}
@@ -1,5 +1,3 @@
-use rand::thread_rng;
-
 use crate::api::{ActionError, Event};
 use crate::inventory::FunctionsQuery;
 use crate::math::{Tile, TileMath};
@@ -14,16 +12,15 @@ impl Game {
         place: Tile,
     ) -> Result<Vec<Event>, ActionError> {
         self.universe.ensure_activity(farmer, Activity::Usage)?;
-        self.ensure_target_reachable(farmer.body, place.position())?;
         let item = self.inventory.get_container_item(farmer.hands)?;
+        self.ensure_target_within_reach(farmer.body, place.position(), item.kind.reach)?;
         let nozzle = item.kind.functions.as_moistener()?;
-        let random = thread_rng();
         let pour_water = self.landscaping.pour_water(
             farmland.land,
             place,
             nozzle.pressure,
             nozzle.spread,
-            random,
+            &mut self.random.actions,
         )?;
         let events = occur![pour_water(),];
         Ok(events)
@@ -0,0 +1,25 @@
+use crate::api::{ActionError, Event};
+use crate::math::VectorMath;
+use crate::model::{Activity, Creature, Farmer};
+use crate::{emit, Game};
+
+/// Speed given to creature by farmer push, fades out by damping of creature body.
+const PUSH_SPEED: f32 = 3.0;
+
+impl Game {
+    pub(crate) fn push_creature(
+        &mut self,
+        farmer: Farmer,
+        creature: Creature,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Idle)?;
+        let position = self.physics.get_body(farmer.body)?.position;
+        let target = self.physics.get_body(creature.body)?.position;
+        self.ensure_target_reachable(farmer.body, target)?;
+
+        let impulse = position.direction_to(target).mul(PUSH_SPEED);
+        let push_body = self.physics.apply_impulse(creature.body, impulse)?;
+
+        emit![push_body()]
+    }
+}
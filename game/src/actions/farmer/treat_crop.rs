@@ -0,0 +1,22 @@
+use crate::api::{ActionError, Event};
+use crate::inventory::FunctionsQuery;
+use crate::model::{Activity, Crop, Farmer};
+use crate::{occur, Game};
+
+impl Game {
+    pub(crate) fn treat_crop(
+        &mut self,
+        farmer: Farmer,
+        crop: Crop,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Usage)?;
+        let item = self.inventory.get_container_item(farmer.hands)?;
+        let efficiency = item.kind.functions.as_pesticide()?;
+        let position = self.physics.get_barrier(crop.barrier)?.position;
+        self.ensure_target_within_reach(farmer.body, position, item.kind.reach)?;
+        let treat_plant = self.planting.treat_plant(crop.plant, efficiency)?;
+        let decrease_item = self.inventory.decrease_container_item(farmer.hands)?;
+        let events = occur![decrease_item(), treat_plant(),];
+        Ok(events)
+    }
+}
@@ -1,7 +1,10 @@
 use crate::api::{ActionError, Event};
 use crate::building::SurveyorId;
 use crate::inventory::ItemId;
-use crate::model::{Activity, Construction, Equipment, Farmer, Farmland, Purpose, Theodolite};
+use crate::math::TileMath;
+use crate::model::{
+    Activity, Construction, Equipment, Farmer, Farmland, Irrigator, Purpose, Theodolite,
+};
 use crate::{emit, Universe};
 use crate::{occur, Game};
 
@@ -9,35 +12,69 @@ impl Game {
     pub(crate) fn uninstall_theodolite(
         &mut self,
         farmer: Farmer,
-        farmland: Farmland,
+        _farmland: Farmland,
         theodolite: Theodolite,
     ) -> Result<Vec<Event>, ActionError> {
         self.universe.ensure_activity(farmer, Activity::Idle)?;
 
-        // TODO: transactional
-        let teardown = self.teardown_constructions(farmer, farmland, theodolite.surveyor)?;
-
+        let constructions = self.get_surveyor_constructions(theodolite.surveyor);
+        let containers = constructions
+            .iter()
+            .map(|construction| construction.container)
+            .collect();
+        let kind = self.known.theodolites.get(theodolite.key)?;
+        self.inventory.get_container(farmer.hands)?; // ensure hands valid before containers destroyed
         let destroy_surveyor = self.building.destroy_surveyor(theodolite.surveyor)?;
         let destroy_barrier = self.physics.destroy_barrier(theodolite.barrier)?;
-        let kind = self.known.theodolites.get(theodolite.key)?;
+        let destroy_containers = self.inventory.destroy_containers(containers, false)?;
+
+        let mut events = occur![
+            destroy_containers(),
+            constructions
+                .into_iter()
+                .map(|construction| self.universe.vanish_construction(construction))
+                .flatten()
+                .collect::<Vec<Universe>>(),
+            destroy_surveyor(),
+            destroy_barrier(),
+        ];
         let item = self.inventory.items_id.introduce().one(ItemId);
         let create_item = self
             .inventory
-            .create_item(item, &kind.item, farmer.hands, 1)?;
-        let vanish_theodolite = self.universe.vanish_theodolite(theodolite);
-        let change_activity = self.universe.change_activity(farmer, Activity::Usage);
-
-        let mut events = teardown;
+            .create_item(item, &kind.item, farmer.hands, 1, 1.0)?;
         events.extend(occur![
-            destroy_surveyor(),
-            destroy_barrier(),
             create_item(),
-            vanish_theodolite,
-            change_activity,
+            self.universe.vanish_theodolite(theodolite),
+            self.universe.change_activity(farmer, Activity::Usage),
         ]);
         Ok(events)
     }
 
+    pub(crate) fn uninstall_irrigator(
+        &mut self,
+        farmer: Farmer,
+        _farmland: Farmland,
+        irrigator: Irrigator,
+    ) -> Result<Vec<Event>, ActionError> {
+        self.universe.ensure_activity(farmer, Activity::Idle)?;
+        let place = self.landscaping.get_irrigation(irrigator.irrigation)?.place;
+        self.ensure_target_reachable(farmer.body, place.position())?;
+        let destroy_irrigation = self.landscaping.destroy_irrigation(irrigator.irrigation)?;
+        let kind = self.known.irrigators.get(irrigator.key)?;
+        let item = self.inventory.items_id.introduce().one(ItemId);
+        let create_item = self
+            .inventory
+            .create_item(item, &kind.item, farmer.hands, 1, 1.0)?;
+
+        let events = occur![
+            destroy_irrigation(),
+            create_item(),
+            self.universe.vanish_irrigator(irrigator),
+            self.universe.change_activity(farmer, Activity::Usage),
+        ];
+        Ok(events)
+    }
+
     pub(crate) fn uninstall_equipment(
         &mut self,
         farmer: Farmer,
@@ -54,7 +91,7 @@ impl Game {
                 let item = self.inventory.items_id.introduce().one(ItemId);
                 let create_item =
                     self.inventory
-                        .create_item(item, &equipment_kind.item, farmer.hands, 1)?;
+                        .create_item(item, &equipment_kind.item, farmer.hands, 1, 1.0)?;
                 let vanish_equipment = self.universe.vanish_equipment(equipment);
                 let change_activity = self.universe.change_activity(farmer, Activity::Usage);
                 emit![
@@ -68,36 +105,12 @@ impl Game {
         }
     }
 
-    fn teardown_constructions(
-        &mut self,
-        _farmer: Farmer,
-        _farmland: Farmland,
-        surveyor: SurveyorId,
-    ) -> Result<Vec<Event>, ActionError> {
-        let constructions: Vec<Construction> = self
-            .universe
+    fn get_surveyor_constructions(&self, surveyor: SurveyorId) -> Vec<Construction> {
+        self.universe
             .constructions
             .iter()
             .filter(|construction| construction.surveyor == surveyor)
             .cloned()
-            .collect();
-
-        let containers = constructions
-            .iter()
-            .map(|construction| construction.container)
-            .collect();
-
-        let destroy_containers = self.inventory.destroy_containers(containers, false)?;
-
-        let events = occur![
-            destroy_containers(),
-            constructions
-                .into_iter()
-                .map(|id| self.universe.vanish_construction(id))
-                .flatten()
-                .collect::<Vec<Universe>>(),
-        ];
-
-        Ok(events)
+            .collect()
     }
 }
@@ -11,7 +11,8 @@ impl Game {
     ) -> Result<Vec<Event>, ActionError> {
         // self.universe.ensure_activity(farmer, Idle)?;
         let destination = self.physics.get_barrier(theodolite.barrier)?.position;
-        self.ensure_target_reachable(farmer.body, destination)?;
+        let kind = self.known.theodolites.get(theodolite.key)?;
+        self.ensure_target_within_reach(farmer.body, destination, kind.reach)?;
         let activity = Activity::Surveying { theodolite };
         emit![self.universe.change_activity(farmer, activity)]
     }
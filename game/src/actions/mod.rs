@@ -3,3 +3,713 @@ pub use farmer::*;
 
 mod creature;
 mod farmer;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use datamap::Storage;
+
+    use crate::api::ActionError;
+    use crate::building::{GridKey, GridKind, Material};
+    use crate::collections::Shared;
+    use crate::inventory::{ContainerId, ContainerKey, ContainerKind, Function, Item, ItemId};
+    use crate::inventory::{InventoryError, ItemKey, ItemKind};
+    use crate::landscaping::{LandKey, LandKind, LandscapingError, Surface};
+    use crate::math::{ArrayIndex, Rotation, VectorMath};
+    use crate::model::{Activity, Corpse, CorpseKey, CorpseKind, Creature, CreatureKey};
+    use crate::model::{Crop, CropKey, CropKind};
+    use crate::model::{Farmer, FarmerKey, Farmland, FarmlandKey, PlayerId, TreeKey, TreeKind};
+    use crate::physics::{BarrierKey, BarrierKind, BodyId, BodyKey, BodyKind, SensorKey};
+    use crate::physics::{PhysicsError, SensorKind, SpaceKey, SpaceKind};
+    use crate::planting::{PlantId, PlantKey, PlantKind, PlantStage, PlantingError};
+    use crate::planting::{SoilKey, SoilKind};
+    use crate::raising::{AnimalId, TetherId};
+    use crate::timing::{CalendarKey, CalendarKind, MinGameMinute, Season};
+    use crate::{Game, SensorTrigger};
+
+    pub(crate) fn item_kind(key: usize, name: &str, functions: Vec<Function>) -> ItemKind {
+        ItemKind {
+            id: ItemKey(key),
+            name: name.to_string(),
+            stackable: false,
+            functions,
+            max_quantity: 1,
+            reach: 2.0,
+        }
+    }
+
+    pub(crate) fn prepare_game(hands_items: Vec<ItemKind>) -> (Game, Farmer, Farmland) {
+        let mut game = Game::new(Storage::open(":memory:").unwrap());
+        let space_kind = Shared::new(SpaceKind {
+            id: SpaceKey(1),
+            name: "space".to_string(),
+            bounds: [128.0, 128.0],
+        });
+        let (space, create_space) = game.physics.create_space(&space_kind).unwrap();
+        create_space();
+        for (key, name) in [(1, "<drop>"), (2, "<hands>")] {
+            let kind = ContainerKind {
+                id: ContainerKey(key),
+                name: name.to_string(),
+                capacity: 1,
+                filter: vec![],
+            };
+            game.known
+                .containers
+                .insert(ContainerKey(key), name.to_string(), kind);
+        }
+        let drop = BarrierKind {
+            id: BarrierKey(1),
+            name: "<drop>".to_string(),
+            bounds: [0.5, 0.5],
+        };
+        game.known
+            .barriers
+            .insert(BarrierKey(1), "<drop>".to_string(), drop);
+
+        let body_kind = Shared::new(BodyKind {
+            id: BodyKey(1),
+            name: "farmer".to_string(),
+            speed: 1.0,
+            radius: 0.25,
+            collision_layer: 0,
+            collision_mask: 0,
+            acceleration: 0.0,
+            damping: 4.0,
+        });
+        let body = BodyId(1);
+        game.physics
+            .create_body(body, space, body_kind, [1.5, 1.5])
+            .unwrap()();
+
+        let hands = ContainerId(1);
+        let items = hands_items
+            .into_iter()
+            .enumerate()
+            .map(|(index, kind)| Item {
+                id: ItemId(index + 1),
+                kind: Shared::new(kind),
+                container: hands,
+                quantity: 1,
+                quality: 1.0,
+            })
+            .collect();
+        let hands_kind = game.known.containers.find("<hands>").unwrap();
+        game.inventory
+            .add_container(hands, &hands_kind, items)
+            .unwrap()();
+
+        let farmer = Farmer {
+            id: 1,
+            kind: FarmerKey(1),
+            player: PlayerId(1),
+            body,
+            hands,
+            backpack: ContainerId(2),
+            tether: TetherId(1),
+        };
+        game.universe.farmers.push(farmer);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Idle);
+        let soil_kind = Shared::new(SoilKind {
+            id: SoilKey(1),
+            name: "soil".to_string(),
+            width: 128,
+            height: 128,
+            nutrients: [0.5, 0.5, 0.5],
+        });
+        let (soil, create_soil) = game.planting.create_soil(&soil_kind).unwrap();
+        create_soil();
+        let land_kind = Shared::new(LandKind {
+            id: LandKey(1),
+            name: "land".to_string(),
+            width: 128,
+            height: 128,
+        });
+        let (land, create_land) = game.landscaping.create_land(&land_kind).unwrap();
+        create_land();
+        let calendar_kind = Shared::new(CalendarKind {
+            id: CalendarKey(1),
+            name: "calendar".to_string(),
+            day_duration: MinGameMinute(24),
+            seasons: vec![Season {
+                duration: MinGameMinute(240),
+                key: "summer".to_string(),
+                temperature: 20.0,
+                weather: Default::default(),
+            }],
+        });
+        let (calendar, create_calendar) = game.timing.create_calendar(&calendar_kind).unwrap();
+        create_calendar();
+        let grid_kind = Shared::new(GridKind {
+            id: GridKey(1),
+            name: "grid".to_string(),
+            width: 128,
+            height: 128,
+        });
+        let (grid, create_grid) = game.building.create_grid(&grid_kind).unwrap();
+        create_grid();
+
+        let farmland = Farmland {
+            id: 1,
+            kind: FarmlandKey(1),
+            space,
+            soil,
+            grid,
+            land,
+            calendar,
+        };
+        game.universe.farmlands.push(farmland);
+        (game, farmer, farmland)
+    }
+
+    pub(crate) fn plant_crop(game: &mut Game, farmland: Farmland, position: [f32; 2]) -> Crop {
+        let barrier = Shared::new(BarrierKind {
+            id: BarrierKey(2),
+            name: "crop".to_string(),
+            bounds: [0.25, 0.25],
+        });
+        let sensor = Shared::new(SensorKind {
+            id: SensorKey(1),
+            name: "crop".to_string(),
+            radius: 0.5,
+            filter: vec![],
+        });
+        let (barrier, sensor, create_crop) = game
+            .physics
+            .create_barrier_sensor(farmland.space, &barrier, &sensor, position, false)
+            .unwrap();
+        create_crop();
+        let plant = Shared::new(PlantKind {
+            id: PlantKey(1),
+            name: "crop".to_string(),
+            growth: 1.0,
+            flexibility: 1.0,
+            transpiration: 1.0,
+            max_fruits: 3.0,
+            consumption: [0.0003, 0.0003, 0.0003],
+            restoration: [0.0, 0.0, 0.0],
+            stages: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            seasons: vec![],
+            temperature: [5.0, 35.0],
+            resistance: 0.5,
+            pollination: 0.0,
+        });
+        let (plant_id, create_plant) = game
+            .planting
+            .create_plant(farmland.soil, position.to_tile(), &plant, 0.0)
+            .unwrap();
+        create_plant();
+        let crop_kind = CropKind {
+            id: CropKey(1),
+            name: "crop".to_string(),
+            plant,
+            barrier: game.known.barriers.find("<drop>").unwrap(),
+            sensor: Shared::new(SensorKind {
+                id: SensorKey(1),
+                name: "crop".to_string(),
+                radius: 0.5,
+                filter: vec![],
+            }),
+            fruits: Shared::new(item_kind(2, "fruits", vec![])),
+            residue: Shared::new(item_kind(3, "residue", vec![Function::Residue])),
+            seeds: Some(Shared::new(item_kind(
+                4,
+                "seeds",
+                vec![Function::Seeding(1)],
+            ))),
+        };
+        game.known
+            .crops
+            .insert(CropKey(1), "crop".to_string(), crop_kind);
+        let crop = Crop {
+            id: 1,
+            key: CropKey(1),
+            plant: plant_id,
+            barrier,
+            sensor,
+        };
+        game.universe.crops.push(crop);
+        crop
+    }
+
+    #[test]
+    fn test_harvest_overripe_crop_drops_seeds() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.planting.get_plant_mut(crop.plant).unwrap().stage = PlantStage::Overripe;
+
+        game.harvest_crop(farmer, farmland, crop).unwrap();
+
+        let hands = game.inventory.get_container(farmer.hands).unwrap();
+        assert_eq!(hands.items[0].kind.name, "fruits");
+        assert_eq!(game.universe.stacks.len(), 1);
+        let stack = game.universe.stacks[0];
+        let seeds = game.inventory.get_container(stack.container).unwrap();
+        assert_eq!(seeds.items[0].kind.name, "seeds");
+    }
+
+    #[test]
+    fn test_late_harvests_share_seeds_stack() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        let crop_kind = game.known.crops.get(crop.key).unwrap();
+        crop_kind.fruits.clone().borrow_mut().max_quantity = 3;
+        crop_kind.seeds.clone().unwrap().borrow_mut().max_quantity = 2;
+        for _ in 0..2 {
+            let plant = game.planting.get_plant_mut(crop.plant).unwrap();
+            plant.stage = PlantStage::Overripe;
+            plant.fruits = 3.0;
+            game.harvest_crop(farmer, farmland, crop).unwrap();
+            game.inventory
+                .mut_container(farmer.hands)
+                .unwrap()
+                .items
+                .clear();
+        }
+
+        assert_eq!(game.universe.stacks.len(), 1);
+        let stack = game.universe.stacks[0];
+        let seeds = game.inventory.get_container(stack.container).unwrap();
+        let quantities: Vec<u8> = seeds.items.iter().map(|item| item.quantity).collect();
+        assert_eq!(quantities, vec![2, 1, 2, 1]);
+        let mut ids: Vec<ItemId> = seeds.items.iter().map(|item| item.id).collect();
+        ids.sort_by_key(|id| id.0);
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+    }
+
+    #[test]
+    fn test_treat_crop_consumes_pesticide() {
+        let pesticide = item_kind(5, "pesticide", vec![Function::Pesticide(0.5)]);
+        let (mut game, farmer, farmland) = prepare_game(vec![pesticide]);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.planting.get_plant_mut(crop.plant).unwrap().infection = 0.75;
+
+        game.treat_crop(farmer, crop).unwrap();
+
+        assert_eq!(game.planting.get_plant(crop.plant).unwrap().infection, 0.25);
+        let hands = game.inventory.get_container(farmer.hands).unwrap();
+        assert!(hands.items.is_empty());
+    }
+
+    #[test]
+    fn test_harvest_not_ripe_crop_fails() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.planting.get_plant_mut(crop.plant).unwrap().stage = PlantStage::Flowering;
+
+        let result = game.harvest_crop(farmer, farmland, crop);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Planting(
+                PlantingError::NotReadyToHarvest { .. }
+            ))
+        ));
+        assert!(game.universe.stacks.is_empty());
+    }
+
+    #[test]
+    fn test_dig_up_crop_unsubscribes_its_sensor() {
+        let shovel = item_kind(1, "shovel", vec![Function::Shovel]);
+        let (mut game, farmer, farmland) = prepare_game(vec![shovel]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.subscribe_sensor(crop.sensor, SensorTrigger::CROP);
+
+        game.dig_up_crop(farmer, farmland, crop).unwrap();
+
+        assert!(game.universe.crops.is_empty());
+        assert!(!game.sensor_triggers.contains_key(&crop.sensor));
+    }
+
+    #[test]
+    fn test_dig_up_crop_not_dropped_over_other_barrier() {
+        let shovel = item_kind(1, "shovel", vec![Function::Shovel]);
+        let (mut game, farmer, farmland) = prepare_game(vec![shovel]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        let kind = game.known.barriers.find("<drop>").unwrap();
+        let (other, create_other) = game
+            .physics
+            .create_barrier(farmland.space, kind, [2.4, 1.6], Rotation::A000, true, true)
+            .unwrap();
+        create_other();
+
+        let result = game.dig_up_crop(farmer, farmland, crop);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Physics(PhysicsError::BarrierCreationOverlaps { other: id })) if id == other
+        ));
+        assert_eq!(game.universe.crops, vec![crop]);
+        assert!(game.physics.get_barrier(crop.barrier).is_ok());
+        assert!(game.planting.get_plant(crop.plant).is_ok());
+    }
+
+    #[test]
+    fn test_crop_reachable_past_stack() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [3.5, 1.5]);
+        let container = game.get_drop_container(farmland.space, [2.5, 1.5]);
+        game.drop_items(farmland.space, [2.5, 1.5], container, vec![])
+            .unwrap();
+        let position = game.physics.get_barrier(crop.barrier).unwrap().position;
+
+        assert!(game.ensure_target_reachable(farmer.body, position).is_ok());
+    }
+
+    #[test]
+    fn test_crop_unreachable_past_other_barrier() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [3.5, 1.5]);
+        let kind = game.known.barriers.find("<drop>").unwrap();
+        let (_wall, create_wall) = game
+            .physics
+            .create_barrier(
+                farmland.space,
+                kind,
+                [2.5, 1.5],
+                Rotation::A000,
+                true,
+                false,
+            )
+            .unwrap();
+        create_wall();
+        let position = game.physics.get_barrier(crop.barrier).unwrap().position;
+
+        assert!(matches!(
+            game.ensure_target_reachable(farmer.body, position),
+            Err(ActionError::TargetUnreachable)
+        ));
+    }
+
+    #[test]
+    fn test_dig_up_crop_leaves_no_changes_if_planting_fails() {
+        let shovel = item_kind(1, "shovel", vec![Function::Shovel]);
+        let (mut game, farmer, farmland) = prepare_game(vec![shovel]);
+        let barrier = Shared::new(BarrierKind {
+            id: BarrierKey(2),
+            name: "crop".to_string(),
+            bounds: [0.25, 0.25],
+        });
+        let sensor = Shared::new(SensorKind {
+            id: SensorKey(1),
+            name: "crop".to_string(),
+            radius: 0.5,
+            filter: vec![],
+        });
+        let (barrier, sensor, create_crop) = game
+            .physics
+            .create_barrier_sensor(farmland.space, &barrier, &sensor, [2.5, 1.5], false)
+            .unwrap();
+        create_crop();
+        let plant = Shared::new(PlantKind {
+            id: PlantKey(1),
+            name: "crop".to_string(),
+            growth: 1.0,
+            flexibility: 1.0,
+            transpiration: 1.0,
+            max_fruits: 1.0,
+            consumption: [0.0003, 0.0003, 0.0003],
+            restoration: [0.0, 0.0, 0.0],
+            stages: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            seasons: vec![],
+            temperature: [5.0, 35.0],
+            resistance: 0.5,
+            pollination: 0.0,
+        });
+        let crop_kind = CropKind {
+            id: CropKey(1),
+            name: "crop".to_string(),
+            plant,
+            barrier: game.known.barriers.find("<drop>").unwrap(),
+            sensor: Shared::new(SensorKind {
+                id: SensorKey(1),
+                name: "crop".to_string(),
+                radius: 0.5,
+                filter: vec![],
+            }),
+            fruits: Shared::new(item_kind(2, "fruits", vec![])),
+            residue: Shared::new(item_kind(3, "residue", vec![Function::Residue])),
+            seeds: None,
+        };
+        game.known
+            .crops
+            .insert(CropKey(1), "crop".to_string(), crop_kind);
+        // plant is not created to fail in planting domain after physics validation
+        let crop = Crop {
+            id: 1,
+            key: CropKey(1),
+            plant: PlantId(1),
+            barrier,
+            sensor,
+        };
+        game.universe.crops.push(crop);
+
+        let result = game.dig_up_crop(farmer, farmland, crop);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Planting(PlantingError::PlantNotFound { .. }))
+        ));
+        assert!(game.physics.get_barrier(barrier).is_ok());
+        assert!(game.physics.get_sensor(sensor).is_ok());
+        assert_eq!(game.physics.barriers[&farmland.space].len(), 1);
+        assert_eq!(game.universe.crops, vec![crop]);
+        assert!(game.universe.stacks.is_empty());
+        assert_eq!(game.inventory.containers.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_corpse_leaves_no_changes_if_inventory_fails() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let corpse_kind = CorpseKind {
+            id: CorpseKey(1),
+            name: "corpse".to_string(),
+            barrier: game.known.barriers.find("<drop>").unwrap(),
+            item: Shared::new(item_kind(1, "corpse", vec![])),
+        };
+        let barrier_kind = corpse_kind.barrier.clone();
+        game.known
+            .corpses
+            .insert(CorpseKey(1), "corpse".to_string(), corpse_kind);
+        let (barrier, create_barrier) = game
+            .physics
+            .create_barrier(
+                farmland.space,
+                barrier_kind,
+                [2.5, 1.5],
+                Rotation::A000,
+                true,
+                false,
+            )
+            .unwrap();
+        create_barrier();
+        let corpse = Corpse {
+            id: 1,
+            key: CorpseKey(1),
+            barrier,
+        };
+        game.universe.corpses.push(corpse);
+        // hands lost to fail in inventory domain after physics validation
+        game.inventory.containers.remove(&farmer.hands);
+
+        let result = game.collect_corpse(farmer, farmland, corpse);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Inventory(
+                InventoryError::ContainerNotFound { .. }
+            ))
+        ));
+        assert!(game.physics.get_barrier(barrier).is_ok());
+        assert_eq!(game.universe.corpses, vec![corpse]);
+        assert_eq!(
+            game.universe.get_farmer_activity(farmer).unwrap(),
+            Activity::Idle
+        );
+    }
+
+    pub(crate) fn prepare_tree_kind(game: &mut Game) {
+        let plant = Shared::new(PlantKind {
+            id: PlantKey(2),
+            name: "tree".to_string(),
+            growth: 10.0,
+            flexibility: 0.0,
+            transpiration: 1.0,
+            max_fruits: 0.0,
+            consumption: [0.0, 0.0, 0.0],
+            restoration: [0.0, 0.0, 0.0],
+            stages: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            seasons: vec![],
+            temperature: [-20.0, 40.0],
+            resistance: 1.0,
+            pollination: 0.0,
+        });
+        let mut wood = item_kind(6, "wood", vec![Function::Material(Material::WOOD)]);
+        wood.max_quantity = 10;
+        let mut sapling = item_kind(7, "sapling", vec![Function::Sapling(1)]);
+        sapling.max_quantity = 5;
+        let tree_kind = TreeKind {
+            id: TreeKey(1),
+            name: "tree".to_string(),
+            barrier: game.known.barriers.find("<drop>").unwrap(),
+            plant,
+            wood: Shared::new(wood),
+            sapling: Some(Shared::new(sapling)),
+        };
+        game.known
+            .trees
+            .insert(TreeKey(1), "tree".to_string(), tree_kind);
+    }
+
+    fn plough_place(game: &mut Game, farmland: Farmland, place: [usize; 2]) {
+        game.landscaping.plough_place(farmland.land, place).unwrap()();
+    }
+
+    #[test]
+    fn test_plant_sapling_grows_tree() {
+        let sapling = item_kind(7, "sapling", vec![Function::Sapling(1)]);
+        let (mut game, farmer, farmland) = prepare_game(vec![sapling]);
+        prepare_tree_kind(&mut game);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+
+        plough_place(&mut game, farmland, [2, 1]);
+        game.plant_tree(farmer, farmland, [2, 1]).unwrap();
+
+        let tree = game.universe.trees[0];
+        assert_eq!(tree.kind, TreeKey(1));
+        let plant = game.planting.get_plant(tree.plant).unwrap();
+        assert_eq!(plant.stage, PlantStage::Sprout);
+        let barrier = game.physics.get_barrier(tree.barrier).unwrap();
+        assert_eq!(barrier.position, [2.5, 1.5]);
+        let hands = game.inventory.get_container(farmer.hands).unwrap();
+        assert!(hands.items.is_empty());
+    }
+
+    #[test]
+    fn test_chop_mature_tree_drops_wood_and_saplings() {
+        let sapling = item_kind(7, "sapling", vec![Function::Sapling(1)]);
+        let axe = item_kind(8, "axe", vec![Function::Axe]);
+        let (mut game, farmer, farmland) = prepare_game(vec![sapling]);
+        prepare_tree_kind(&mut game);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+        plough_place(&mut game, farmland, [2, 1]);
+        game.plant_tree(farmer, farmland, [2, 1]).unwrap();
+        let tree = game.universe.trees[0];
+        game.inventory
+            .create_item(ItemId(10), &Shared::new(axe), farmer.hands, 1, 1.0)
+            .unwrap()();
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+        game.planting.get_plant_mut(tree.plant).unwrap().growth = 2.5;
+
+        game.chop_tree(farmer, farmland, tree).unwrap();
+
+        assert!(game.universe.trees.is_empty());
+        assert!(game.planting.get_plant(tree.plant).is_err());
+        assert!(game.physics.get_barrier(tree.barrier).is_err());
+        let stack = game.universe.stacks[0];
+        let drop = game.inventory.get_container(stack.container).unwrap();
+        assert_eq!(drop.items[0].kind.name, "wood");
+        assert_eq!(drop.items[0].quantity, 10);
+        assert_eq!(drop.items[1].kind.name, "sapling");
+        assert_eq!(drop.items[1].quantity, TreeKind::SAPLINGS);
+    }
+
+    #[test]
+    fn test_chop_tree_requires_axe() {
+        let saplings = vec![
+            item_kind(7, "sapling", vec![Function::Sapling(1)]),
+            item_kind(7, "sapling", vec![Function::Sapling(1)]),
+        ];
+        let (mut game, farmer, farmland) = prepare_game(saplings);
+        prepare_tree_kind(&mut game);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+        plough_place(&mut game, farmland, [2, 1]);
+        game.plant_tree(farmer, farmland, [2, 1]).unwrap();
+        let tree = game.universe.trees[0];
+
+        let result = game.chop_tree(farmer, farmland, tree);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Inventory(InventoryError::ItemFunctionNotFound))
+        ));
+        assert_eq!(game.universe.trees, vec![tree]);
+    }
+
+    #[test]
+    fn test_plant_sapling_requires_ploughed_place() {
+        let sapling = item_kind(7, "sapling", vec![Function::Sapling(1)]);
+        let (mut game, farmer, farmland) = prepare_game(vec![sapling]);
+        prepare_tree_kind(&mut game);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+
+        let result = game.plant_tree(farmer, farmland, [2, 1]);
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Landscaping(
+                LandscapingError::InvalidLandSurface { .. }
+            ))
+        ));
+        assert!(game.universe.trees.is_empty());
+    }
+
+    #[test]
+    fn test_pave_place_creates_path() {
+        let gravel = item_kind(9, "gravel", vec![Function::Gravel]);
+        let (mut game, farmer, farmland) = prepare_game(vec![gravel]);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+
+        game.pave_place(farmer, farmland, [2, 1]).unwrap();
+
+        let land = game.landscaping.get_land(farmland.land).unwrap();
+        assert_eq!(land.surface[[2, 1].fit(land.kind.width)], Surface::GRAVEL);
+        let space = game.physics.get_space(farmland.space).unwrap();
+        assert!(space.is_path([2.5, 1.5]));
+        let hands = game.inventory.get_container(farmer.hands).unwrap();
+        assert!(hands.items.is_empty());
+    }
+
+    #[test]
+    fn test_clear_rocks_drops_stones() {
+        let shovel = item_kind(10, "shovel", vec![Function::Shovel]);
+        let (mut game, farmer, farmland) = prepare_game(vec![shovel]);
+        let stones = item_kind(11, "stones", vec![Function::Stone]);
+        game.known
+            .items
+            .insert(ItemKey(11), "stones".to_string(), stones);
+        let land = game.landscaping.get_land_mut(farmland.land).unwrap();
+        let place = [2, 1].fit(land.kind.width);
+        land.surface[place] = Surface::ROCKS;
+
+        game.dig_place(farmer, farmland, [2, 1]).unwrap();
+
+        let land = game.landscaping.get_land(farmland.land).unwrap();
+        assert_eq!(land.surface[place], Surface::PLAINS);
+        let stack = game.universe.stacks[0];
+        let drop = game.inventory.get_container(stack.container).unwrap();
+        assert_eq!(drop.items[0].kind.name, "stones");
+    }
+
+    #[test]
+    fn test_push_creature_away_from_farmer() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let kind = game.physics.get_body(farmer.body).unwrap().kind.clone();
+        let body = BodyId(2);
+        game.physics
+            .create_body(body, farmland.space, kind, [2.5, 1.5])
+            .unwrap()();
+        let creature = Creature {
+            id: 1,
+            key: CreatureKey(1),
+            body,
+            animal: AnimalId(1),
+        };
+
+        let events = game.push_creature(farmer, creature).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let body = game.physics.get_body(body).unwrap();
+        assert_eq!(body.impulse, [3.0, 0.0]);
+        game.physics.update(0.1);
+        let body = game.physics.get_body(creature.body).unwrap();
+        assert!(body.position[0] > 2.5);
+    }
+}
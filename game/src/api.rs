@@ -3,15 +3,15 @@ use std::fmt::Debug;
 use crate::assembling::{Assembling, AssemblingError, Rotation};
 use crate::building::{Building, BuildingError, SurveyorId};
 use crate::collections::DictionaryError;
-use crate::inventory::{ContainerId, Inventory, InventoryError, ItemId};
+use crate::inventory::{ContainerId, Inventory, InventoryError, ItemData, ItemId, ItemKey};
 use crate::landscaping::{Landscaping, LandscapingError};
-use crate::math::Tile;
+use crate::math::{Position, Tile};
 use crate::model::{
-    Cementer, Composter, Construction, Corpse, Creature, Crop, Door, Equipment, Farmer, Rest,
-    Stack, Theodolite, Universe, UniverseError,
+    Cementer, Composter, Construction, Corpse, Creature, Crop, Door, Equipment, Farmer, Farmland,
+    Irrigator, Rest, Stack, Theodolite, Tree, Universe, UniverseError,
 };
-use crate::physics::{Physics, PhysicsError};
-use crate::planting::{Planting, PlantingError};
+use crate::physics::{Physics, PhysicsError, SpaceId};
+use crate::planting::{PlantStage, Planting, PlantingError};
 use crate::raising::{Raising, RaisingError, TetherId};
 use crate::timing::{Timing, TimingError};
 use crate::working::{DeviceId, Working, WorkingError};
@@ -33,6 +33,10 @@ pub enum PlayerRequest {
         action_id: usize,
         action: Action,
     },
+    Query {
+        query_id: usize,
+        query: Query,
+    },
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -51,6 +55,14 @@ pub enum GameResponse {
     Login {
         result: LoginResult,
     },
+    View {
+        query_id: usize,
+        view: View,
+    },
+    QueryError {
+        query_id: usize,
+        error: ActionError,
+    },
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -60,7 +72,7 @@ pub enum LoginResult {
     InvalidPassword,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum Action {
     EatFoodFromStack {
         creature: Creature,
@@ -91,7 +103,87 @@ pub enum Action {
     },
 }
 
+/// Read-only request to game state.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum Query {
+    EntitiesWithin {
+        space: SpaceId,
+        min: Position,
+        max: Position,
+    },
+    CropsByGrowth {
+        stage: PlantStage,
+    },
+    ContainersHolding {
+        item: ItemKey,
+    },
+    AnimalsByHealth {
+        min: f32,
+        max: f32,
+    },
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub enum View {
+    Entities(Vec<EntityView>),
+    Crops(Vec<CropView>),
+    Containers(Vec<ContainerView>),
+    Animals(Vec<AnimalView>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Entity {
+    Farmland(Farmland),
+    Farmer(Farmer),
+    Creature(Creature),
+    Tree(Tree),
+    Crop(Crop),
+    Corpse(Corpse),
+    Stack(Stack),
+    Equipment(Equipment),
+    Theodolite(Theodolite),
+    Door(Door),
+    Rest(Rest),
+    Cementer(Cementer),
+    Composter(Composter),
+    Irrigator(Irrigator),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct EntityView {
+    pub entity: Entity,
+    pub position: Position,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CropView {
+    pub entity: Crop,
+    pub position: Position,
+    pub growth: f32,
+    pub health: f32,
+    pub thirst: f32,
+    pub hunger: f32,
+    pub fruits: f32,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ContainerView {
+    pub id: ContainerId,
+    pub items: Vec<ItemData>,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct AnimalView {
+    pub entity: Creature,
+    pub position: Position,
+    pub health: f32,
+    pub hunger: f32,
+    pub thirst: f32,
+    pub age: f32,
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum Cheat {
     GrowthUpCrops { radius: f32, growth: f32 },
     SetCreaturesHealth { radius: f32, health: f32 },
@@ -112,6 +204,9 @@ pub enum FarmerBound {
     UninstallTheodolite {
         theodolite: Theodolite,
     },
+    UninstallIrrigator {
+        irrigator: Irrigator,
+    },
     UseEquipment {
         equipment: Equipment,
     },
@@ -181,9 +276,18 @@ pub enum FarmerBound {
     WaterCrop {
         crop: Crop,
     },
+    TreatCrop {
+        crop: Crop,
+    },
     HarvestCrop {
         crop: Crop,
     },
+    PlantTree {
+        tile: [usize; 2],
+    },
+    ChopTree {
+        tree: Tree,
+    },
     StartAssembly {
         pivot: [usize; 2],
         rotation: Rotation,
@@ -224,6 +328,12 @@ pub enum FarmerBound {
     FillBasin {
         place: Tile,
     },
+    PloughPlace {
+        place: Tile,
+    },
+    PavePlace {
+        place: Tile,
+    },
     PourWater {
         place: Tile,
     },
@@ -239,6 +349,9 @@ pub enum FarmerBound {
     TieCreature {
         creature: Creature,
     },
+    PushCreature {
+        creature: Creature,
+    },
     UntieCreature {
         creature: Creature,
     },
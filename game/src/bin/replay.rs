@@ -0,0 +1,50 @@
+use std::env;
+use std::process::exit;
+
+use datamap::Storage;
+use game::journal::{Journal, JournalError};
+use game::Game;
+
+/// Reproduces recorded game session.
+///
+/// Usage: replay <save_file> <journal_file>
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (save_file, journal_file) = match (args.get(1), args.get(2)) {
+        (Some(save_file), Some(journal_file)) => (save_file.clone(), journal_file.clone()),
+        _ => {
+            eprintln!("Usage: replay <save_file> <journal_file>");
+            exit(2);
+        }
+    };
+
+    let storage = Storage::open(&save_file).unwrap();
+    let mut game = Game::new(storage);
+    game.load_game_full();
+    let records = match Journal::read(&journal_file) {
+        Ok(records) => records,
+        Err(error) => {
+            eprintln!("Unable to read journal {journal_file}: {error:?}");
+            exit(1);
+        }
+    };
+    let total = records.len();
+    match game.replay(records) {
+        Ok(replayed) => println!("Replayed {replayed}/{total} records, no desync detected"),
+        Err(JournalError::Desync {
+            position,
+            tick,
+            events,
+        }) => {
+            eprintln!("Desync at record {position} (tick {tick}), actual events:");
+            for event in events {
+                eprintln!("  {event:?}");
+            }
+            exit(1);
+        }
+        Err(error) => {
+            eprintln!("Replay failed: {error:?}");
+            exit(1);
+        }
+    }
+}
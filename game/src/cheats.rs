@@ -2,6 +2,7 @@ use crate::api::{ActionError, Event};
 use crate::math::{TileMath, VectorMath};
 use crate::model::{Farmer, Farmland};
 use crate::physics::BodyId;
+use crate::raising::Sex;
 use crate::{emit, Game};
 
 impl Game {
@@ -36,7 +37,10 @@ impl Game {
             creature_kind.body.clone(),
             tile.position(),
         )?;
-        let (animal, create_animal) = self.raising.create_animal(creature_kind.animal.clone())?;
+        let sex = Sex::random(&mut self.random.actions);
+        let (animal, create_animal) = self
+            .raising
+            .create_animal(creature_kind.animal.clone(), sex)?;
 
         emit![
             create_body(),
@@ -116,6 +116,18 @@ where
         Ok(kind)
     }
 
+    pub fn find_optional_by(
+        &self,
+        row: &rusqlite::Row,
+        index: &str,
+    ) -> Result<Option<Shared<T>>, DataError> {
+        let name: Option<String> = row.get(index)?;
+        match name {
+            Some(name) => Ok(Some(self.find(&name)?)),
+            None => Ok(None),
+        }
+    }
+
     pub fn get_by<C>(
         &self,
         row: &rusqlite::Row,
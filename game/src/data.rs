@@ -1,9 +1,11 @@
 use core::fmt::Debug;
 
 use datamap::Storage;
-use log::info;
+use log::{error, info};
+use rusqlite::params;
 use serde::de;
 
+use crate::api::ActionError;
 use crate::assembling::{Placement, PlacementId};
 use crate::building::{
     Grid, GridId, GridKey, GridKind, Surveyor, SurveyorId, SurveyorKey, SurveyorKind,
@@ -12,24 +14,30 @@ use crate::collections::DictionaryError;
 use crate::inventory::{
     Container, ContainerId, ContainerKey, ContainerKind, Item, ItemId, ItemKey, ItemKind,
 };
-use crate::landscaping::{Land, LandId, LandKey, LandKind};
+use crate::landscaping::{
+    Irrigation, IrrigationId, IrrigationKey, IrrigationKind, Land, LandId, LandKey, LandKind,
+};
+use crate::math::{Random, Randomness, Rotation, VectorMath};
 use crate::model::{
     Assembly, AssemblyKey, AssemblyKind, AssemblyTarget, Cementer, CementerKey, CementerKind,
     Composter, ComposterKey, ComposterKind, Construction, Corpse, CorpseKey, CorpseKind, Creature,
     CreatureKey, CreatureKind, Crop, CropKey, CropKind, Door, DoorKey, DoorKind, Equipment,
     EquipmentKey, EquipmentKind, Farmer, FarmerKey, FarmerKind, Farmland, FarmlandKey,
-    FarmlandKind, Knowledge, Player, PlayerId, Purpose, PurposeDescription, Rest, RestKey,
-    RestKind, Stack, Theodolite, TheodoliteKey, TheodoliteKind, Tree, TreeKey, TreeKind,
+    FarmlandKind, Irrigator, IrrigatorKey, IrrigatorKind, Knowledge, Player, PlayerId, Purpose,
+    PurposeDescription, Rest, RestKey, RestKind, Stack, Theodolite, TheodoliteKey, TheodoliteKind,
+    Tree, TreeKey, TreeKind,
 };
 use crate::physics::{
     Barrier, BarrierId, BarrierKey, BarrierKind, Body, BodyId, BodyKey, BodyKind, Sensor, SensorId,
     SensorKey, SensorKind, Space, SpaceId, SpaceKey, SpaceKind,
 };
-use crate::planting::{Plant, PlantId, PlantKey, PlantKind, Soil, SoilId, SoilKey, SoilKind};
-use crate::raising::{Animal, AnimalId, AnimalKey, AnimalKind, Tether, TetherId};
-use crate::timing::{Calendar, CalendarId, CalendarKey, CalendarKind, MinGameMinute};
+use crate::planting::{
+    Plant, PlantId, PlantKey, PlantKind, PlantStress, Soil, SoilId, SoilKey, SoilKind,
+};
+use crate::raising::{Animal, AnimalId, AnimalKey, AnimalKind, Sex, Tether, TetherId};
+use crate::timing::{Calendar, CalendarId, CalendarKey, CalendarKind, MinGameMinute, Weather};
 use crate::working::{Device, DeviceId, DeviceKey, DeviceKind};
-use crate::Game;
+use crate::{Game, SensorTrigger};
 
 impl Game {
     pub fn load_knowledge(path: &str) -> Knowledge {
@@ -98,6 +106,11 @@ impl Game {
         for kind in storage.find_all(|row| self.load_land_kind(row))? {
             self.known.lands.insert(kind.id, kind.name.clone(), kind);
         }
+        for kind in storage.find_all(|row| self.load_irrigation_kind(row))? {
+            self.known
+                .irrigation
+                .insert(kind.id, kind.name.clone(), kind);
+        }
         // universe
         for kind in storage.find_all(|row| self.load_tree_kind(row))? {
             self.known.trees.insert(kind.id, kind.name.clone(), kind);
@@ -120,6 +133,11 @@ impl Game {
                 .theodolites
                 .insert(kind.id, kind.name.clone(), kind);
         }
+        for kind in storage.find_all(|row| self.load_irrigator_kind(row))? {
+            self.known
+                .irrigators
+                .insert(kind.id, kind.name.clone(), kind);
+        }
         for kind in storage.find_all(|row| self.load_crop_kind(row))? {
             self.known.crops.insert(kind.id, kind.name.clone(), kind);
         }
@@ -163,6 +181,10 @@ impl Game {
         info!("Starts game state loading from {}", self.storage.path);
         let storage = self.storage.open_into();
         self.players = storage.find_all(|row| self.load_player(row))?;
+        if let Some(randomness) = storage.find_all(|row| self.load_randomness(row))?.pop() {
+            self.random = randomness;
+        }
+        self.timing.load_seed(self.random.seed);
 
         // timing
         let (calendars, _) = storage.get_sequence(|row| self.load_calendar(row))?;
@@ -187,6 +209,8 @@ impl Game {
         // landscaping
         let (lands, _) = storage.get_sequence(|row| self.load_land(row))?;
         self.landscaping.load_lands(lands);
+        let (irrigation, sequence) = storage.get_sequence(|row| self.load_irrigation(row))?;
+        self.landscaping.load_irrigation(irrigation, sequence);
 
         // raising
         let (animals, sequence) = storage.get_sequence(|row| self.load_animal(row))?;
@@ -222,6 +246,8 @@ impl Game {
         let (theodolites, theodolites_id) =
             storage.get_sequence(|row| self.load_theodolite(row))?;
         self.universe.load_theodolites(theodolites, theodolites_id);
+        let (irrigators, id) = storage.get_sequence(|row| self.load_irrigator(row))?;
+        self.universe.load_irrigators(irrigators, id);
         let (farmers, farmers_id) = storage.get_sequence(|row| self.load_farmer(row))?;
         self.universe.load_farmers(farmers, farmers_id);
         let (stacks, stacks_id) = storage.get_sequence(|row| self.load_stack(row))?;
@@ -231,7 +257,11 @@ impl Game {
         let (equipments, id) = storage.get_sequence(|row| self.load_equipment(row))?;
         self.universe.load_equipments(equipments, id);
         let (crops, id) = storage.get_sequence(|row| self.load_crop(row))?;
+        for crop in &crops {
+            self.subscribe_sensor(crop.sensor, SensorTrigger::CROP);
+        }
         self.universe.load_crops(crops, id);
+        self.load_plants_places();
         let (corpses, id) = storage.get_sequence(|row| self.load_corpse(row))?;
         self.universe.load_corpses(corpses, id);
         let (creatures, id) = storage.get_sequence(|row| self.load_creature(row))?;
@@ -255,6 +285,63 @@ impl Game {
 
     pub fn save_game(&mut self) {}
 
+    /// Saves random streams of just loaded game, so starting save
+    /// with session journal reproduces the session.
+    ///
+    /// Must not be called after game update, random streams state
+    /// would not match world state of the save.
+    pub fn save_randomness(&self) -> Result<(), DataError> {
+        let random = &self.random;
+        self.storage.save::<Randomness, _>(
+            &[
+                "id",
+                "seed",
+                "actions",
+                "working",
+                "landscaping",
+                "raising",
+                "planting",
+            ],
+            params![
+                1,
+                random.seed as i64,
+                random.actions.state() as i64,
+                random.working.state() as i64,
+                random.landscaping.state() as i64,
+                random.raising.state() as i64,
+                random.planting.state() as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn load_plants_places(&mut self) {
+        let crops = self
+            .universe
+            .crops
+            .iter()
+            .map(|crop| (crop.plant, crop.barrier));
+        let trees = self
+            .universe
+            .trees
+            .iter()
+            .map(|tree| (tree.plant, tree.barrier));
+        let plants: Vec<(PlantId, BarrierId)> = crops.chain(trees).collect();
+        for (plant, barrier) in plants {
+            let result = self
+                .physics
+                .get_barrier(barrier)
+                .map_err(ActionError::from)
+                .and_then(|barrier| {
+                    let tile = barrier.position.to_tile();
+                    Ok(self.planting.load_plant_place(plant, tile)?)
+                });
+            if let Err(error) = result {
+                error!("Unable to restore place of plant {plant:?}, {error:?}");
+            }
+        }
+    }
+
     pub(crate) fn load_player(&mut self, row: &rusqlite::Row) -> Result<Player, DataError> {
         let data = Player {
             id: PlayerId(row.get("id")?),
@@ -263,6 +350,23 @@ impl Game {
         Ok(data)
     }
 
+    pub(crate) fn load_randomness(&mut self, row: &rusqlite::Row) -> Result<Randomness, DataError> {
+        let stream = |name: &str| -> Result<Random, DataError> {
+            let state: i64 = row.get(name)?;
+            Ok(Random::with_seed(state as u64))
+        };
+        let seed: i64 = row.get("seed")?;
+        let data = Randomness {
+            seed: seed as u64,
+            actions: stream("actions")?,
+            working: stream("working")?,
+            landscaping: stream("landscaping")?,
+            raising: stream("raising")?,
+            planting: stream("planting")?,
+        };
+        Ok(data)
+    }
+
     pub(crate) fn load_equipment_kind(
         &mut self,
         row: &rusqlite::Row,
@@ -331,6 +435,7 @@ impl Game {
             surveyor: self.known.surveyors.find_by(row, "surveyor")?,
             barrier: self.known.barriers.find_by(row, "barrier")?,
             item: self.known.items.find_by(row, "item")?,
+            reach: row.get("reach")?,
         };
         Ok(data)
     }
@@ -345,6 +450,28 @@ impl Game {
         Ok(data)
     }
 
+    pub(crate) fn load_irrigator_kind(
+        &mut self,
+        row: &rusqlite::Row,
+    ) -> Result<IrrigatorKind, DataError> {
+        let data = IrrigatorKind {
+            id: IrrigatorKey(row.get("id")?),
+            name: row.get("name")?,
+            irrigation: self.known.irrigation.find_by(row, "irrigation")?,
+            item: self.known.items.find_by(row, "item")?,
+        };
+        Ok(data)
+    }
+
+    pub(crate) fn load_irrigator(&mut self, row: &rusqlite::Row) -> Result<Irrigator, DataError> {
+        let data = Irrigator {
+            id: row.get("id")?,
+            key: IrrigatorKey(row.get("key")?),
+            irrigation: IrrigationId(row.get("irrigation")?),
+        };
+        Ok(data)
+    }
+
     pub(crate) fn load_farmer_kind(
         &mut self,
         row: &rusqlite::Row,
@@ -381,6 +508,7 @@ impl Game {
             sensor: self.known.sensors.find_by(row, "sensor")?,
             fruits: self.known.items.find_by(row, "fruits")?,
             residue: self.known.items.find_by(row, "residue")?,
+            seeds: self.known.items.find_optional_by(row, "seeds")?,
         };
         Ok(data)
     }
@@ -406,6 +534,7 @@ impl Game {
             body: self.known.bodies.find_by(row, "body")?,
             animal: self.known.animals.find_by(row, "animal")?,
             corpse: self.known.corpses.find_by(row, "corpse")?,
+            pollinator: row.get("pollinator")?,
         };
         Ok(data)
     }
@@ -448,6 +577,8 @@ impl Game {
             name: row.get("name")?,
             barrier: self.known.barriers.get_by(row, "barrier", BarrierKey)?,
             plant: self.known.plants.get_by(row, "plant", PlantKey)?,
+            wood: self.known.items.find_by(row, "wood")?,
+            sapling: self.known.items.find_optional_by(row, "sapling")?,
         };
         Ok(data)
     }
@@ -638,10 +769,17 @@ impl Game {
     }
 
     pub(crate) fn load_space(&mut self, row: &rusqlite::Row) -> Result<Space, DataError> {
+        let holes: Vec<Vec<u8>> = row.decode("holes")?;
+        // spaces saved before paving have no paths
+        let paths = match row.get::<_, Option<Vec<u8>>>("paths")? {
+            Some(_) => row.decode("paths")?,
+            None => holes.iter().map(|row| vec![false; row.len()]).collect(),
+        };
         let data = Space {
             id: SpaceId(row.get("id")?),
             kind: self.known.spaces.get_by(row, "kind", SpaceKey)?,
-            holes: row.decode("holes")?,
+            holes,
+            paths,
         };
         Ok(data)
     }
@@ -652,6 +790,10 @@ impl Game {
             name: row.get("name")?,
             speed: row.get("speed")?,
             radius: row.get("radius")?,
+            collision_layer: row.get("collision_layer")?,
+            collision_mask: row.get("collision_mask")?,
+            acceleration: row.get("acceleration")?,
+            damping: row.get("damping")?,
         };
         Ok(data)
     }
@@ -663,6 +805,9 @@ impl Game {
             position: row.get_json("position")?,
             destination: row.get_json("destination")?,
             space: SpaceId(row.get("space")?),
+            path: vec![],
+            velocity: [0.0, 0.0],
+            impulse: [0.0, 0.0],
         };
         Ok(data)
     }
@@ -686,6 +831,7 @@ impl Game {
             position: row.get_json("position")?,
             space: SpaceId(row.get("space")?),
             active: row.get("active")?,
+            rotation: Rotation::from_index(row.get("rotation")?),
         };
         Ok(data)
     }
@@ -698,6 +844,11 @@ impl Game {
             id: SensorKey(row.get("id")?),
             name: row.get("name")?,
             radius: row.get("radius")?,
+            filter: row
+                .get_json::<Vec<usize>>("filter")?
+                .into_iter()
+                .map(BodyKey)
+                .collect(),
         };
         Ok(data)
     }
@@ -720,6 +871,8 @@ impl Game {
         let data = GridKind {
             id: GridKey(row.get("id")?),
             name: row.get("name")?,
+            width: row.get("width")?,
+            height: row.get("height")?,
         };
         Ok(data)
     }
@@ -790,6 +943,7 @@ impl Game {
             stackable: row.get("stackable")?,
             max_quantity: row.get("max_quantity")?,
             functions: row.get_json("functions")?,
+            reach: row.get("reach")?,
         };
         Ok(data)
     }
@@ -800,6 +954,7 @@ impl Game {
             kind: self.known.items.get_by(row, "kind", ItemKey)?,
             container: ContainerId(row.get("container")?),
             quantity: row.get("quantity")?,
+            quality: row.get("quality")?,
         };
         Ok(data)
     }
@@ -858,12 +1013,49 @@ impl Game {
     }
 
     pub(crate) fn load_land(&mut self, row: &rusqlite::Row) -> Result<Land, DataError> {
+        let kind = self.known.lands.get_by(row, "kind", LandKey)?;
+        let moisture: Vec<f32> = row.decode("moisture")?;
+        // elevation is optional, land is flat without it
+        let elevation = match row.get::<_, Option<Vec<u8>>>("elevation")? {
+            Some(_) => row.decode("elevation")?,
+            None => vec![0; moisture.len()],
+        };
         let data = Land {
             id: LandId(row.get("id")?),
-            kind: self.known.lands.get_by(row, "kind", LandKey)?,
-            moisture: row.decode("moisture")?,
+            dirty_regions: vec![false; Land::count_regions(kind.width, kind.height)],
+            kind,
+            observed_moisture: moisture.clone(),
+            sheltered: vec![false; moisture.len()],
+            moisture,
             moisture_capacity: row.decode("moisture_capacity")?,
             surface: row.decode("surface")?,
+            elevation,
+            water: row.get("water")?,
+            rainfall: 0.0,
+            evaporation: 1.0,
+        };
+        Ok(data)
+    }
+
+    pub(crate) fn load_irrigation_kind(
+        &mut self,
+        row: &rusqlite::Row,
+    ) -> Result<IrrigationKind, DataError> {
+        let data = IrrigationKind {
+            id: IrrigationKey(row.get("id")?),
+            name: row.get("name")?,
+            part: row.get_json("part")?,
+        };
+        Ok(data)
+    }
+
+    pub(crate) fn load_irrigation(&mut self, row: &rusqlite::Row) -> Result<Irrigation, DataError> {
+        let data = Irrigation {
+            id: IrrigationId(row.get("id")?),
+            kind: self.known.irrigation.get_by(row, "kind", IrrigationKey)?,
+            land: LandId(row.get("land")?),
+            place: row.get_json("place")?,
+            pressure: row.get("pressure")?,
         };
         Ok(data)
     }
@@ -890,6 +1082,9 @@ impl Game {
             season: row.get("season")?,
             season_day: row.get("season_day")?,
             times_of_day: row.get("times_of_day")?,
+            day: row.get("day")?,
+            weather: Weather::Clear,
+            seed: 0,
         };
         Ok(data)
     }
@@ -902,15 +1097,33 @@ impl Game {
             name: row.get("name")?,
             width: row.get("width")?,
             height: row.get("height")?,
+            nutrients: row.get_json("nutrients")?,
         };
         Ok(data)
     }
 
     pub(crate) fn load_soil(&mut self, row: &rusqlite::Row) -> Result<Soil, DataError> {
+        let kind = self.known.soils.get_by(row, "kind", SoilKey)?;
+        let size = kind.width * kind.height;
+        // soils saved before nutrient channels have only single fertility layer
+        let nutrients = match row.get::<_, Option<Vec<u8>>>("nutrients")? {
+            Some(_) => row.decode("nutrients")?,
+            None => {
+                let fertility: Vec<f32> = row.decode("fertility")?;
+                [fertility.clone(), fertility.clone(), fertility]
+            }
+        };
+        let mut history: Vec<Vec<usize>> = row.get_json("history")?;
+        history.resize(size, vec![]);
+        let history = history
+            .into_iter()
+            .map(|keys| keys.into_iter().map(PlantKey).collect())
+            .collect();
         let data = Soil {
             id: SoilId(row.get("id")?),
-            kind: self.known.soils.get_by(row, "kind", SoilKey)?,
-            fertility: row.decode("fertility")?,
+            kind,
+            nutrients,
+            history,
         };
         Ok(data)
     }
@@ -923,21 +1136,42 @@ impl Game {
             flexibility: row.get("flexibility")?,
             transpiration: row.get("transpiration")?,
             max_fruits: row.get("max_fruits")?,
+            consumption: row.get_json("consumption")?,
+            restoration: row.get_json("restoration")?,
+            stages: row.get_json("stages")?,
+            seasons: row.get_json("seasons")?,
+            temperature: row.get_json("temperature")?,
+            resistance: row.get("resistance")?,
+            pollination: row.get("pollination")?,
         };
         Ok(data)
     }
 
     pub(crate) fn load_plant(&mut self, row: &rusqlite::Row) -> Result<Plant, DataError> {
+        let kind = self.known.plants.get_by(row, "kind", PlantKey)?;
+        let health = row.get("health")?;
+        let growth = row.get("growth")?;
         let data = Plant {
             id: PlantId(row.get("id")?),
-            kind: self.known.plants.get_by(row, "kind", PlantKey)?,
+            stage: kind.get_stage(growth, health),
+            temperature: kind.optimal_temperature(),
+            kind,
             soil: SoilId(row.get("soil")?),
             impact: row.get("impact")?,
             thirst: row.get("thirst")?,
             hunger: row.get("hunger")?,
-            health: row.get("health")?,
-            growth: row.get("growth")?,
+            health,
+            growth,
             fruits: row.get("fruits")?,
+            in_season: true,
+            stress: PlantStress::None,
+            place: 0,
+            moisture: 0.0,
+            infection: row.get("infection")?,
+            quality: row.get("quality")?,
+            pollination: row.get("pollination")?,
+            pollinators: 0,
+            wind: 0.0,
         };
         Ok(data)
     }
@@ -955,14 +1189,24 @@ impl Game {
             thirst_damage: row.get("thirst_damage")?,
             death_threshold: row.get("death_threshold")?,
             voracity: row.get("voracity")?,
+            maturity: row.get("maturity")?,
+            gestation: row.get("gestation")?,
+            fertility: row.get("fertility")?,
         };
         Ok(data)
     }
 
     pub(crate) fn load_animal(&mut self, row: &rusqlite::Row) -> Result<Animal, DataError> {
+        let sex: Option<String> = row.get("sex")?;
+        let sex = match sex {
+            Some(sex) => serde_json::from_str(&sex)?,
+            // animals saved before breeding was introduced
+            None => Sex::random(&mut self.random.raising),
+        };
         let data = Animal {
             id: AnimalId(row.get("id")?),
             kind: self.known.animals.get_by(row, "kind", AnimalKey)?,
+            sex,
             age: row.get("age")?,
             weight: 0.0,
             thirst: row.get("thirst")?,
@@ -971,6 +1215,8 @@ impl Game {
             health: row.get("health")?,
             stress: row.get("stress")?,
             behaviour: row.get_json("behaviour")?,
+            pregnancy: row.get("pregnancy")?,
+            partners: vec![],
         };
         Ok(data)
     }
@@ -1051,3 +1297,58 @@ impl<'stmt> BincodeDeserializer for rusqlite::Row<'stmt> {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+
+    use datamap::Storage;
+
+    use crate::actions::tests::{plant_crop, prepare_game};
+    use crate::math::{ArrayIndex, Randomness};
+    use crate::Game;
+
+    #[test]
+    fn test_randomness_saved_and_loaded() {
+        let path = temp_dir().join(format!("farmisto-{}-save.sqlite", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let storage = Storage::open(&path).unwrap();
+        let mut game = Game::new(storage);
+        let schema = include_str!("../../database/database.sql");
+        let table = schema
+            .split(';')
+            .find(|statement| statement.contains("create table Randomness"))
+            .unwrap();
+        rusqlite::Connection::open(&path)
+            .unwrap()
+            .execute_batch(table)
+            .unwrap();
+        game.random = Randomness::new(42);
+        game.random.raising.next_u64();
+        game.random.planting.generate();
+
+        game.save_randomness().unwrap();
+        let mut loaded = Game::new(Storage::open(&path).unwrap());
+        let storage = loaded.storage.open_into();
+        let randomness = storage
+            .find_all(|row| loaded.load_randomness(row))
+            .unwrap()
+            .pop();
+        remove_file(&path).unwrap();
+
+        assert_eq!(randomness, Some(game.random));
+    }
+
+    #[test]
+    fn test_plant_place_restored_from_crop_position() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [3.5, 2.5]);
+        game.planting.get_plant_mut(crop.plant).unwrap().place = 0;
+
+        game.load_plants_places();
+
+        let plant = game.planting.get_plant(crop.plant).unwrap();
+        assert_eq!(plant.place, [3, 2].fit(128));
+    }
+}
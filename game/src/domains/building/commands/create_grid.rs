@@ -7,7 +7,7 @@ impl BuildingDomain {
         kind: &Shared<GridKind>,
     ) -> Result<(GridId, impl FnOnce() -> Vec<Building> + '_), BuildingError> {
         let id = GridId(self.grids_sequence + 1);
-        let cells = Grid::default_map();
+        let cells = Grid::default_map(kind.width, kind.height);
         let rooms = Grid::calculate_rooms(&cells);
         let grid = Grid {
             id,
@@ -55,6 +55,8 @@ pub struct GridKey(pub usize);
 pub struct GridKind {
     pub id: GridKey,
     pub name: String,
+    pub width: usize,
+    pub height: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -65,7 +67,7 @@ pub struct Room {
     pub id: usize,
     pub contour: bool,
     pub area_y: usize,
-    pub area: Vec<u128>,
+    pub area: Vec<RoomRow>,
     pub aabb: Rect,
     pub active: bool,
     pub material: Material,
@@ -88,17 +90,123 @@ impl Room {
 
     pub fn contains(&self, point: [usize; 2]) -> bool {
         let [x, y] = point;
-        let x_bit = 1 << (128 - x - 1);
         if y >= self.area_y && y < self.area_y + self.area.len() {
-            let row = self.area[y - self.area_y];
-            if row & x_bit != 0 {
-                return true;
-            }
+            return self.area[y - self.area_y].contains(x);
         }
         false
     }
 }
 
+/// Bit mask of grid row, bit of column is set if cell belongs to room.
+///
+/// Bits stored from the most significant one, so column 0 is the highest bit of first word.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RoomRow {
+    columns: usize,
+    words: Vec<u128>,
+}
+
+impl RoomRow {
+    const BITS: usize = u128::BITS as usize;
+
+    pub fn empty(columns: usize) -> Self {
+        Self {
+            columns,
+            words: vec![0; columns.div_ceil(Self::BITS)],
+        }
+    }
+
+    pub fn filled(columns: usize) -> Self {
+        let mut row = Self::empty(columns);
+        row.insert_range(0, columns);
+        row
+    }
+
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    #[inline]
+    pub fn contains(&self, x: usize) -> bool {
+        if x >= self.columns {
+            return false;
+        }
+        let bit = 1 << (Self::BITS - x % Self::BITS - 1);
+        self.words[x / Self::BITS] & bit != 0
+    }
+
+    #[inline]
+    pub fn insert(&mut self, x: usize) {
+        if x < self.columns {
+            let bit = 1 << (Self::BITS - x % Self::BITS - 1);
+            self.words[x / Self::BITS] |= bit;
+        }
+    }
+
+    /// Sets bits of columns from start to end (exclusive), clamped by row width.
+    pub fn insert_range(&mut self, start: usize, end: usize) {
+        for x in start..end.min(self.columns) {
+            self.insert(x);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn intersects(&self, other: &RoomRow) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .any(|(word, other)| word & other != 0)
+    }
+
+    pub fn union(&mut self, other: &RoomRow) {
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= other;
+        }
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        for (index, word) in self.words.iter().enumerate() {
+            if *word != 0 {
+                return Some(index * Self::BITS + word.leading_zeros() as usize);
+            }
+        }
+        None
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        for (index, word) in self.words.iter().enumerate().rev() {
+            if *word != 0 {
+                return Some(index * Self::BITS + Self::BITS - word.trailing_zeros() as usize - 1);
+            }
+        }
+        None
+    }
+
+    /// Returns continuous runs of set bits as column ranges [start, end).
+    pub fn segments(&self) -> Vec<[usize; 2]> {
+        let mut segments = vec![];
+        let mut start = None;
+        for x in 0..self.columns {
+            match (self.contains(x), start) {
+                (true, None) => start = Some(x),
+                (false, Some(begin)) => {
+                    segments.push([begin, x]);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(begin) = start {
+            segments.push([begin, self.columns]);
+        }
+        segments
+    }
+}
+
 pub struct Grid {
     pub id: GridId,
     pub kind: Shared<GridKind>,
@@ -107,11 +215,8 @@ pub struct Grid {
 }
 
 impl Grid {
-    pub const COLUMNS: usize = 128;
-    pub const ROWS: usize = 128;
-
-    pub fn default_map() -> Vec<Vec<Cell>> {
-        vec![vec![Cell::default(); Grid::COLUMNS]; Grid::ROWS]
+    pub fn default_map(columns: usize, rows: usize) -> Vec<Vec<Cell>> {
+        vec![vec![Cell::default(); columns]; rows]
     }
 
     #[inline]
@@ -119,6 +224,35 @@ impl Grid {
         &mut self.cells[cell[1]][cell[0]]
     }
 
+    /// Returns enclosed room which contains given cell, exterior is never returned.
+    pub fn get_room(&self, cell: [usize; 2]) -> Option<&Room> {
+        self.rooms
+            .iter()
+            .find(|room| room.id != Room::EXTERIOR_ID && room.contains(cell))
+    }
+
+    /// Returns row-major mask of given size, cell is set if it is covered by room.
+    pub fn get_rooms_mask(&self, width: usize, height: usize) -> Vec<bool> {
+        let mut mask = vec![false; width * height];
+        for room in &self.rooms {
+            if room.id == Room::EXTERIOR_ID {
+                continue;
+            }
+            for (offset, row) in room.area.iter().enumerate() {
+                let y = room.area_y + offset;
+                if y >= height {
+                    break;
+                }
+                for x in 0..width {
+                    if row.contains(x) {
+                        mask[y * width + x] = true;
+                    }
+                }
+            }
+        }
+        mask
+    }
+
     #[inline]
     pub fn get_cell(&self, cell: [usize; 2]) -> Result<&Cell, BuildingError> {
         let [x, y] = cell;
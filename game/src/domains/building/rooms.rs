@@ -2,41 +2,35 @@ use std::collections::HashMap;
 
 use log::error;
 
-use crate::building::{Cell, Grid, Material, Room};
+use crate::building::{Cell, Grid, Material, Room, RoomRow};
 
 impl Grid {
     pub fn calculate_rooms(map: &Vec<Vec<Cell>>) -> Vec<Room> {
-        // TODO: array on stack increases speed to ~2 times!
-        // let mut map = [[Cell::default(); Grid::COLUMNS]; Grid::ROWS];
-        // for y in 0..Grid::ROWS {
-        //     for x in 0..Grid::COLUMNS {
-        //         map[y][x] = input_map[y][x];
-        //     }
-        // }
-
+        let rows = map.len();
+        let columns = map.first().map(|row| row.len()).unwrap_or(0);
         let exterior = Room {
             id: Room::EXTERIOR_ID,
             contour: false,
             area_y: 0,
-            area: vec![u128::MAX],
-            aabb: [0, 0, Grid::COLUMNS, Grid::ROWS],
+            area: vec![RoomRow::filled(columns)],
+            aabb: [0, 0, columns, rows],
             active: true,
             material: Material(Material::UNKNOWN),
         };
         let mut unique_id = 1;
         let mut rooms: Vec<Room> = vec![exterior];
-        for y in 1..Grid::ROWS {
-            let mut row = 0;
-            for x in 0..Grid::COLUMNS {
+        for y in 1..rows {
+            let mut row = RoomRow::empty(columns);
+            for x in 0..columns {
                 if !map[y][x].wall {
-                    row = row | 1 << (Grid::COLUMNS - x - 1);
+                    row.insert(x);
                 }
             }
-            let rooms_above_row: Vec<u128> = rooms
+            let rooms_above_row: Vec<RoomRow> = rooms
                 .iter()
                 .map(|room| match room.active {
-                    true => *room.area.last().unwrap(),
-                    false => 0,
+                    true => room.area.last().unwrap().clone(),
+                    false => RoomRow::empty(columns),
                 })
                 .collect();
             let expansions = Self::expand_rooms_by_row(row, rooms_above_row);
@@ -57,12 +51,13 @@ impl Grid {
             }
             let min_y = room.area_y;
             let max_y = room.area_y + room.area.len() - 1;
-            let mut min_x = Grid::COLUMNS;
+            let mut min_x = usize::MAX;
             let mut max_x = 0;
             for row in &room.area {
-                let left = row.leading_zeros() as usize;
-                let right = row.trailing_zeros() as usize;
-                let right = Grid::COLUMNS - right - 1;
+                let (left, right) = match (row.first(), row.last()) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => continue,
+                };
                 if left < min_x {
                     min_x = left;
                 }
@@ -82,9 +77,10 @@ impl Grid {
             let mut materials = HashMap::new();
             for (i, row) in room.area.iter().enumerate() {
                 let y = room.area_y + i;
-                let left = row.leading_zeros() as usize;
-                let right = row.trailing_zeros() as usize;
-                let right = Grid::COLUMNS - right - 1;
+                let (left, right) = match (row.first(), row.last()) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => continue,
+                };
                 let left = cells[y][left].material;
                 let right = cells[y][right].material;
                 let counter = materials.entry(left).or_insert(0);
@@ -109,33 +105,18 @@ impl Grid {
                 continue;
             }
             // grow vertically
-            let mut area = vec![0; room.area.len() + 2];
+            let columns = room.area[0].columns();
+            let mut area = vec![RoomRow::empty(columns); room.area.len() + 2];
             for i in 1..=room.area.len() {
-                area[i - 1] |= room.area[i - 1];
-                area[i] |= room.area[i - 1];
-                area[i + 1] |= room.area[i - 1];
+                area[i - 1].union(&room.area[i - 1]);
+                area[i].union(&room.area[i - 1]);
+                area[i + 1].union(&room.area[i - 1]);
             }
             // grow horizontally by segments
             for row in area.iter_mut() {
-                let mut value: u128 = *row;
-                let mut i = 0;
-                let mut grow_row = 0;
-                while value != 0 {
-                    let skip_zeros = value.leading_zeros();
-                    i += skip_zeros;
-                    value = value << skip_zeros;
-
-                    let width = value.leading_ones() + 2;
-                    i -= 1;
-                    let val = u128::MAX >> (128 - width);
-                    let segment = val << (128 - i - width);
-                    grow_row = grow_row | segment;
-
-                    i += width - 1;
-                    if width == 128 {
-                        break;
-                    }
-                    value = value << (width - 2);
+                let mut grow_row = RoomRow::empty(columns);
+                for [start, end] in row.segments() {
+                    grow_row.insert_range(start.saturating_sub(1), end + 1);
                 }
                 *row = grow_row;
             }
@@ -158,13 +139,14 @@ impl Grid {
                 let offset = source_y as isize - room.area_y as isize;
                 if offset < 0 {
                     room.area_y = source_y;
-                    let mut rows = vec![0; offset.abs() as usize];
-                    rows.extend(&room.area);
+                    let columns = room.area[0].columns();
+                    let mut rows = vec![RoomRow::empty(columns); offset.unsigned_abs()];
+                    rows.append(&mut room.area);
                     room.area = rows;
                 }
                 for (index, row) in source_rows.into_iter().enumerate() {
                     let room_index = (index as isize + offset) as usize;
-                    room.area[room_index] = room.area[room_index] | row;
+                    room.area[room_index].union(&row);
                 }
             }
 
@@ -184,30 +166,29 @@ impl Grid {
         y: usize,
         rooms: &mut Vec<Room>,
         room_id: &mut usize,
-        expansions: Vec<u128>,
+        expansions: Vec<RoomRow>,
     ) -> Vec<[usize; 2]> {
         let rooms_before = rooms.len();
         let mut merges = vec![];
         let mut trunk = HashMap::new();
-        for room in 0..expansions.len() {
+        for (room, expansion) in expansions.into_iter().enumerate() {
             if room >= rooms_before {
                 rooms.push(Room {
                     id: *room_id,
                     contour: false,
                     area_y: y,
-                    area: vec![expansions[room]],
+                    area: vec![expansion],
                     aabb: [0; 4],
                     active: true,
                     material: Material(Material::UNKNOWN),
                 });
                 *room_id += 1;
             } else {
-                let expansion = expansions[room];
-                if expansion != 0 {
+                if !expansion.is_empty() {
                     match trunk.get(&expansion) {
                         None => {
+                            trunk.insert(expansion.clone(), room);
                             rooms[room].area.push(expansion);
-                            trunk.insert(expansion, room);
                         }
                         Some(trunk) => {
                             merges.push([room, *trunk]);
@@ -221,35 +202,22 @@ impl Grid {
         merges
     }
 
-    pub fn expand_rooms_by_row(row: u128, rooms: Vec<u128>) -> Vec<u128> {
-        let mut appends = vec![0u128; rooms.len()];
-        let mut value: u128 = row;
-        let mut i = 0;
-        while value != 0 {
-            let skip_zeros = value.leading_zeros();
-            i += skip_zeros;
-            value = value << skip_zeros;
-            // println!("skip to {}", i);
-            let width = value.leading_ones();
-            let val = u128::MAX >> (128 - width);
-            let segment = val << (128 - i - width);
-            // println!("segment {}..{} {:#010b}", i, i + width - 1, segment);
+    pub fn expand_rooms_by_row(row: RoomRow, rooms: Vec<RoomRow>) -> Vec<RoomRow> {
+        let columns = row.columns();
+        let mut appends = vec![RoomRow::empty(columns); rooms.len()];
+        for [start, end] in row.segments() {
+            let mut segment = RoomRow::empty(columns);
+            segment.insert_range(start, end);
             let mut any = false;
             for (index, append) in appends.iter_mut().enumerate() {
-                if index < rooms.len() && rooms[index] & segment != 0 {
-                    *append = *append | segment;
+                if index < rooms.len() && rooms[index].intersects(&segment) {
+                    append.union(&segment);
                     any = true;
-                    continue;
                 }
             }
             if !any {
                 appends.push(segment);
             }
-            i += width;
-            if width == 128 {
-                break;
-            }
-            value = value << width;
         }
         appends
     }
@@ -271,7 +239,7 @@ impl Grid {
                     let offset = offset as usize;
                     let overlaps = source.area.len().min(destination.area.len() - offset);
                     for i in 0..overlaps {
-                        if destination.area[i + offset] & source.area[i] != 0 {
+                        if destination.area[i + offset].intersects(&source.area[i]) {
                             merge = Some([source_index, destination_index]);
                             break 'collision_detection;
                         }
@@ -287,3 +255,90 @@ impl Grid {
         rooms
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::building::{Cell, Grid, GridId, GridKey, GridKind, Material};
+    use crate::collections::Shared;
+
+    fn walls(columns: usize, rows: usize, rect: [usize; 4]) -> Vec<Vec<Cell>> {
+        let [x, y, w, h] = rect;
+        let mut map = Grid::default_map(columns, rows);
+        for cy in y..y + h {
+            for cx in x..x + w {
+                if cy == y || cy == y + h - 1 || cx == x || cx == x + w - 1 {
+                    map[cy][cx].wall = true;
+                }
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_room_beyond_128_columns() {
+        let map = walls(256, 16, [140, 2, 5, 4]);
+        let rooms = Grid::calculate_rooms(&map);
+        assert_eq!(rooms.len(), 2);
+        let room = &rooms[1];
+        assert!(room.contains([142, 3]));
+        assert!(room.contains([140, 2]));
+        assert!(!room.contains([139, 3]));
+        assert_eq!(room.aabb, [140, 2, 144, 5]);
+        assert!(rooms[0].contains([255, 0]));
+    }
+
+    #[test]
+    fn test_room_in_small_grid() {
+        let map = walls(8, 8, [1, 1, 4, 4]);
+        let rooms = Grid::calculate_rooms(&map);
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[1].aabb, [1, 1, 4, 4]);
+        assert!(!rooms[1].contains([5, 2]));
+    }
+
+    #[test]
+    fn test_get_room_ignores_exterior() {
+        let mut cells = walls(8, 8, [1, 1, 4, 4]);
+        for row in cells.iter_mut() {
+            for cell in row.iter_mut().filter(|cell| cell.wall) {
+                cell.material = Material(Material::GLASS);
+            }
+        }
+        let grid = Grid {
+            id: GridId(1),
+            kind: Shared::new(GridKind {
+                id: GridKey(1),
+                name: "test".to_string(),
+                width: 8,
+                height: 8,
+            }),
+            rooms: Grid::calculate_rooms(&cells),
+            cells,
+        };
+        let room = grid.get_room([2, 2]).unwrap();
+        assert_eq!(room.material.index(), Material::GLASS);
+        assert!(grid.get_room([6, 6]).is_none());
+    }
+
+    #[test]
+    fn test_rooms_mask_fits_land_size() {
+        let cells = walls(8, 8, [1, 1, 4, 4]);
+        let grid = Grid {
+            id: GridId(1),
+            kind: Shared::new(GridKind {
+                id: GridKey(1),
+                name: "test".to_string(),
+                width: 8,
+                height: 8,
+            }),
+            rooms: Grid::calculate_rooms(&cells),
+            cells,
+        };
+        let mask = grid.get_rooms_mask(4, 3);
+        assert_eq!(mask.len(), 12);
+        assert!(mask[2 * 4 + 2]);
+        assert!(mask[4 + 3]);
+        assert!(!mask[0]);
+        assert!(!mask[2 * 4]);
+    }
+}
@@ -26,6 +26,7 @@ impl InventoryDomain {
                     key: item.kind.id,
                     container: item.container,
                     quantity: item.quantity,
+                    quality: item.quality,
                 });
                 domain.items_id.register(item.id.0);
                 container.items.push(item);
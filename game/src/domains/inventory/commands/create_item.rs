@@ -11,6 +11,7 @@ impl InventoryDomain {
         kind: &Shared<ItemKind>,
         container: ContainerId,
         quantity: u8,
+        quality: f32,
     ) -> Result<impl FnOnce() -> Vec<Inventory> + '_, InventoryError> {
         self.get_container(container)?; // ensure container valid
         let item = Item {
@@ -18,6 +19,7 @@ impl InventoryDomain {
             kind: kind.clone(),
             container,
             quantity,
+            quality,
         };
         let operation = move || {
             let events = vec![ItemsAdded {
@@ -26,6 +28,7 @@ impl InventoryDomain {
                     key: item.kind.id,
                     container,
                     quantity: item.quantity,
+                    quality: item.quality,
                 }],
             }];
             self.items_id.register(item.id.0);
@@ -35,4 +38,29 @@ impl InventoryDomain {
         };
         Ok(operation)
     }
+
+    pub fn add_items(
+        &mut self,
+        container: ContainerId,
+        items: Vec<Item>,
+    ) -> Result<impl FnOnce() -> Vec<Inventory> + '_, InventoryError> {
+        self.get_container(container)?; // ensure container valid
+        let operation = move || {
+            let mut data = Vec::with_capacity(items.len());
+            for item in &items {
+                data.push(ItemData {
+                    id: item.id,
+                    key: item.kind.id,
+                    container,
+                    quantity: item.quantity,
+                    quality: item.quality,
+                });
+                self.items_id.register(item.id.0);
+            }
+            let container = self.mut_container(container).unwrap();
+            container.items.extend(items);
+            vec![ItemsAdded { items: data }]
+        };
+        Ok(operation)
+    }
 }
@@ -18,6 +18,7 @@ impl InventoryDomain {
                     id: item.id,
                     container: item.container,
                     quantity: item.quantity,
+                    quality: item.quality,
                 })
             } else {
                 let item = container.items.remove(index);
@@ -23,6 +23,7 @@ impl InventoryDomain {
                 key: item.kind.id,
                 container: item.container,
                 quantity: item.quantity,
+                quality: item.quality,
             };
             self.containers.insert(
                 destination_id,
@@ -8,6 +8,7 @@ impl InventoryDomain {
         &'operation mut self,
         container: ContainerId,
         increment: u8,
+        quality: f32,
     ) -> Result<impl FnOnce() -> Vec<Inventory> + 'operation, InventoryError> {
         let container = self.mut_container(container)?;
         let index = container.ensure_item_at(-1)?;
@@ -19,11 +20,15 @@ impl InventoryDomain {
         let operation = move || {
             let mut events = vec![];
             let item = &mut container.items[index];
+            // stack keeps average quality of all its units
+            let total = item.quality * item.quantity as f32 + quality * increment as f32;
             item.quantity += increment;
+            item.quality = total / item.quantity as f32;
             events.push(ItemQuantityChanged {
                 id: item.id,
                 container: item.container,
                 quantity: item.quantity,
+                quality: item.quality,
             });
             events
         };
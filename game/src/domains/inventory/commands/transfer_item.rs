@@ -91,6 +91,7 @@ impl InventoryDomain {
                     key: item.kind.id,
                     container: item.container,
                     quantity: item.quantity,
+                    quality: item.quality,
                 }],
             });
             destination.items.push(item);
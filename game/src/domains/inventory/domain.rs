@@ -33,6 +33,7 @@ pub struct Nozzle {
 pub enum Installation {
     Theodolite(usize),
     Peg(usize),
+    Irrigator(usize),
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
@@ -43,11 +44,22 @@ pub enum Function {
     Carry,
     Instrumenting,
     Shovel,
+    /// Ploughs plains for planting.
+    Hoe,
+    /// Paves path on plains.
+    Gravel,
+    /// Fells trees.
+    Axe,
+    /// Grows into tree of specified kind when planted.
+    Sapling(usize),
     Product(usize),
     Assembly(usize),
     Stone,
     Moistener(Nozzle),
-    Fertilizer(f32),
+    /// Nutrients added to soil tile, indexed by nitrogen, phosphorus and potassium.
+    Fertilizer([f32; 3]),
+    /// Reduces severity of plant disease or pests.
+    Pesticide(f32),
     Residue,
     Food,
     Tether,
@@ -62,6 +74,8 @@ pub struct ItemKind {
     pub stackable: bool,
     pub functions: Vec<Function>,
     pub max_quantity: u8,
+    /// Distance at which item held in hands can be applied to target.
+    pub reach: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -72,6 +86,8 @@ pub struct Item {
     pub kind: Shared<ItemKind>,
     pub container: ContainerId,
     pub quantity: u8,
+    /// Relative value of item, 1.0 is normal quality.
+    pub quality: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -80,6 +96,7 @@ pub struct ItemData {
     pub key: ItemKey,
     pub container: ContainerId,
     pub quantity: u8,
+    pub quality: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -101,6 +118,7 @@ pub enum Inventory {
         id: ItemId,
         container: ContainerId,
         quantity: u8,
+        quality: f32,
     },
 }
 
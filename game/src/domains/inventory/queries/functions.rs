@@ -8,13 +8,18 @@ pub trait FunctionsQuery {
     fn as_seeds<T>(&self, constructor: Constructor<T>) -> Result<T, InventoryError>;
     fn as_hammer(&self) -> Result<(), InventoryError>;
     fn as_shovel(&self) -> Result<(), InventoryError>;
+    fn as_hoe(&self) -> Result<(), InventoryError>;
+    fn as_gravel(&self) -> Result<(), InventoryError>;
+    fn as_axe(&self) -> Result<(), InventoryError>;
+    fn as_sapling<T>(&self, constructor: Constructor<T>) -> Result<T, InventoryError>;
     fn as_stone(&self) -> Result<(), InventoryError>;
     fn as_material(&self) -> Result<u8, InventoryError>;
     fn as_product(&self) -> Result<usize, InventoryError>;
     fn as_installation(&self) -> Result<Installation, InventoryError>;
     fn as_assembly<T>(&self, constructor: Constructor<T>) -> Result<T, InventoryError>;
     fn as_moistener(&self) -> Result<Nozzle, InventoryError>;
-    fn as_fertilizer(&self) -> Result<f32, InventoryError>;
+    fn as_fertilizer(&self) -> Result<[f32; 3], InventoryError>;
+    fn as_pesticide(&self) -> Result<f32, InventoryError>;
 }
 
 impl FunctionsQuery for Vec<Function> {
@@ -63,10 +68,19 @@ impl FunctionsQuery for Vec<Function> {
         Err(InventoryError::ItemFunctionNotFound)
     }
 
-    fn as_fertilizer(&self) -> Result<f32, InventoryError> {
+    fn as_fertilizer(&self) -> Result<[f32; 3], InventoryError> {
         for function in self {
-            if let Function::Fertilizer(quality) = function {
-                return Ok(*quality);
+            if let Function::Fertilizer(nutrients) = function {
+                return Ok(*nutrients);
+            }
+        }
+        Err(InventoryError::ItemFunctionNotFound)
+    }
+
+    fn as_pesticide(&self) -> Result<f32, InventoryError> {
+        for function in self {
+            if let Function::Pesticide(efficiency) = function {
+                return Ok(*efficiency);
             }
         }
         Err(InventoryError::ItemFunctionNotFound)
@@ -81,6 +95,42 @@ impl FunctionsQuery for Vec<Function> {
         Err(InventoryError::ItemFunctionNotFound)
     }
 
+    fn as_hoe(&self) -> Result<(), InventoryError> {
+        for function in self {
+            if let Function::Hoe = function {
+                return Ok(());
+            }
+        }
+        Err(InventoryError::ItemFunctionNotFound)
+    }
+
+    fn as_gravel(&self) -> Result<(), InventoryError> {
+        for function in self {
+            if let Function::Gravel = function {
+                return Ok(());
+            }
+        }
+        Err(InventoryError::ItemFunctionNotFound)
+    }
+
+    fn as_axe(&self) -> Result<(), InventoryError> {
+        for function in self {
+            if let Function::Axe = function {
+                return Ok(());
+            }
+        }
+        Err(InventoryError::ItemFunctionNotFound)
+    }
+
+    fn as_sapling<T>(&self, constructor: Constructor<T>) -> Result<T, InventoryError> {
+        for function in self {
+            if let Function::Sapling(kind) = function {
+                return Ok(constructor(*kind));
+            }
+        }
+        Err(InventoryError::ItemFunctionNotFound)
+    }
+
     fn as_material(&self) -> Result<u8, InventoryError> {
         for function in self {
             if let Function::Material(material) = function {
@@ -0,0 +1,45 @@
+use crate::collections::Shared;
+use crate::landscaping::{
+    Irrigation, IrrigationId, IrrigationKind, IrrigationPart, LandId, Landscaping,
+    LandscapingDomain, LandscapingError, Place, Surface,
+};
+
+impl LandscapingDomain {
+    pub fn create_irrigation(
+        &mut self,
+        land: LandId,
+        place: Place,
+        kind: &Shared<IrrigationKind>,
+    ) -> Result<(IrrigationId, impl FnOnce() -> Vec<Landscaping> + '_), LandscapingError> {
+        let land_state = self.get_land(land)?;
+        land_state.ensure_surface(place, Surface::PLAINS)?;
+        if let Some(irrigation) = self.get_irrigation_at(land, place) {
+            return Err(LandscapingError::IrrigationPlaceOccupied { id: irrigation.id });
+        }
+        if let IrrigationPart::Pump { .. } = kind.part {
+            if !land_state.has_basin_nearby(place) {
+                return Err(LandscapingError::BasinNotFound { place });
+            }
+        }
+        let id = self.irrigation_id.introduce().one(IrrigationId);
+        let irrigation = Irrigation {
+            id,
+            kind: kind.clone(),
+            land,
+            place,
+            pressure: 0.0,
+        };
+        let command = move || {
+            let events = vec![Landscaping::IrrigationCreated {
+                id,
+                key: irrigation.kind.id,
+                land,
+                place,
+            }];
+            self.irrigation_id.register(id.0);
+            self.irrigation.push(irrigation);
+            events
+        };
+        Ok((id, command))
+    }
+}
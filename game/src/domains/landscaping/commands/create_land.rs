@@ -10,12 +10,20 @@ impl LandscapingDomain {
         kind: &Shared<LandKind>,
     ) -> Result<(LandId, impl FnOnce() -> Vec<Landscaping> + '_), LandscapingError> {
         let id = self.lands_id.introduce().one(LandId);
+        let size = kind.width * kind.height;
         let land = Land {
             id,
             kind: kind.clone(),
-            moisture: vec![0.0; 128 * 128],
-            moisture_capacity: vec![0.0; 128 * 128],
-            surface: vec![0; 128 * 128],
+            moisture: vec![0.0; size],
+            moisture_capacity: vec![0.0; size],
+            surface: vec![0; size],
+            elevation: vec![0; size],
+            water: 1.0,
+            rainfall: 0.0,
+            evaporation: 1.0,
+            sheltered: vec![false; size],
+            observed_moisture: vec![0.0; size],
+            dirty_regions: vec![true; Land::count_regions(kind.width, kind.height)],
         };
         let command = move || {
             self.lands_id.register(id.0);
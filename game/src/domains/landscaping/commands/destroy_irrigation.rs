@@ -0,0 +1,19 @@
+use crate::landscaping::{IrrigationId, Landscaping, LandscapingDomain, LandscapingError};
+
+impl LandscapingDomain {
+    pub fn destroy_irrigation(
+        &mut self,
+        id: IrrigationId,
+    ) -> Result<impl FnOnce() -> Vec<Landscaping> + '_, LandscapingError> {
+        let index = self
+            .irrigation
+            .iter()
+            .position(|irrigation| irrigation.id == id)
+            .ok_or(LandscapingError::IrrigationNotFound { id })?;
+        let command = move || {
+            self.irrigation.remove(index);
+            vec![Landscaping::IrrigationDestroyed { id }]
+        };
+        Ok(command)
+    }
+}
@@ -15,6 +15,8 @@ impl LandscapingDomain {
         land.ensure_surface(place, Surface::PLAINS)?;
         let command = move || {
             let place = place.fit(land.kind.width);
+            // dug place sinks, so water of basins nearby flows into it
+            land.elevation[place] = land.elevation[place].saturating_sub(1);
             if capacity == 1.0 {
                 land.surface[place] = Surface::BASIN;
                 vec![]
@@ -1,4 +1,9 @@
+mod clear_rocks;
+mod create_irrigation;
 mod create_land;
+mod destroy_irrigation;
 mod dig_place;
 mod fill_basin;
+mod pave_place;
+mod plough_place;
 mod pour_water;
@@ -0,0 +1,21 @@
+use crate::landscaping::{
+    LandId, Landscaping, LandscapingDomain, LandscapingError, Place, Surface,
+};
+use crate::math::ArrayIndex;
+
+impl LandscapingDomain {
+    pub fn plough_place(
+        &mut self,
+        id: LandId,
+        place: Place,
+    ) -> Result<impl FnOnce() -> Vec<Landscaping> + '_, LandscapingError> {
+        let land = self.get_land_mut(id)?;
+        land.ensure_surface(place, Surface::PLAINS)?;
+        let command = move || {
+            let place = place.fit(land.kind.width);
+            land.surface[place] = Surface::PLOUGHED;
+            vec![]
+        };
+        Ok(command)
+    }
+}
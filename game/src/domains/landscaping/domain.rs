@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::collections::{Sequence, Shared};
-use crate::math::Rect;
+use crate::math::{ArrayIndex, Rect, TileMath, VectorMath};
 
 pub type Place = [usize; 2];
 
@@ -11,6 +11,12 @@ pub struct Surface {}
 impl Surface {
     pub const PLAINS: u8 = 0;
     pub const BASIN: u8 = 1;
+    /// Loosened soil ready for planting.
+    pub const PLOUGHED: u8 = 2;
+    /// Paved path, speeds up walking.
+    pub const GRAVEL: u8 = 3;
+    /// Stony ground, has to be cleared before any use.
+    pub const ROCKS: u8 = 4;
 }
 
 pub struct LandscapingDomain {
@@ -18,6 +24,8 @@ pub struct LandscapingDomain {
     pub lands_update: f32,
     pub lands_id: Sequence,
     pub lands: HashMap<LandId, Land>,
+    pub irrigation_id: Sequence,
+    pub irrigation: Vec<Irrigation>,
 }
 
 impl Default for LandscapingDomain {
@@ -27,6 +35,8 @@ impl Default for LandscapingDomain {
             lands_update: 0.0,
             lands_id: Sequence::default(),
             lands: HashMap::default(),
+            irrigation_id: Sequence::default(),
+            irrigation: vec![],
         }
     }
 }
@@ -50,6 +60,139 @@ pub struct Land {
     pub moisture: Vec<f32>,
     pub moisture_capacity: Vec<f32>,
     pub surface: Vec<u8>,
+    /// Height of places in steps, water flows easier downhill.
+    pub elevation: Vec<u8>,
+    /// Fullness of basins, they dry out in hot weather and are refilled by rain.
+    pub water: f32,
+    /// Volume of rain water falling on every place per second.
+    pub rainfall: f32,
+    /// Multiplier of moisture evaporation caused by weather.
+    pub evaporation: f32,
+    /// Places under roofs of rooms, rain never falls on them.
+    pub sheltered: Vec<bool>,
+    /// Moisture as it was at last inspection, used to detect changed regions.
+    pub observed_moisture: Vec<f32>,
+    /// Regions of land where moisture was written since last inspection.
+    pub dirty_regions: Vec<bool>,
+}
+
+impl Land {
+    /// Loss of basins water per second for every unit of evaporation above normal.
+    pub const BASIN_EVAPORATION: f32 = 0.0005;
+
+    /// Change of water flow for every step of elevation between places.
+    pub const ELEVATION_FLOW: f32 = 0.25;
+
+    /// Size of square region of land inspected at once.
+    pub const REGION_SIZE: usize = 16;
+
+    /// Minimal change of moisture at any place of region to inspect it again.
+    pub const MOISTURE_TOLERANCE: f32 = 0.01;
+
+    /// Counts regions covering land of given size.
+    pub fn count_regions(width: usize, height: usize) -> usize {
+        width.div_ceil(Self::REGION_SIZE) * height.div_ceil(Self::REGION_SIZE)
+    }
+
+    /// Marks region of place as dirty, so only written regions are compared at inspection.
+    pub fn mark_moisture_changed(&mut self, place: usize) {
+        let width = self.kind.width;
+        let [x, y] = [place % width, place / width];
+        let columns = width.div_ceil(Self::REGION_SIZE);
+        let region = (y / Self::REGION_SIZE) * columns + x / Self::REGION_SIZE;
+        self.dirty_regions[region] = true;
+    }
+
+    /// Spreads water volume around place, decreasing with distance from center.
+    ///
+    /// Adds no more than available volume of water in total,
+    /// returns volume of water actually soaked into places.
+    pub fn spray(
+        &mut self,
+        place: Place,
+        volume: f32,
+        spread: u32,
+        available: f32,
+        mut noise: impl FnMut() -> f32,
+    ) -> f32 {
+        let mut sprayed = 0.0;
+        let spread = spread as i32;
+        let center = place.position();
+        let [x, y] = place;
+        let [width, height] = [self.kind.width as i32, self.kind.height as i32];
+        for sy in -spread..=spread {
+            for sx in -spread..=spread {
+                let [sx, sy] = [x as i32 + sx, y as i32 + sy];
+                if sx < 0 || sy < 0 || sx >= width || sy >= height {
+                    continue;
+                }
+                let x = sx as usize;
+                let y = sy as usize;
+                let place = [x, y];
+                let distance = place.position().distance(center);
+                let place = place.fit(self.kind.width);
+                let moisture = self.moisture[place];
+                let moisture_capacity = self.moisture_capacity[place];
+                let mut factor = if distance == 1.0 {
+                    1.0
+                } else {
+                    let step = 1.0 / (1 + spread) as f32;
+                    (1.0 - step * distance).max(0.0)
+                };
+                factor += noise();
+                let volume = (volume * factor)
+                    .min((moisture_capacity - moisture).max(0.0))
+                    .min(available - sprayed);
+                if volume > 0.0 {
+                    self.moisture[place] = moisture + volume;
+                    self.mark_moisture_changed(place);
+                    sprayed += volume;
+                }
+            }
+        }
+        sprayed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IrrigationPart {
+    Pipe,
+    /// Draws water from adjacent basin, flow is maximum volume of water per second.
+    Pump {
+        flow: f32,
+    },
+    /// Sprays water around like watering can, pressure is volume of water per second.
+    Sprinkler {
+        pressure: f32,
+        spread: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IrrigationKey(pub usize);
+
+pub struct IrrigationKind {
+    pub id: IrrigationKey,
+    pub name: String,
+    pub part: IrrigationPart,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IrrigationId(pub usize);
+
+/// Part of irrigation network, connected with neighbour parts on land.
+pub struct Irrigation {
+    pub id: IrrigationId,
+    pub kind: Shared<IrrigationKind>,
+    pub land: LandId,
+    pub place: Place,
+    /// Share of nominal pressure reaching this part, 0.0 if not connected to working pump.
+    pub pressure: f32,
+}
+
+impl Irrigation {
+    /// Share of pressure lost on every pipe segment from pump.
+    pub const PRESSURE_LOSS: f32 = 0.05;
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -68,23 +211,38 @@ pub enum Landscaping {
         land: LandId,
         rect: Rect,
         surface: Vec<u8>,
-    }, // MoistureUpdate {
-       //     land: LandId,
-       //     moisture: LandMap,
-       // },
-       // MoistureUpdated {
-       //     land: LandId,
-       //     rect: [usize; 4],
-       //     moisture: Vec<f32>,
-       // },
-       // MoistureCapacityUpdate {
-       //     land: LandId,
-       //     moisture_capacity: LandMap,
-       // },
-       // SurfaceUpdate {
-       //     land: LandId,
-       //     surface: LandMap,
-       // },
+        elevation: Vec<u8>,
+    },
+    IrrigationCreated {
+        id: IrrigationId,
+        key: IrrigationKey,
+        land: LandId,
+        place: Place,
+    },
+    IrrigationDestroyed {
+        id: IrrigationId,
+    },
+    IrrigationPressureChanged {
+        id: IrrigationId,
+        pressure: f32,
+    },
+    // MoistureUpdate {
+    //     land: LandId,
+    //     moisture: LandMap,
+    // },
+    // MoistureUpdated {
+    //     land: LandId,
+    //     rect: [usize; 4],
+    //     moisture: Vec<f32>,
+    // },
+    // MoistureCapacityUpdate {
+    //     land: LandId,
+    //     moisture_capacity: LandMap,
+    // },
+    // SurfaceUpdate {
+    //     land: LandId,
+    //     surface: LandMap,
+    // },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -101,4 +259,13 @@ pub enum LandscapingError {
         id: LandId,
         place: Place,
     },
+    IrrigationNotFound {
+        id: IrrigationId,
+    },
+    IrrigationPlaceOccupied {
+        id: IrrigationId,
+    },
+    BasinNotFound {
+        place: Place,
+    },
 }
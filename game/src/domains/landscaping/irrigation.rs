@@ -0,0 +1,341 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::landscaping::{
+    Irrigation, IrrigationPart, LandId, Landscaping, LandscapingDomain, Place,
+};
+
+impl LandscapingDomain {
+    /// Distributes water from pumps to sprinklers through connected parts of irrigation network.
+    ///
+    /// Pumps share their flow between all sprinklers of network, so sprinklers lose pressure
+    /// when they demand more water than pumps can draw. Pressure also drops with every segment
+    /// of pipe between sprinkler and nearest working pump. Water sprayed by sprinklers is drawn
+    /// from moisture of basins next to working pumps of network, sprinklers stop when
+    /// those basins dry out.
+    pub fn irrigate(&mut self, time: f32) -> Vec<Landscaping> {
+        let mut events = vec![];
+        let parts: HashMap<(LandId, Place), usize> = self
+            .irrigation
+            .iter()
+            .enumerate()
+            .map(|(index, irrigation)| ((irrigation.land, irrigation.place), index))
+            .collect();
+        let neighbours = |irrigation: &Irrigation| -> Vec<usize> {
+            match self.lands.get(&irrigation.land) {
+                Some(land) => land
+                    .get_neighbours(irrigation.place)
+                    .into_iter()
+                    .filter_map(|place| parts.get(&(irrigation.land, place)).copied())
+                    .collect(),
+                None => vec![],
+            }
+        };
+
+        // split parts to independent networks
+        let mut networks = vec![usize::MAX; self.irrigation.len()];
+        let mut supply = vec![];
+        let mut basins = vec![];
+        for start in 0..self.irrigation.len() {
+            if networks[start] != usize::MAX {
+                continue;
+            }
+            let network = supply.len();
+            let mut flow = 0.0;
+            let mut demand = 0.0;
+            let mut network_basins = HashSet::new();
+            networks[start] = network;
+            let mut queue = VecDeque::from([start]);
+            while let Some(index) = queue.pop_front() {
+                let irrigation = &self.irrigation[index];
+                match irrigation.kind.part {
+                    IrrigationPart::Pipe => {}
+                    IrrigationPart::Pump { flow: pump_flow } => {
+                        if self.is_pump_working(irrigation) {
+                            flow += pump_flow;
+                            if let Some(land) = self.lands.get(&irrigation.land) {
+                                network_basins.extend(land.get_basins_nearby(irrigation.place));
+                            }
+                        }
+                    }
+                    IrrigationPart::Sprinkler { pressure, .. } => demand += pressure,
+                }
+                for next in neighbours(irrigation) {
+                    if networks[next] == usize::MAX {
+                        networks[next] = network;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            let network_supply = if demand > 0.0 {
+                (flow / demand).min(1.0)
+            } else if flow > 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+            supply.push(network_supply);
+            let mut network_basins: Vec<usize> = network_basins.into_iter().collect();
+            network_basins.sort();
+            basins.push(network_basins);
+        }
+
+        // measure distance of every part to nearest working pump
+        let mut distances = vec![usize::MAX; self.irrigation.len()];
+        let mut queue = VecDeque::new();
+        for (index, irrigation) in self.irrigation.iter().enumerate() {
+            if let IrrigationPart::Pump { .. } = irrigation.kind.part {
+                if self.is_pump_working(irrigation) {
+                    distances[index] = 0;
+                    queue.push_back(index);
+                }
+            }
+        }
+        while let Some(index) = queue.pop_front() {
+            for next in neighbours(&self.irrigation[index]) {
+                if distances[next] == usize::MAX {
+                    distances[next] = distances[index] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        for (index, irrigation) in self.irrigation.iter_mut().enumerate() {
+            let pressure = if distances[index] == usize::MAX {
+                0.0
+            } else {
+                let loss = Irrigation::PRESSURE_LOSS * distances[index] as f32;
+                supply[networks[index]] * (1.0 - loss).max(0.0)
+            };
+            if (irrigation.pressure - pressure).abs() > 0.01
+                || (pressure == 0.0 && irrigation.pressure != 0.0)
+            {
+                irrigation.pressure = pressure;
+                events.push(Landscaping::IrrigationPressureChanged {
+                    id: irrigation.id,
+                    pressure,
+                });
+            }
+            if let IrrigationPart::Sprinkler { pressure, spread } = irrigation.kind.part {
+                if irrigation.pressure > 0.0 {
+                    if let Some(land) = self.lands.get_mut(&irrigation.land) {
+                        let basins = &basins[networks[index]];
+                        let available: f32 = basins.iter().map(|basin| land.moisture[*basin]).sum();
+                        let volume = pressure * irrigation.pressure * time;
+                        let mut sprayed =
+                            land.spray(irrigation.place, volume, spread, available, || 0.0);
+                        for basin in basins {
+                            let drawn = land.moisture[*basin].min(sprayed);
+                            land.moisture[*basin] -= drawn;
+                            land.mark_moisture_changed(*basin);
+                            sprayed -= drawn;
+                        }
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    fn is_pump_working(&self, pump: &Irrigation) -> bool {
+        self.lands
+            .get(&pump.land)
+            .map(|land| {
+                land.get_basins_nearby(pump.place)
+                    .into_iter()
+                    .any(|basin| land.moisture[basin] > 0.0)
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Shared;
+    use crate::landscaping::{
+        IrrigationKey, IrrigationKind, IrrigationPart, LandId, LandKey, LandKind,
+        LandscapingDomain, LandscapingError, Surface,
+    };
+    use crate::math::{ArrayIndex, Random};
+
+    fn prepare_domain() -> (LandscapingDomain, LandId) {
+        let mut domain = LandscapingDomain::default();
+        let kind = Shared::new(LandKind {
+            id: LandKey(1),
+            name: "test".to_string(),
+            width: 8,
+            height: 8,
+        });
+        let (land, create_land) = domain.create_land(&kind).unwrap();
+        create_land();
+        let land_state = domain.get_land_mut(land).unwrap();
+        land_state.moisture_capacity = vec![1.0; 64];
+        land_state.surface[[0, 0].fit(8)] = Surface::BASIN;
+        land_state.moisture[[0, 0].fit(8)] = 1.0;
+        (domain, land)
+    }
+
+    fn kind(key: usize, part: IrrigationPart) -> Shared<IrrigationKind> {
+        Shared::new(IrrigationKind {
+            id: IrrigationKey(key),
+            name: format!("{part:?}"),
+            part,
+        })
+    }
+
+    #[test]
+    fn test_sprinkler_loses_pressure_along_pipe() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 1.0 });
+        let pipe = kind(2, IrrigationPart::Pipe);
+        let sprinkler = kind(
+            3,
+            IrrigationPart::Sprinkler {
+                pressure: 0.5,
+                spread: 1,
+            },
+        );
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        domain.create_irrigation(land, [2, 0], &pipe).unwrap().1();
+        domain.create_irrigation(land, [3, 0], &pipe).unwrap().1();
+        let (id, create) = domain.create_irrigation(land, [4, 0], &sprinkler).unwrap();
+        create();
+
+        let events = domain.irrigate(0.5);
+
+        assert_eq!(events.len(), 4);
+        let pressure = domain.get_irrigation(id).unwrap().pressure;
+        assert!((pressure - 0.85).abs() < 0.0001);
+        let land = domain.get_land(land).unwrap();
+        assert!(land.moisture[[4, 0].fit(8)] > 0.0);
+        assert!(land.moisture[[4, 1].fit(8)] > 0.0);
+        assert_eq!(land.moisture[[7, 7].fit(8)], 0.0);
+    }
+
+    #[test]
+    fn test_sprinklers_share_limited_pump_flow() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 0.5 });
+        let sprinkler = kind(
+            3,
+            IrrigationPart::Sprinkler {
+                pressure: 0.5,
+                spread: 1,
+            },
+        );
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        let (a, create) = domain.create_irrigation(land, [2, 0], &sprinkler).unwrap();
+        create();
+        let (b, create) = domain.create_irrigation(land, [1, 1], &sprinkler).unwrap();
+        create();
+
+        domain.irrigate(1.0);
+
+        let expected = 0.5 * (1.0 - 0.05);
+        assert!((domain.get_irrigation(a).unwrap().pressure - expected).abs() < 0.0001);
+        assert!((domain.get_irrigation(b).unwrap().pressure - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_pump_stops_without_basin() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 1.0 });
+        let sprinkler = kind(
+            3,
+            IrrigationPart::Sprinkler {
+                pressure: 0.5,
+                spread: 0,
+            },
+        );
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        let (id, create) = domain.create_irrigation(land, [2, 0], &sprinkler).unwrap();
+        create();
+        domain.irrigate(1.0);
+        assert!(domain.get_irrigation(id).unwrap().pressure > 0.0);
+
+        domain.fill_basin(land, [0, 0]).unwrap()();
+        let events = domain.irrigate(1.0);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(domain.get_irrigation(id).unwrap().pressure, 0.0);
+    }
+
+    #[test]
+    fn test_sprinklers_drain_basin_until_pump_stops() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 1.0 });
+        let sprinkler = kind(
+            3,
+            IrrigationPart::Sprinkler {
+                pressure: 0.5,
+                spread: 0,
+            },
+        );
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        let (id, create) = domain.create_irrigation(land, [2, 0], &sprinkler).unwrap();
+        create();
+        domain.get_land_mut(land).unwrap().moisture[[0, 0].fit(8)] = 0.1;
+
+        domain.irrigate(0.1);
+        let land_state = domain.get_land(land).unwrap();
+        let sprayed = 0.5 * 0.95 * 0.1;
+        assert!((land_state.moisture[[0, 0].fit(8)] - (0.1 - sprayed)).abs() < 0.0001);
+        assert!((land_state.moisture[[2, 0].fit(8)] - sprayed).abs() < 0.0001);
+
+        for _ in 0..3 {
+            domain.irrigate(0.1);
+        }
+        let land_state = domain.get_land(land).unwrap();
+        assert_eq!(land_state.moisture[[0, 0].fit(8)], 0.0);
+        assert!((land_state.moisture[[2, 0].fit(8)] - 0.1).abs() < 0.0001);
+        assert_eq!(land_state.water, 1.0);
+        assert_eq!(domain.get_irrigation(id).unwrap().pressure, 0.0);
+    }
+
+    #[test]
+    fn test_basin_disperses_water_while_sprinkler_works() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 1.0 });
+        let sprinkler = kind(
+            3,
+            IrrigationPart::Sprinkler {
+                pressure: 0.5,
+                spread: 0,
+            },
+        );
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        let (id, create) = domain.create_irrigation(land, [2, 0], &sprinkler).unwrap();
+        create();
+        let mut random = Random::with_seed(1);
+        for _ in 0..20 {
+            domain.update(0.5, &mut random);
+        }
+        domain.get_land_mut(land).unwrap().moisture[[0, 2].fit(8)] = 0.0;
+
+        domain.update(0.5, &mut random);
+
+        let land_state = domain.get_land(land).unwrap();
+        assert_eq!(land_state.water, 1.0);
+        assert!(land_state.moisture[[0, 2].fit(8)] > 0.0);
+        assert!(land_state.moisture[[2, 0].fit(8)] > 0.0);
+        assert!(domain.get_irrigation(id).unwrap().pressure > 0.0);
+    }
+
+    #[test]
+    fn test_pump_requires_basin_nearby() {
+        let (mut domain, land) = prepare_domain();
+        let pump = kind(1, IrrigationPart::Pump { flow: 1.0 });
+
+        let result = domain.create_irrigation(land, [3, 3], &pump).map(|_| ());
+        assert!(matches!(
+            result,
+            Err(LandscapingError::BasinNotFound { .. })
+        ));
+
+        domain.create_irrigation(land, [1, 0], &pump).unwrap().1();
+        let result = domain.create_irrigation(land, [1, 0], &pump).map(|_| ());
+        assert!(matches!(
+            result,
+            Err(LandscapingError::IrrigationPlaceOccupied { .. })
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::landscaping::{Land, LandscapingDomain};
+use crate::landscaping::{Irrigation, Land, LandscapingDomain};
 
 impl LandscapingDomain {
     pub fn load_lands(&mut self, lands: Vec<Land>) {
@@ -7,4 +7,9 @@ impl LandscapingDomain {
             self.lands.insert(land.id, land);
         }
     }
+
+    pub fn load_irrigation(&mut self, irrigation: Vec<Irrigation>, sequence: usize) {
+        self.irrigation_id.set(sequence);
+        self.irrigation.extend(irrigation);
+    }
 }
@@ -6,6 +6,7 @@ pub use update::*;
 
 mod commands;
 mod domain;
+mod irrigation;
 mod loading;
 mod queries;
 mod update;
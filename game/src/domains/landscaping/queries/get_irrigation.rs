@@ -0,0 +1,18 @@
+use crate::landscaping::{
+    Irrigation, IrrigationId, LandId, LandscapingDomain, LandscapingError, Place,
+};
+
+impl LandscapingDomain {
+    pub fn get_irrigation(&self, id: IrrigationId) -> Result<&Irrigation, LandscapingError> {
+        self.irrigation
+            .iter()
+            .find(|irrigation| irrigation.id == id)
+            .ok_or(LandscapingError::IrrigationNotFound { id })
+    }
+
+    pub fn get_irrigation_at(&self, land: LandId, place: Place) -> Option<&Irrigation> {
+        self.irrigation
+            .iter()
+            .find(|irrigation| irrigation.land == land && irrigation.place == place)
+    }
+}
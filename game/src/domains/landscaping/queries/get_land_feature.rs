@@ -1,5 +1,5 @@
-use crate::landscaping::{Land, LandscapingError, Place};
-use crate::math::ArrayIndex;
+use crate::landscaping::{Land, LandscapingError, Place, Surface};
+use crate::math::{ArrayIndex, Rect};
 
 impl Land {
     pub fn get_moisture(&self, place: Place) -> Result<f32, LandscapingError> {
@@ -40,4 +40,52 @@ impl Land {
             Err(LandscapingError::OutOfLand { place, id: self.id })
         }
     }
+
+    /// Returns places adjacent to given one by side within land bounds.
+    pub fn get_neighbours(&self, place: Place) -> Vec<Place> {
+        let [x, y] = place;
+        let mut neighbours = Vec::with_capacity(4);
+        if x > 0 {
+            neighbours.push([x - 1, y]);
+        }
+        if y > 0 {
+            neighbours.push([x, y - 1]);
+        }
+        if x + 1 < self.kind.width {
+            neighbours.push([x + 1, y]);
+        }
+        if y + 1 < self.kind.height {
+            neighbours.push([x, y + 1]);
+        }
+        neighbours
+    }
+
+    pub fn has_basin_nearby(&self, place: Place) -> bool {
+        self.get_neighbours(place)
+            .into_iter()
+            .any(|neighbour| self.surface[neighbour.fit(self.kind.width)] == Surface::BASIN)
+    }
+
+    /// Returns indices of basin places adjacent to given one.
+    pub fn get_basins_nearby(&self, place: Place) -> Vec<usize> {
+        self.get_neighbours(place)
+            .into_iter()
+            .map(|neighbour| neighbour.fit(self.kind.width))
+            .filter(|index| self.surface[*index] == Surface::BASIN)
+            .collect()
+    }
+
+    pub fn is_moisture_changed(&self, rect: Rect) -> bool {
+        let [x, y, width, height] = rect;
+        for y in y..y + height {
+            for x in x..x + width {
+                let index = [x, y].fit(self.kind.width);
+                let delta = self.moisture[index] - self.observed_moisture[index];
+                if delta.abs() >= Land::MOISTURE_TOLERANCE {
+                    return true;
+                }
+            }
+        }
+        false
+    }
 }
@@ -0,0 +1,17 @@
+use crate::landscaping::{LandId, Landscaping, LandscapingDomain, LandscapingError};
+use crate::math::{Array, Rect};
+
+impl LandscapingDomain {
+    pub fn inspect_moisture(
+        &self,
+        id: LandId,
+        rect: Rect,
+    ) -> Result<Landscaping, LandscapingError> {
+        let land = self.get_land(id)?;
+        Ok(Landscaping::MoistureInspected {
+            land: id,
+            rect,
+            moisture: land.moisture.copy(land.kind.width, rect),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+mod get_irrigation;
 mod get_land;
 mod get_land_feature;
-mod inspect_land;
+mod inspect_moisture;
@@ -1,100 +1,319 @@
-use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::mem::take;
 
-use rand::rngs::ThreadRng;
-use rand::Rng;
-
-use crate::landscaping::{Land, LandId, Landscaping, LandscapingDomain, LandscapingError, Surface};
-use crate::math::TileOffsetMath;
+use crate::landscaping::{
+    Land, LandId, Landscaping, LandscapingDomain, LandscapingError, Place, Surface,
+};
+use crate::math::{Array, ArrayIndex, Random};
 
 impl LandscapingDomain {
     pub fn request_consumption(
         &mut self,
         land: LandId,
-        place: usize,
+        place: Place,
         expected: f32,
     ) -> Result<f32, LandscapingError> {
         let land = self.get_land_mut(land)?;
-        let moisture = land.moisture[place];
+        let moisture = land.get_moisture(place)?;
         let delta = expected.min(moisture);
+        let place = place.fit(land.kind.width);
         land.moisture[place] -= delta;
+        land.mark_moisture_changed(place);
         Ok(delta)
     }
 
-    pub fn update(&mut self, time: f32, random: ThreadRng) -> Vec<Landscaping> {
-        for land in self.lands.values_mut() {
-            LandscapingDomain::drain(land, time, random.clone());
+    pub fn integrate_weather(
+        &mut self,
+        land: LandId,
+        rainfall: f32,
+        evaporation: f32,
+    ) -> Result<(), LandscapingError> {
+        let land = self.get_land_mut(land)?;
+        land.rainfall = rainfall;
+        land.evaporation = evaporation;
+        Ok(())
+    }
+
+    pub fn integrate_shelter(
+        &mut self,
+        land: LandId,
+        sheltered: Vec<bool>,
+    ) -> Result<(), LandscapingError> {
+        let land = self.get_land_mut(land)?;
+        land.sheltered = sheltered;
+        Ok(())
+    }
+
+    pub fn update(&mut self, time: f32, random: &mut Random) -> Vec<Landscaping> {
+        self.lands_update += time;
+        if self.lands_update < self.lands_update_interval {
+            return vec![];
+        }
+        let time = take(&mut self.lands_update);
+        // iterates in order of lands to keep random numbers and events order deterministic
+        let mut lands: Vec<LandId> = self.lands.keys().cloned().collect();
+        lands.sort_by_key(|land| land.0);
+        for land in &lands {
+            let land = self.lands.get_mut(land).unwrap();
+            LandscapingDomain::drain(land, time, random);
             LandscapingDomain::disperse_moisture_from_water(land, time, 0.75);
         }
-        vec![]
+        let mut events = self.irrigate(time);
+        for land in &lands {
+            let land = self.lands.get_mut(land).unwrap();
+            events.extend(LandscapingDomain::inspect_changed_moisture(land));
+        }
+        events
     }
 
-    pub fn drain(land: &mut Land, time: f32, mut random: impl Rng) {
-        for moisture in land.moisture.iter_mut() {
-            let heat = random.gen_range(0.0025..0.0035);
-            *moisture = (*moisture - heat * time).max(0.0);
+    pub fn drain(land: &mut Land, time: f32, random: &mut Random) {
+        for place in 0..land.moisture.len() {
+            let heat = random.range(0.0025, 0.0035) * land.evaporation;
+            let rainfall = match land.sheltered.get(place) {
+                Some(true) => 0.0,
+                _ => land.rainfall,
+            };
+            let moisture = land.moisture[place];
+            let capacity = land.moisture_capacity[place];
+            // rain never soaks place over its capacity
+            let rain = (rainfall * time).min((capacity - moisture).max(0.0));
+            let drained = (moisture + rain - heat * time).max(0.0);
+            if drained != moisture {
+                land.moisture[place] = drained;
+                land.mark_moisture_changed(place);
+            }
         }
+        let drying = (land.evaporation - 1.0).max(0.0) * Land::BASIN_EVAPORATION;
+        land.water = (land.water + (land.rainfall - drying) * time).clamp(0.0, 1.0);
     }
 
+    /// Spreads water of basins over land in breadth-first order,
+    /// so every place is moistened by the nearest basin only.
     pub fn disperse_moisture_from_water(land: &mut Land, time: f32, pressure: f32) {
-        let mut source = HashSet::new();
-        let mut flow = Vec::with_capacity(128);
+        let [width, height] = [land.kind.width, land.kind.height];
+        let mut visited = vec![false; land.surface.len()];
+        let mut flow = VecDeque::new();
         for (place, surface) in land.surface.iter().enumerate() {
-            if surface == &Surface::BASIN {
-                source.insert(place);
-                let directions = [
-                    [-1, -1],
-                    [-1, 0],
-                    [-1, 1],
-                    [0, 1],
-                    [1, 1],
-                    [1, 0],
-                    [1, -1],
-                    [0, -1],
-                ];
-                let directions = directions.map(|offset| offset.fit_offset(land.kind.width));
-                let mut fluid = 1;
-                for direction in directions {
-                    let neighbor = place as isize - direction;
-                    if neighbor < 0 {
-                        continue;
-                    }
-                    let neighbor = neighbor as usize;
-                    if neighbor >= land.surface.len() {
-                        continue;
-                    }
-                    if land.surface[neighbor] == Surface::BASIN {
+            if *surface != Surface::BASIN {
+                continue;
+            }
+            visited[place] = true;
+            let [x, y] = [place % width, place / width];
+            let mut fluid = 0;
+            for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                    if land.surface[[nx, ny].fit(width)] == Surface::BASIN {
                         fluid += 1;
                     }
                 }
-                let fluid = (fluid as f32 / 8.0).min(1.0);
-                flow.push((place, fluid));
             }
+            let fluid = (fluid as f32 / 8.0).min(1.0) * land.water;
+            flow.push_back((place, fluid));
         }
-        let directions = [[-1, 0], [1, 0], [0, -1], [0, 1]];
-        let directions = directions.map(|offset| offset.fit_offset(land.kind.width));
-        while let Some((place, fluid)) = flow.pop() {
+        while let Some((place, fluid)) = flow.pop_front() {
             let volume = fluid * time;
             let moisture = land.moisture[place];
             let moisture_capacity = land.moisture_capacity[place];
-            land.moisture[place] = (moisture + volume).min(moisture_capacity);
-            for direction in directions {
-                let next_place = place as isize - direction;
-                if next_place < 0 {
-                    continue;
-                }
-                let next_place = next_place as usize;
-                if next_place >= land.surface.len() || source.contains(&next_place) {
+            let dispersed = (moisture + volume).min(moisture_capacity);
+            if dispersed != moisture {
+                land.moisture[place] = dispersed;
+                land.mark_moisture_changed(place);
+            }
+            let [x, y] = [place % width, place / width];
+            let neighbours = [
+                (x > 0).then(|| place - 1),
+                (x + 1 < width).then(|| place + 1),
+                (y > 0).then(|| place - width),
+                (y + 1 < height).then(|| place + width),
+            ];
+            for next_place in neighbours.into_iter().flatten() {
+                if visited[next_place] {
                     continue;
                 }
                 let moisture_capacity = land.moisture_capacity[next_place];
-                let fluid = moisture_capacity * pressure * fluid;
+                let slope = land.elevation[place] as f32 - land.elevation[next_place] as f32;
+                let slope = (1.0 + slope * Land::ELEVATION_FLOW).max(0.0);
+                let fluid = (moisture_capacity * pressure * slope * fluid).min(1.0);
                 if fluid > 0.001 {
-                    source.insert(next_place);
-                    flow.insert(0, (next_place, fluid))
+                    visited[next_place] = true;
+                    flow.push_back((next_place, fluid));
                 }
             }
         }
     }
 
+    /// Inspects regions of land where moisture noticeably changed since last inspection.
+    ///
+    /// Only regions written since last inspection are compared with observed moisture.
+    pub fn inspect_changed_moisture(land: &mut Land) -> Vec<Landscaping> {
+        let [width, height] = [land.kind.width, land.kind.height];
+        let size = Land::REGION_SIZE;
+        let columns = width.div_ceil(size);
+        let mut events = vec![];
+        for y in (0..height).step_by(size) {
+            for x in (0..width).step_by(size) {
+                let region = (y / size) * columns + x / size;
+                if !take(&mut land.dirty_regions[region]) {
+                    continue;
+                }
+                let rect = [x, y, size.min(width - x), size.min(height - y)];
+                if !land.is_moisture_changed(rect) {
+                    continue;
+                }
+                let moisture = land.moisture.copy(width, rect);
+                land.observed_moisture.paste(width, rect, &moisture);
+                events.push(Landscaping::MoistureInspected {
+                    land: land.id,
+                    rect,
+                    moisture,
+                });
+            }
+        }
+        events
+    }
+
     pub fn disperse_moisture(_land: &mut Land) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Shared;
+    use crate::landscaping::{
+        Land, LandId, LandKey, LandKind, Landscaping, LandscapingDomain, Surface,
+    };
+    use crate::math::{ArrayIndex, Random};
+
+    fn prepare_domain() -> (LandscapingDomain, LandId) {
+        let mut domain = LandscapingDomain::default();
+        let kind = Shared::new(LandKind {
+            id: LandKey(1),
+            name: "test".to_string(),
+            width: 4,
+            height: 4,
+        });
+        let (land, create_land) = domain.create_land(&kind).unwrap();
+        create_land();
+        let land_state = domain.get_land_mut(land).unwrap();
+        land_state.moisture_capacity = vec![0.5; 16];
+        (domain, land)
+    }
+
+    #[test]
+    fn test_rain_soaks_land_up_to_capacity() {
+        let (mut domain, land) = prepare_domain();
+        domain.get_land_mut(land).unwrap().water = 0.0;
+        domain.integrate_weather(land, 0.01, 0.25).unwrap();
+
+        domain.update(100.0, &mut Random::with_seed(1));
+
+        let land = domain.get_land(land).unwrap();
+        assert_eq!(land.water, 1.0);
+        for moisture in &land.moisture {
+            assert!(*moisture > 0.4 && *moisture <= 0.5);
+        }
+    }
+
+    #[test]
+    fn test_rain_not_falls_under_roof() {
+        let (mut domain, land) = prepare_domain();
+        let mut sheltered = vec![false; 16];
+        sheltered[[1, 2].fit(4)] = true;
+        domain.integrate_shelter(land, sheltered).unwrap();
+        domain.integrate_weather(land, 0.01, 0.0).unwrap();
+
+        domain.update(10.0, &mut Random::with_seed(1));
+
+        let land = domain.get_land(land).unwrap();
+        assert_eq!(land.moisture[[1, 2].fit(4)], 0.0);
+        assert!(land.moisture[[2, 2].fit(4)] > 0.0);
+    }
+
+    #[test]
+    fn test_drought_dries_basins() {
+        let (mut domain, land) = prepare_domain();
+        domain.get_land_mut(land).unwrap().surface[[0, 0].fit(4)] = Surface::BASIN;
+        domain.integrate_weather(land, 0.0, 2.5).unwrap();
+
+        domain.update(100.0, &mut Random::with_seed(1));
+        let water = 1.0 - 100.0 * 1.5 * Land::BASIN_EVAPORATION;
+        assert!((domain.get_land(land).unwrap().water - water).abs() < 0.0001);
+
+        domain.update(2000.0, &mut Random::with_seed(1));
+        let land = domain.get_land(land).unwrap();
+        assert_eq!(land.water, 0.0);
+        assert_eq!(land.moisture[[1, 0].fit(4)], 0.0);
+    }
+
+    #[test]
+    fn test_lands_updated_at_interval() {
+        let (mut domain, land) = prepare_domain();
+        domain.integrate_weather(land, 0.01, 0.0).unwrap();
+
+        domain.update(0.3, &mut Random::with_seed(1));
+        assert_eq!(domain.get_land(land).unwrap().moisture[0], 0.0);
+
+        domain.update(0.3, &mut Random::with_seed(1));
+        let moisture = domain.get_land(land).unwrap().moisture[0];
+        assert!((moisture - 0.006).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_only_changed_regions_inspected() {
+        let mut domain = LandscapingDomain::default();
+        let kind = Shared::new(LandKind {
+            id: LandKey(1),
+            name: "test".to_string(),
+            width: 40,
+            height: 20,
+        });
+        let (land, create_land) = domain.create_land(&kind).unwrap();
+        create_land();
+        let land_state = domain.get_land_mut(land).unwrap();
+        land_state.moisture_capacity = vec![1.0; 800];
+        land_state.moisture[[35, 18].fit(40)] = 0.5;
+
+        let events = domain.update(1.0, &mut Random::with_seed(1));
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            Landscaping::MoistureInspected { rect, moisture, .. }
+                if *rect == [32, 16, 8, 4] && moisture.len() == 32
+        ));
+        let events = domain.update(1.0, &mut Random::with_seed(1));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_only_dirty_regions_compared() {
+        let (mut domain, land) = prepare_domain();
+        domain.update(1.0, &mut Random::with_seed(1));
+        let land_state = domain.get_land_mut(land).unwrap();
+        land_state.moisture[[1, 1].fit(4)] = 0.5;
+
+        let events = LandscapingDomain::inspect_changed_moisture(land_state);
+        assert!(events.is_empty());
+
+        land_state.mark_moisture_changed([1, 1].fit(4));
+        let events = LandscapingDomain::inspect_changed_moisture(land_state);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_water_flows_downhill() {
+        let (mut domain, land) = prepare_domain();
+        let land_state = domain.get_land_mut(land).unwrap();
+        land_state.surface[[1, 1].fit(4)] = Surface::BASIN;
+        land_state.elevation = vec![1; 16];
+        land_state.elevation[[2, 1].fit(4)] = 0;
+        land_state.elevation[[0, 1].fit(4)] = 2;
+
+        LandscapingDomain::disperse_moisture_from_water(land_state, 0.1, 0.75);
+
+        let downhill = land_state.moisture[[2, 1].fit(4)];
+        let flat = land_state.moisture[[1, 2].fit(4)];
+        let uphill = land_state.moisture[[0, 1].fit(4)];
+        assert!(downhill > flat);
+        assert!(flat > uphill);
+    }
+}
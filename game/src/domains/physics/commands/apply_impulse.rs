@@ -0,0 +1,24 @@
+use crate::math::VectorMath;
+use crate::physics::Physics::BodyPushed;
+use crate::physics::{BodyId, Physics, PhysicsDomain, PhysicsError};
+
+impl PhysicsDomain {
+    /// Pushes body with given velocity in addition to its own movement,
+    /// push fades out over time according to damping of body kind.
+    pub fn apply_impulse(
+        &mut self,
+        id: BodyId,
+        impulse: [f32; 2],
+    ) -> Result<impl FnOnce() -> Vec<Physics> + '_, PhysicsError> {
+        let body = self.get_body_mut(id)?;
+        let command = move || {
+            body.impulse = body.impulse.add(impulse);
+            vec![BodyPushed {
+                id,
+                space: body.space,
+                impulse,
+            }]
+        };
+        Ok(command)
+    }
+}
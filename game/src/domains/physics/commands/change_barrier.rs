@@ -6,14 +6,15 @@ impl PhysicsDomain {
         id: BarrierId,
         active: bool,
     ) -> Result<impl FnOnce() -> Vec<Physics> + '_, PhysicsError> {
-        let barrier = self.get_barrier_mut(id)?;
+        let barrier = self.get_barrier(id)?;
+        let space = barrier.space;
+        let previous = barrier.shape();
         let command = move || {
+            let barrier = self.get_barrier_mut(id).unwrap();
             barrier.active = active;
-            vec![Physics::BarrierChanged {
-                id,
-                space: barrier.space,
-                active: barrier.active,
-            }]
+            let shape = barrier.shape();
+            self.barriers_hash.replace(space, previous, shape);
+            vec![Physics::BarrierChanged { id, space, active }]
         };
         Ok(command)
     }
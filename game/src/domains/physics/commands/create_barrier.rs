@@ -1,5 +1,5 @@
 use crate::collections::Shared;
-use crate::math::VectorMath;
+use crate::math::{Rotation, VectorMath};
 use crate::physics::{
     Barrier, BarrierId, BarrierKind, Physics, PhysicsDomain, PhysicsError, SpaceId,
 };
@@ -10,6 +10,7 @@ impl PhysicsDomain {
         space: SpaceId,
         kind: Shared<BarrierKind>,
         position: [f32; 2],
+        rotation: Rotation,
         active: bool,
         overlapping: bool,
     ) -> Result<(BarrierId, impl FnOnce() -> Vec<Physics> + '_), PhysicsError> {
@@ -20,10 +21,10 @@ impl PhysicsDomain {
             position,
             space,
             active,
+            rotation,
         };
-        let i: usize = space.into();
         if !overlapping {
-            for barrier in &self.barriers[i] {
+            for barrier in self.barriers.get(&space).into_iter().flatten() {
                 if barrier.position.to_tile() == position.to_tile() {
                     return Err(PhysicsError::BarrierCreationOverlaps { other: barrier.id });
                 }
@@ -36,9 +37,11 @@ impl PhysicsDomain {
                 space: barrier.space,
                 position: barrier.position,
                 active: barrier.active,
+                rotation: barrier.rotation,
             }];
             self.barriers_sequence += 1;
-            self.barriers[i].push(barrier);
+            self.barriers_hash.insert(space, barrier.shape());
+            self.barriers.entry(space).or_default().push(barrier);
             events
         };
         Ok((id, operation))
@@ -1,5 +1,5 @@
 use crate::collections::Shared;
-use crate::math::VectorMath;
+use crate::math::{Rotation, VectorMath};
 use crate::physics::Physics::BarrierCreated;
 use crate::physics::{
     Barrier, BarrierId, BarrierKind, Physics, PhysicsDomain, PhysicsError, Sensor, SensorId,
@@ -23,7 +23,7 @@ impl PhysicsDomain {
         PhysicsError,
     > {
         if !overlapping {
-            for barrier in &self.barriers[space.0] {
+            for barrier in self.barriers.get(&space).into_iter().flatten() {
                 if barrier.position.to_tile() == position.to_tile() {
                     return Err(PhysicsError::BarrierCreationOverlaps { other: barrier.id });
                 }
@@ -36,6 +36,7 @@ impl PhysicsDomain {
             position,
             space,
             active: true,
+            rotation: Rotation::A000,
         };
         let sensor_id = SensorId(self.sensors_sequence + 1);
         let sensor = Sensor {
@@ -53,11 +54,13 @@ impl PhysicsDomain {
                 space: barrier.space,
                 position: barrier.position,
                 active: barrier.active,
+                rotation: barrier.rotation,
             }];
             self.barriers_sequence += 1;
-            self.barriers[space.0].push(barrier);
+            self.barriers_hash.insert(space, barrier.shape());
+            self.barriers.entry(space).or_default().push(barrier);
             self.sensors_sequence += 1;
-            self.sensors[space.0].push(sensor);
+            self.sensors.entry(space).or_default().push(sensor);
             events
         };
         Ok((barrier_id, sensor_id, operation))
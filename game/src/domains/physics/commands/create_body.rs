@@ -15,12 +15,16 @@ impl PhysicsDomain {
             position,
             destination: position,
             space,
+            path: vec![],
+            velocity: [0.0, 0.0],
+            impulse: [0.0, 0.0],
         };
         let mut domain = TrustedRef::from(self);
         let command = move || {
             let events = vec![];
             domain.bodies_sequence.register(id.0);
-            domain.bodies[space.0].push(body);
+            domain.bodies_hash.insert(space, body.shape());
+            domain.bodies.entry(space).or_default().push(body);
             events
         };
         Ok(command)
@@ -0,0 +1,20 @@
+use crate::physics::{Physics, PhysicsDomain, PhysicsError, SpaceId};
+
+impl PhysicsDomain {
+    pub fn create_path<'operation>(
+        &'operation mut self,
+        id: SpaceId,
+        tile: [usize; 2],
+    ) -> Result<impl FnOnce() -> Vec<Physics> + 'operation, PhysicsError> {
+        let space = self.get_space_mut(id)?;
+        let [x, y] = tile;
+        if y >= space.paths.len() || x >= space.paths[y].len() {
+            return Err(PhysicsError::TileOutOfSpace { space: id, tile });
+        }
+        let operation = move || {
+            space.paths[y][x] = true;
+            vec![]
+        };
+        Ok(operation)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::collections::Shared;
+use crate::math::VectorMath;
 use crate::physics::{Physics, PhysicsDomain, PhysicsError, Space, SpaceId, SpaceKind};
 
 impl PhysicsDomain {
@@ -7,15 +8,20 @@ impl PhysicsDomain {
         kind: &Shared<SpaceKind>,
     ) -> Result<(SpaceId, impl FnOnce() -> Vec<Physics> + '_), PhysicsError> {
         let id = SpaceId(self.spaces_sequence + 1);
+        let [columns, rows] = kind.bounds.to_tile();
         let space = Space {
             id,
             kind: kind.clone(),
-            holes: vec![vec![0; 128]; 128],
+            holes: vec![vec![0; columns]; rows],
+            paths: vec![vec![false; columns]; rows],
         };
         let command = move || {
             let events = vec![];
             self.barriers_sequence += 1;
             self.spaces_sequence += 1;
+            self.bodies.insert(id, vec![]);
+            self.barriers.insert(id, vec![]);
+            self.sensors.insert(id, vec![]);
             self.spaces.push(space);
             events
         };
@@ -9,11 +9,13 @@ impl PhysicsDomain {
         let barrier = self.get_barrier(id)?;
         let space = barrier.space;
         let command = move || {
-            let index = self.barriers[space.0]
+            let barriers = self.barriers.get_mut(&space).unwrap();
+            let index = barriers
                 .iter()
                 .position(|barrier| barrier.id == id)
                 .unwrap();
-            let barrier = self.barriers[space.0].remove(index);
+            let barrier = barriers.remove(index);
+            self.barriers_hash.remove(space, barrier.shape());
 
             vec![BarrierDestroyed {
                 id,
@@ -8,11 +8,9 @@ impl PhysicsDomain {
         let sensor = self.get_sensor(id)?;
         let space = sensor.space;
         let command = move || {
-            let index = self.sensors[space.0]
-                .iter()
-                .position(|sensor| sensor.id == id)
-                .unwrap();
-            let _sensor = self.sensors[space.0].remove(index);
+            let sensors = self.sensors.get_mut(&space).unwrap();
+            let index = sensors.iter().position(|sensor| sensor.id == id).unwrap();
+            sensors.remove(index);
 
             vec![]
         };
@@ -0,0 +1,22 @@
+use crate::physics::{Physics, PhysicsDomain, PhysicsError, SpaceId};
+
+impl PhysicsDomain {
+    pub fn destroy_space(
+        &mut self,
+        id: SpaceId,
+    ) -> Result<impl FnOnce() -> Vec<Physics> + '_, PhysicsError> {
+        self.get_space(id)?;
+        let command = move || {
+            self.spaces.retain(|space| space.id != id);
+            for body in self.bodies.remove(&id).unwrap_or_default() {
+                self.bodies_hash.remove(id, body.shape());
+            }
+            for barrier in self.barriers.remove(&id).unwrap_or_default() {
+                self.barriers_hash.remove(id, barrier.shape());
+            }
+            self.sensors.remove(&id);
+            vec![]
+        };
+        Ok(command)
+    }
+}
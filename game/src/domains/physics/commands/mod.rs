@@ -1,11 +1,15 @@
+mod apply_impulse;
 mod change_barrier;
 mod create_barrier;
 mod create_barrier_sensor;
 mod create_body;
 mod create_hole;
+mod create_path;
 mod create_space;
 mod destroy_barrier;
 mod destroy_hole;
 mod destroy_sensor;
+mod destroy_space;
 mod move_body;
+mod move_body_along_path;
 mod stop_body;
@@ -9,6 +9,7 @@ impl PhysicsDomain {
         let body = self.get_body_mut(id)?;
         let command = move || {
             body.destination = destination;
+            body.path = vec![];
             vec![]
         };
         Ok(command)
@@ -0,0 +1,24 @@
+use crate::physics::{BodyId, Physics, PhysicsDomain, PhysicsError};
+
+impl PhysicsDomain {
+    pub fn move_body_along_path(
+        &mut self,
+        id: BodyId,
+        destination: [f32; 2],
+    ) -> Result<impl FnOnce() -> Vec<Physics> + '_, PhysicsError> {
+        let body = self.get_body(id)?;
+        let mut path = self.find_path(body.space, body.position, destination)?;
+        let body = self.get_body_mut(id)?;
+        let command = move || {
+            let event = Physics::BodyPathChanged {
+                id: body.id,
+                space: body.space,
+                path: path.clone(),
+            };
+            body.destination = path.remove(0);
+            body.path = path;
+            vec![event]
+        };
+        Ok(command)
+    }
+}
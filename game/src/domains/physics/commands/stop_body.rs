@@ -8,6 +8,9 @@ impl PhysicsDomain {
         let body = self.get_body_mut(id)?;
         let command = move || {
             body.destination = body.position;
+            body.path = vec![];
+            body.velocity = [0.0, 0.0];
+            body.impulse = [0.0, 0.0];
             vec![Physics::BodyPositionChanged {
                 id: body.id.into(),
                 space: body.space,
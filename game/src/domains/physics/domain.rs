@@ -1,36 +1,23 @@
 use core::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::collections::{Sequence, Shared};
-use crate::math::{Collider, Tile, VectorMath};
-
-pub const MAX_SPACES: usize = 128;
+use crate::math::{Collider, Rotation, Tile, VectorMath};
+use crate::physics::{Shape, SpatialHash};
 
+#[derive(Default)]
 pub struct PhysicsDomain {
     pub spaces: Vec<Space>,
     pub spaces_sequence: usize,
-    pub bodies: Vec<Vec<Body>>,
+    pub bodies: HashMap<SpaceId, Vec<Body>>,
     pub bodies_sequence: Sequence,
-    pub barriers: Vec<Vec<Barrier>>,
+    pub barriers: HashMap<SpaceId, Vec<Barrier>>,
     pub barriers_sequence: usize,
-    pub sensors: Vec<Vec<Sensor>>,
+    pub sensors: HashMap<SpaceId, Vec<Sensor>>,
     pub sensors_sequence: usize,
-}
-
-impl Default for PhysicsDomain {
-    fn default() -> Self {
-        Self {
-            spaces: vec![],
-            spaces_sequence: 0,
-            bodies: vec![vec![]; MAX_SPACES],
-            bodies_sequence: Sequence::default(),
-            barriers: vec![vec![]; MAX_SPACES],
-            barriers_sequence: 0,
-            sensors: vec![vec![]; MAX_SPACES],
-            sensors_sequence: 0,
-        }
-    }
+    pub bodies_hash: SpatialHash<BodyId>,
+    pub barriers_hash: SpatialHash<BarrierId>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -61,6 +48,22 @@ pub struct Space {
     pub id: SpaceId,
     pub kind: Shared<SpaceKind>,
     pub holes: Vec<Vec<u8>>,
+    /// Paved tiles, bodies move faster along them.
+    pub paths: Vec<Vec<bool>>,
+}
+
+impl Space {
+    /// Speed multiplier of body moving along path.
+    pub const PATH_SPEED: f32 = 1.5;
+
+    pub fn is_path(&self, position: [f32; 2]) -> bool {
+        let [x, y] = position.to_tile();
+        self.paths
+            .get(y)
+            .and_then(|row| row.get(x))
+            .cloned()
+            .unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -71,6 +74,21 @@ pub struct BodyKind {
     pub name: String,
     pub speed: f32,
     pub radius: f32,
+    /// Bit flags of collision groups body belongs to.
+    pub collision_layer: u8,
+    /// Bit flags of collision groups body collides with.
+    pub collision_mask: u8,
+    /// Rate of speed change to ease in and out movement, zero to reach speed instantly.
+    pub acceleration: f32,
+    /// Fraction of impulse velocity lost per second.
+    pub damping: f32,
+}
+
+impl BodyKind {
+    pub fn collides(&self, other: &BodyKind) -> bool {
+        self.collision_mask & other.collision_layer != 0
+            && other.collision_mask & self.collision_layer != 0
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -83,6 +101,22 @@ pub struct Body {
     pub position: [f32; 2],
     pub destination: [f32; 2],
     pub space: SpaceId,
+    pub path: Vec<[f32; 2]>,
+    /// Velocity of steering towards destination.
+    pub velocity: [f32; 2],
+    /// Velocity of external pushes, decays over time by damping of body kind.
+    pub impulse: [f32; 2],
+}
+
+impl Body {
+    pub fn shape(&self) -> Shape<BodyId> {
+        Shape {
+            id: self.id,
+            position: self.position,
+            bounds: [self.kind.radius * 2.0; 2],
+            solid: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -104,6 +138,18 @@ pub struct Barrier {
     pub position: [f32; 2],
     pub space: SpaceId,
     pub active: bool,
+    pub rotation: Rotation,
+}
+
+impl Barrier {
+    pub fn shape(&self) -> Shape<BarrierId> {
+        Shape {
+            id: self.id,
+            position: self.position,
+            bounds: self.rotation.apply_bounds(self.kind.bounds),
+            solid: self.active,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -113,6 +159,14 @@ pub struct SensorKind {
     pub id: SensorKey,
     pub name: String,
     pub radius: f32,
+    /// Kinds of bodies sensor reacts to, all bodies if empty.
+    pub filter: Vec<BodyKey>,
+}
+
+impl SensorKind {
+    pub fn accepts(&self, body: BodyKey) -> bool {
+        self.filter.is_empty() || self.filter.contains(&body)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -136,12 +190,18 @@ pub enum Physics {
         position: [f32; 2],
         destination: [f32; 2],
     },
+    BodyPathChanged {
+        id: BodyId,
+        space: SpaceId,
+        path: Vec<[f32; 2]>,
+    },
     BarrierCreated {
         id: BarrierId,
         key: BarrierKey,
         space: SpaceId,
         position: [f32; 2],
         active: bool,
+        rotation: Rotation,
     },
     BarrierChanged {
         id: BarrierId,
@@ -153,10 +213,23 @@ pub enum Physics {
         space: SpaceId,
         position: [f32; 2],
     },
+    SensorEntered {
+        sensor: SensorId,
+        body: BodyId,
+    },
+    SensorExited {
+        sensor: SensorId,
+        body: BodyId,
+    },
     SpaceUpdated {
         id: SpaceId,
         holes: Vec<Vec<u8>>,
     },
+    BodyPushed {
+        id: BodyId,
+        space: SpaceId,
+        impulse: [f32; 2],
+    },
 }
 
 impl Debug for Physics {
@@ -172,16 +245,78 @@ impl Debug for Physics {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PhysicsError {
-    SpaceNotFound { space: SpaceId },
-    BodyNotFound { id: BodyId },
-    BodyNotFoundAt { position: [f32; 2] },
-    BarrierCreationOverlaps { other: BarrierId },
-    BarrierNotFound { id: BarrierId },
-    BarrierNotFoundAt { position: [f32; 2] },
-    SensorNotFound { id: SensorId },
-    HoleNotFound { hole: [usize; 2] },
-    HoleAlreadyExists { hole: [usize; 2] },
-    HoleCreationContainsBody { hole: [usize; 2] },
+    SpaceNotFound {
+        space: SpaceId,
+    },
+    BodyNotFound {
+        id: BodyId,
+    },
+    BodyNotFoundAt {
+        position: [f32; 2],
+    },
+    BarrierCreationOverlaps {
+        other: BarrierId,
+    },
+    BarrierNotFound {
+        id: BarrierId,
+    },
+    BarrierNotFoundAt {
+        position: [f32; 2],
+    },
+    SensorNotFound {
+        id: SensorId,
+    },
+    HoleNotFound {
+        hole: [usize; 2],
+    },
+    HoleAlreadyExists {
+        hole: [usize; 2],
+    },
+    HoleCreationContainsBody {
+        hole: [usize; 2],
+    },
+    PathNotFound {
+        start: [f32; 2],
+        destination: [f32; 2],
+    },
+    TileOutOfSpace {
+        space: SpaceId,
+        tile: [usize; 2],
+    },
+}
+
+/// Physics object intersected by ray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RaycastTarget {
+    Hole { tile: [usize; 2], hole: u8 },
+    Barrier(BarrierId),
+    Body(BodyId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastHit {
+    pub target: RaycastTarget,
+    /// Point where ray enters target.
+    pub contact: [f32; 2],
+    pub distance: f32,
+    /// Center and size of target bounds.
+    pub position: [f32; 2],
+    pub bounds: [f32; 2],
+}
+
+impl RaycastHit {
+    pub fn covers(&self, point: [f32; 2]) -> bool {
+        let offset = point.sub(self.position);
+        offset[0].abs() <= self.bounds[0] / 2.0 && offset[1].abs() <= self.bounds[1] / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RaycastFilter {
+    /// Kinds of holes map tiles which stop ray.
+    pub holes: Vec<u8>,
+    pub barriers: bool,
+    pub bodies: bool,
 }
 
 pub struct Hole {
@@ -217,7 +352,7 @@ impl Collider for Barrier {
     #[inline]
     fn bounds(&self) -> [f32; 2] {
         if self.active {
-            self.kind.bounds
+            self.rotation.apply_bounds(self.kind.bounds)
         } else {
             [0.0, 0.0]
         }
@@ -225,9 +360,8 @@ impl Collider for Barrier {
 }
 
 impl BarrierKind {
-    pub fn tiles(&self, position: [f32; 2]) -> Vec<Tile> {
-        // TODO: rotation of barrier
-        let bounds = self.bounds.to_offset();
+    pub fn tiles(&self, position: [f32; 2], rotation: Rotation) -> Vec<Tile> {
+        let bounds = rotation.apply_bounds(self.bounds).to_offset();
         let [w, h] = bounds;
         let offset_x = (bounds[0] / 2) as isize;
         let offset_y = (bounds[1] / 2) as isize;
@@ -245,3 +379,27 @@ impl BarrierKind {
         tiles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::math::Rotation;
+    use crate::physics::{BarrierKey, BarrierKind};
+
+    #[test]
+    fn test_rotated_barrier_tiles() {
+        let kind = BarrierKind {
+            id: BarrierKey(1),
+            name: "cementer".to_string(),
+            bounds: [3.0, 1.0],
+        };
+        let position = [5.5, 5.5];
+        assert_eq!(
+            kind.tiles(position, Rotation::A000),
+            vec![[4, 5], [5, 5], [6, 5]]
+        );
+        assert_eq!(
+            kind.tiles(position, Rotation::A090),
+            vec![[5, 4], [5, 5], [5, 6]]
+        );
+    }
+}
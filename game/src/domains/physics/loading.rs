@@ -3,27 +3,37 @@ use crate::physics::{Barrier, Body, PhysicsDomain, Sensor, Space};
 impl PhysicsDomain {
     pub fn load_spaces(&mut self, spaces: Vec<Space>, sequence: usize) {
         self.spaces_sequence = sequence;
-        self.spaces.extend(spaces);
+        for space in spaces {
+            self.bodies.entry(space.id).or_default();
+            self.barriers.entry(space.id).or_default();
+            self.sensors.entry(space.id).or_default();
+            self.spaces.push(space);
+        }
     }
 
     pub fn load_bodies(&mut self, bodies: Vec<Body>) {
         for body in bodies {
             self.bodies_sequence.register(body.id.0);
-            self.bodies[body.space.0].push(body);
+            self.bodies_hash.insert(body.space, body.shape());
+            self.bodies.entry(body.space).or_default().push(body);
         }
     }
 
     pub fn load_barriers(&mut self, barriers: Vec<Barrier>, sequence: usize) {
         self.barriers_sequence = sequence;
         for barrier in barriers {
-            self.barriers[barrier.space.0].push(barrier);
+            self.barriers_hash.insert(barrier.space, barrier.shape());
+            self.barriers
+                .entry(barrier.space)
+                .or_default()
+                .push(barrier);
         }
     }
 
     pub fn load_sensors(&mut self, sensors: Vec<Sensor>, sequence: usize) {
         self.sensors_sequence = sequence;
         for sensor in sensors {
-            self.sensors[sensor.space.0].push(sensor);
+            self.sensors.entry(sensor.space).or_default().push(sensor);
         }
     }
 }
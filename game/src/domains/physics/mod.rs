@@ -2,10 +2,12 @@ pub use commands::*;
 pub use domain::*;
 pub use loading::*;
 pub use queries::*;
+pub use spatial::*;
 pub use update::*;
 
 mod commands;
 mod domain;
 mod loading;
 mod queries;
+mod spatial;
 mod update;
@@ -0,0 +1,20 @@
+use crate::math::VectorMath;
+use crate::physics::{BarrierId, PhysicsDomain, PhysicsError, SpaceId};
+
+impl PhysicsDomain {
+    /// Ensures that no barrier except given one occupies tile of position,
+    /// so new barrier can be created there over the given one.
+    pub fn ensure_no_overlap_except(
+        &self,
+        space: SpaceId,
+        position: [f32; 2],
+        except: BarrierId,
+    ) -> Result<(), PhysicsError> {
+        for barrier in self.barriers.get(&space).into_iter().flatten() {
+            if barrier.id != except && barrier.position.to_tile() == position.to_tile() {
+                return Err(PhysicsError::BarrierCreationOverlaps { other: barrier.id });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use crate::math::{find_path, smooth_path, test_line_of_sight, TileMath, VectorMath};
+use crate::physics::{PhysicsDomain, PhysicsError, SpaceId};
+
+const OBSTACLES: [u8; 2] = [1, 2];
+
+impl PhysicsDomain {
+    /// Plans walking route around holes and active barriers of space.
+    ///
+    /// Returns waypoints excluding start position, last one is exactly destination.
+    /// If destination tile itself is occupied, route is just straight line
+    /// to let body walk up to obstacle as before.
+    pub fn find_path(
+        &self,
+        space: SpaceId,
+        start: [f32; 2],
+        destination: [f32; 2],
+    ) -> Result<Vec<[f32; 2]>, PhysicsError> {
+        let space = self.get_space(space)?;
+        let mut barriers = HashSet::new();
+        for barrier in self.barriers.get(&space.id).into_iter().flatten() {
+            if barrier.active {
+                barriers.extend(barrier.kind.tiles(barrier.position, barrier.rotation));
+            }
+        }
+        let holes = &space.holes;
+        let start_tile = start.to_tile();
+        let destination_tile = destination.to_tile();
+        let [x, y] = destination_tile;
+        let occupied = barriers.contains(&destination_tile)
+            || holes
+                .get(y)
+                .and_then(|row| row.get(x))
+                .map(|hole| OBSTACLES.contains(hole))
+                .unwrap_or(true);
+        if occupied
+            || test_line_of_sight(start_tile, destination_tile, holes, &OBSTACLES, &barriers)
+        {
+            return Ok(vec![destination]);
+        }
+        let path = find_path(start_tile, destination_tile, holes, &OBSTACLES, &barriers)
+            .ok_or(PhysicsError::PathNotFound { start, destination })?;
+        let mut waypoints: Vec<[f32; 2]> =
+            smooth_path(start_tile, path, holes, &OBSTACLES, &barriers)
+                .into_iter()
+                .map(|tile| tile.position())
+                .collect();
+        waypoints.pop();
+        waypoints.push(destination);
+        Ok(waypoints)
+    }
+}
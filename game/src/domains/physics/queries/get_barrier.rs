@@ -2,7 +2,7 @@ use crate::physics::{Barrier, BarrierId, PhysicsDomain, PhysicsError};
 
 impl PhysicsDomain {
     pub fn get_barrier(&self, id: BarrierId) -> Result<&Barrier, PhysicsError> {
-        for barriers in self.barriers.iter() {
+        for barriers in self.barriers.values() {
             for barrier in barriers {
                 if barrier.id == id {
                     return Ok(barrier);
@@ -13,7 +13,7 @@ impl PhysicsDomain {
     }
 
     pub fn get_barrier_mut(&mut self, id: BarrierId) -> Result<&mut Barrier, PhysicsError> {
-        for barriers in self.barriers.iter_mut() {
+        for barriers in self.barriers.values_mut() {
             for barrier in barriers {
                 if barrier.id == id {
                     return Ok(barrier);
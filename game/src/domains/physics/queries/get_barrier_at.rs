@@ -3,9 +3,9 @@ use crate::physics::{Barrier, PhysicsDomain, SpaceId};
 
 impl PhysicsDomain {
     pub fn get_barrier_at(&self, space: SpaceId, position: [f32; 2]) -> Option<&Barrier> {
-        for barrier in self.barriers[space.0].iter() {
-            if test_rect_collision(position, [0.1; 2], barrier.position, barrier.kind.bounds) {
-                return Some(barrier);
+        for shape in self.barriers_hash.query(space, position, [0.1; 2]) {
+            if test_rect_collision(position, [0.1; 2], shape.position, shape.bounds) {
+                return self.get_barrier(shape.id).ok();
             }
         }
         return None;
@@ -2,7 +2,7 @@ use crate::physics::{Body, BodyId, PhysicsDomain, PhysicsError};
 
 impl PhysicsDomain {
     pub fn get_body_mut(&mut self, id: BodyId) -> Result<&mut Body, PhysicsError> {
-        for bodies in self.bodies.iter_mut() {
+        for bodies in self.bodies.values_mut() {
             for body in bodies {
                 if body.id == id {
                     return Ok(body);
@@ -13,7 +13,7 @@ impl PhysicsDomain {
     }
 
     pub fn get_body(&self, id: BodyId) -> Result<&Body, PhysicsError> {
-        for bodies in self.bodies.iter() {
+        for bodies in self.bodies.values() {
             for body in bodies {
                 if body.id == id {
                     return Ok(body);
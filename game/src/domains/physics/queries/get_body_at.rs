@@ -3,9 +3,9 @@ use crate::physics::{Body, PhysicsDomain, PhysicsError, SpaceId};
 
 impl PhysicsDomain {
     pub fn get_body_at(&self, space: SpaceId, position: [f32; 2]) -> Result<&Body, PhysicsError> {
-        for body in self.bodies[space.0].iter() {
-            if body.position.to_tile() == position.to_tile() {
-                return Ok(body);
+        for shape in self.bodies_hash.query(space, position, [1.0; 2]) {
+            if shape.position.to_tile() == position.to_tile() {
+                return self.get_body(shape.id);
             }
         }
         return Err(PhysicsError::BodyNotFoundAt { position });
@@ -2,7 +2,7 @@ use crate::physics::{PhysicsDomain, PhysicsError, Sensor, SensorId};
 
 impl PhysicsDomain {
     pub fn get_sensor(&self, id: SensorId) -> Result<&Sensor, PhysicsError> {
-        for sensors in self.sensors.iter() {
+        for sensors in self.sensors.values() {
             for sensor in sensors {
                 if sensor.id == id {
                     return Ok(sensor);
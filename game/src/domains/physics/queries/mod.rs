@@ -1,7 +1,9 @@
-mod cast_ray;
+mod ensure_no_overlap_except;
+mod find_path;
 mod get_barrier;
 mod get_barrier_at;
 mod get_body;
 mod get_body_at;
 mod get_sensor;
 mod get_space;
+mod raycast;
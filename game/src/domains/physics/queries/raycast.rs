@@ -0,0 +1,105 @@
+use crate::math::{intersect_ray_aabb, intersect_ray_circle, rasterize_line, TileMath, VectorMath};
+use crate::physics::{
+    PhysicsDomain, PhysicsError, RaycastFilter, RaycastHit, RaycastTarget, SpaceId,
+};
+
+impl PhysicsDomain {
+    /// Casts ray from start towards end, but not further than max distance.
+    ///
+    /// Returns all hits ordered by distance. Barriers and bodies containing start point
+    /// are ignored, so ray can be cast from body position without hitting itself.
+    pub fn raycast(
+        &self,
+        space: SpaceId,
+        start: [f32; 2],
+        end: [f32; 2],
+        max_distance: f32,
+        filter: &RaycastFilter,
+    ) -> Result<Vec<RaycastHit>, PhysicsError> {
+        let space = self.get_space(space)?;
+        let length = start.distance(end).min(max_distance);
+        if length <= 0.0 {
+            return Ok(vec![]);
+        }
+        let direction = start.direction_to(end);
+        let end = start.add(direction.mul(length));
+        let mut hits = vec![];
+
+        if !filter.holes.is_empty() {
+            for tile in rasterize_line(start.to_tile(), end.to_tile()) {
+                let [x, y] = tile;
+                let hole = match space.holes.get(y).and_then(|row| row.get(x)) {
+                    Some(hole) => *hole,
+                    None => continue,
+                };
+                if !filter.holes.contains(&hole) {
+                    continue;
+                }
+                let position = tile.position();
+                if let Some(distance) = intersect_ray_aabb(start, direction, position, [1.0; 2]) {
+                    if distance <= length {
+                        hits.push(RaycastHit {
+                            target: RaycastTarget::Hole { tile, hole },
+                            contact: start.add(direction.mul(distance)),
+                            distance,
+                            position,
+                            bounds: [1.0; 2],
+                        });
+                    }
+                }
+            }
+        }
+
+        let center = start.add(end).mul(0.5);
+        let area = [(end[0] - start[0]).abs(), (end[1] - start[1]).abs()];
+        if filter.barriers {
+            for shape in self.barriers_hash.query(space.id, center, area) {
+                if !shape.solid {
+                    continue;
+                }
+                let offset = start.sub(shape.position);
+                if offset[0].abs() <= shape.bounds[0] / 2.0
+                    && offset[1].abs() <= shape.bounds[1] / 2.0
+                {
+                    continue;
+                }
+                let intersection =
+                    intersect_ray_aabb(start, direction, shape.position, shape.bounds);
+                if let Some(distance) = intersection {
+                    if distance <= length {
+                        hits.push(RaycastHit {
+                            target: RaycastTarget::Barrier(shape.id),
+                            contact: start.add(direction.mul(distance)),
+                            distance,
+                            position: shape.position,
+                            bounds: shape.bounds,
+                        });
+                    }
+                }
+            }
+        }
+        if filter.bodies {
+            for shape in self.bodies_hash.query(space.id, center, area) {
+                let radius = shape.bounds[0] / 2.0;
+                if start.distance(shape.position) <= radius {
+                    continue;
+                }
+                let intersection = intersect_ray_circle(start, direction, shape.position, radius);
+                if let Some(distance) = intersection {
+                    if distance <= length {
+                        hits.push(RaycastHit {
+                            target: RaycastTarget::Body(shape.id),
+                            contact: start.add(direction.mul(distance)),
+                            distance,
+                            position: shape.position,
+                            bounds: shape.bounds,
+                        });
+                    }
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(hits)
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::math::{Collider, VectorMath};
+use crate::physics::SpaceId;
+
+pub const CELL_SIZE: f32 = 4.0;
+
+/// Axis aligned bounds of physics object registered in spatial hash.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shape<K> {
+    pub id: K,
+    pub position: [f32; 2],
+    pub bounds: [f32; 2],
+    pub solid: bool,
+}
+
+impl<K> Collider for Shape<K> {
+    fn position(&self) -> [f32; 2] {
+        self.position
+    }
+
+    #[inline]
+    fn bounds(&self) -> [f32; 2] {
+        if self.solid {
+            self.bounds
+        } else {
+            [0.0, 0.0]
+        }
+    }
+}
+
+/// Uniform grid of physics objects to find neighbours
+/// without iteration over all objects of space.
+pub struct SpatialHash<K> {
+    cells: HashMap<(SpaceId, [isize; 2]), Vec<Shape<K>>>,
+}
+
+impl<K> Default for SpatialHash<K> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+fn cells_of(position: [f32; 2], bounds: [f32; 2]) -> impl Iterator<Item = [isize; 2]> {
+    let [min_x, min_y] = position.sub(bounds.mul(0.5)).div(CELL_SIZE);
+    let [max_x, max_y] = position.add(bounds.mul(0.5)).div(CELL_SIZE);
+    let [min_x, min_y] = [min_x.floor() as isize, min_y.floor() as isize];
+    let [max_x, max_y] = [max_x.floor() as isize, max_y.floor() as isize];
+    (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| [x, y]))
+}
+
+impl<K: Copy + PartialEq> SpatialHash<K> {
+    pub fn insert(&mut self, space: SpaceId, shape: Shape<K>) {
+        for cell in cells_of(shape.position, shape.bounds) {
+            self.cells.entry((space, cell)).or_default().push(shape);
+        }
+    }
+
+    /// Removes shape previously inserted with same position and bounds.
+    pub fn remove(&mut self, space: SpaceId, shape: Shape<K>) {
+        for cell in cells_of(shape.position, shape.bounds) {
+            if let Some(shapes) = self.cells.get_mut(&(space, cell)) {
+                shapes.retain(|other| other.id != shape.id);
+                if shapes.is_empty() {
+                    self.cells.remove(&(space, cell));
+                }
+            }
+        }
+    }
+
+    pub fn replace(&mut self, space: SpaceId, previous: Shape<K>, shape: Shape<K>) {
+        self.remove(space, previous);
+        self.insert(space, shape);
+    }
+
+    /// Returns shapes registered in cells overlapped by given area, without duplicates.
+    pub fn query(&self, space: SpaceId, position: [f32; 2], bounds: [f32; 2]) -> Vec<Shape<K>> {
+        let mut shapes: Vec<Shape<K>> = vec![];
+        for cell in cells_of(position, bounds) {
+            if let Some(cell) = self.cells.get(&(space, cell)) {
+                for shape in cell {
+                    if !shapes.iter().any(|other| other.id == shape.id) {
+                        shapes.push(*shape);
+                    }
+                }
+            }
+        }
+        shapes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::{Shape, SpaceId, SpatialHash};
+
+    fn shape(id: usize, position: [f32; 2], bounds: [f32; 2]) -> Shape<usize> {
+        Shape {
+            id,
+            position,
+            bounds,
+            solid: true,
+        }
+    }
+
+    #[test]
+    fn test_query_finds_shapes_across_cells() {
+        let mut hash = SpatialHash::default();
+        let space = SpaceId(1);
+        hash.insert(space, shape(1, [4.0, 4.0], [2.0, 2.0]));
+        hash.insert(space, shape(2, [30.0, 30.0], [1.0, 1.0]));
+        let found = hash.query(space, [2.5, 2.5], [1.0, 1.0]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, 1);
+        assert!(hash.query(SpaceId(2), [4.0, 4.0], [1.0, 1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_removed_shape_not_found() {
+        let mut hash = SpatialHash::default();
+        let space = SpaceId(1);
+        let target = shape(1, [4.0, 4.0], [2.0, 2.0]);
+        hash.insert(space, target);
+        hash.remove(space, target);
+        assert!(hash.query(space, [4.0, 4.0], [8.0, 8.0]).is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use crate::math::{collide_circle_to_circle, test_collisions, VectorMath};
-use crate::physics::{Hole, Physics, PhysicsDomain};
-use std::collections::HashSet;
+use crate::physics::{
+    BarrierId, Body, BodyId, BodyKey, Hole, Physics, PhysicsDomain, Space, SpatialHash,
+};
+use std::collections::{HashMap, HashSet};
 
 const MAX_ELAPSED_TIME: f32 = 0.03; // 40 ms
+const SEPARATION_SPEED: f32 = 1.0;
 
 impl PhysicsDomain {
     pub fn update(&mut self, mut elapsed_time: f32) -> Vec<Physics> {
@@ -23,24 +26,44 @@ impl PhysicsDomain {
         let mut events = vec![];
 
         for space in self.spaces.iter() {
-            let bodies = &mut self.bodies[space.id.0];
-            let barriers = &mut self.barriers[space.id.0];
-            let sensors = &mut self.sensors[space.id.0];
+            let bodies = self.bodies.entry(space.id).or_default();
+            let sensors = self.sensors.entry(space.id).or_default();
+            let indices: HashMap<BodyId, usize> = bodies
+                .iter()
+                .enumerate()
+                .map(|(index, body)| (body.id, index))
+                .collect();
             for index in 0..bodies.len() {
                 let _id = bodies[index].id;
 
                 let body = &bodies[index];
-                let delta = body.kind.speed * time;
+                let mut destination = body.destination;
+                let mut distance = body.position.distance(destination);
 
-                let destination = body.destination;
+                if distance <= 0.00001 && !body.path.is_empty() {
+                    let body = &mut bodies[index];
+                    body.destination = body.path.remove(0);
+                    destination = body.destination;
+                    distance = body.position.distance(destination);
+                }
 
-                let distance = body.position.distance(destination);
+                let idle = distance <= 0.00001;
+                let body = &mut bodies[index];
+                let speed = if space.is_path(body.position) {
+                    body.kind.speed * Space::PATH_SPEED
+                } else {
+                    body.kind.speed
+                };
+                body.velocity = steer(body, speed, distance, time);
+                body.impulse = damp(body.impulse, body.kind.damping, time);
+                let movement = body.velocity.add(body.impulse).mul(time);
+                let body = &bodies[index];
 
-                if distance > 0.00001 {
-                    let body_next_position = if delta > distance {
+                if movement.length() > 0.00001 {
+                    let arrived = body.impulse.is_zero() && movement.length() >= distance;
+                    let body_next_position = if arrived {
                         destination
                     } else {
-                        let movement = body.position.direction_to(destination).mul(delta);
                         body.position.add(movement)
                     };
 
@@ -53,19 +76,30 @@ impl PhysicsDomain {
                     };
                     if holes_offsets.len() > 1 {
                         // body already blocked by holes, no need to test collision with barriers
+                        bodies[index].impulse = [0.0, 0.0];
                         continue;
                     }
 
-                    let offsets = match test_collisions(&body, body_next_position, &barriers) {
+                    let area = [body.kind.radius * 2.0; 2];
+                    let barriers = self.barriers_hash.query(space.id, body_next_position, area);
+                    let mut offsets = match test_collisions(&body, body_next_position, &barriers) {
                         None => holes_offsets,
                         Some(mut barrier_offsets) => {
                             barrier_offsets.extend(holes_offsets);
                             barrier_offsets
                         }
                     };
+                    offsets.extend(test_body_collisions(
+                        bodies,
+                        &indices,
+                        &self.bodies_hash,
+                        index,
+                        body_next_position,
+                    ));
 
                     if offsets.len() > 1 {
                         // blocked
+                        bodies[index].impulse = [0.0, 0.0];
                         continue;
                     }
 
@@ -77,9 +111,18 @@ impl PhysicsDomain {
                         body_next_position
                     };
                     let body = &mut bodies[index];
+                    let previous = body.shape();
                     body.position = position;
+                    if arrived {
+                        body.velocity = [0.0, 0.0];
+                    }
+                    if idle {
+                        // pushed body stays where it was pushed to
+                        body.destination = position;
+                    }
+                    self.bodies_hash.replace(space.id, previous, body.shape());
                     events.push(Physics::BodyPositionChanged {
-                        id: body.id.into(),
+                        id: body.id,
                         space: body.space,
                         position: body.position,
                         destination: body.destination,
@@ -87,29 +130,58 @@ impl PhysicsDomain {
                 }
             }
 
-            for index in 0..sensors.len() {
-                let sensor = &mut sensors[index];
+            events.extend(separate_bodies(
+                bodies,
+                &indices,
+                &space.holes,
+                &self.barriers_hash,
+                &mut self.bodies_hash,
+                time,
+            ));
+
+            let kinds: HashMap<BodyId, BodyKey> =
+                bodies.iter().map(|body| (body.id, body.kind.id)).collect();
+            for sensor in sensors.iter_mut() {
                 sensor.signals = vec![];
                 let mut now = HashSet::new();
-                for body in &self.bodies[space.id.0] {
+                let area = [sensor.kind.radius * 2.0; 2];
+                for body in self.bodies_hash.query(space.id, sensor.position, area) {
+                    match kinds.get(&body.id) {
+                        Some(kind) if sensor.kind.accepts(*kind) => {}
+                        _ => continue,
+                    }
                     let collision = collide_circle_to_circle(
                         sensor.position,
                         sensor.kind.radius,
                         body.position,
-                        body.kind.radius,
+                        body.bounds[0] / 2.0,
                     );
                     if let Some(_) = collision {
                         now.insert(body.id);
                         if !sensor.registered.contains(&body.id) {
                             sensor.registered.insert(body.id);
                             sensor.signals.push(body.position.sub(sensor.position));
+                            events.push(Physics::SensorEntered {
+                                sensor: sensor.id,
+                                body: body.id,
+                            });
                         }
                     }
                 }
-                for body in sensor.registered.clone() {
-                    if !now.contains(&body) {
-                        sensor.registered.remove(&body);
-                    }
+                let mut exited: Vec<BodyId> = sensor
+                    .registered
+                    .iter()
+                    .filter(|body| !now.contains(body))
+                    .cloned()
+                    .collect();
+                // sorted to keep events order deterministic for replay
+                exited.sort_by_key(|body| body.0);
+                for body in exited {
+                    sensor.registered.remove(&body);
+                    events.push(Physics::SensorExited {
+                        sensor: sensor.id,
+                        body,
+                    });
                 }
             }
         }
@@ -118,6 +190,40 @@ impl PhysicsDomain {
     }
 }
 
+/// Returns velocity of body steered towards its destination.
+///
+/// Body without acceleration moves at full speed immediately,
+/// otherwise it speeds up gradually and brakes to stop exactly at destination.
+pub fn steer(body: &Body, speed: f32, distance: f32, time: f32) -> [f32; 2] {
+    if distance <= 0.00001 {
+        return [0.0, 0.0];
+    }
+    let acceleration = body.kind.acceleration;
+    let direction = body.position.direction_to(body.destination);
+    if acceleration <= 0.0 {
+        return direction.mul(speed);
+    }
+    let braking_speed = (2.0 * acceleration * distance).sqrt();
+    let desired = direction.mul(speed.min(braking_speed));
+    let change = desired.sub(body.velocity);
+    let max_change = acceleration * time;
+    if change.length() > max_change {
+        body.velocity.add(change.normalize().mul(max_change))
+    } else {
+        desired
+    }
+}
+
+/// Returns impulse velocity decayed by damping.
+pub fn damp(impulse: [f32; 2], damping: f32, time: f32) -> [f32; 2] {
+    let impulse = impulse.mul((1.0 - damping * time).max(0.0));
+    if impulse.length() < 0.01 {
+        [0.0, 0.0]
+    } else {
+        impulse
+    }
+}
+
 pub fn generate_holes(
     position: [f32; 2],
     r: f32,
@@ -131,8 +237,8 @@ pub fn generate_holes(
     let mut holes = vec![];
     for hole_y in min_y..=max_y {
         for hole_x in min_x..=max_x {
-            let h = holes_map[hole_y][hole_x];
-            if filter.contains(&h) {
+            let hole = holes_map.get(hole_y).and_then(|row| row.get(hole_x));
+            if hole.map(|hole| filter.contains(hole)).unwrap_or(false) {
                 holes.push(Hole {
                     position: [hole_x as f32 + 0.5, hole_y as f32 + 0.5],
                     bounds: [1.0, 1.0],
@@ -142,3 +248,371 @@ pub fn generate_holes(
     }
     holes
 }
+
+/// Returns offsets of body moved to destination from other bodies it collides with.
+///
+/// Only bodies found in spatial hash near destination are tested,
+/// indices map their identifiers to positions in bodies of space.
+pub fn test_body_collisions(
+    bodies: &[Body],
+    indices: &HashMap<BodyId, usize>,
+    bodies_hash: &SpatialHash<BodyId>,
+    index: usize,
+    destination: [f32; 2],
+) -> Vec<[f32; 2]> {
+    let body = &bodies[index];
+    let mut offsets = vec![];
+    for other in neighbours(body, destination, indices, bodies_hash) {
+        let other = &bodies[other];
+        if other.id == body.id || !body.kind.collides(&other.kind) {
+            continue;
+        }
+        let collision = collide_circle_to_circle(
+            destination,
+            body.kind.radius,
+            other.position,
+            other.kind.radius,
+        );
+        if let Some(collision) = collision {
+            offsets.push(collision.normal.mul(collision.penetration));
+        }
+    }
+    offsets
+}
+
+/// Returns indices of bodies registered in spatial hash near body placed at position,
+/// in ascending order to keep collision response deterministic.
+fn neighbours(
+    body: &Body,
+    position: [f32; 2],
+    indices: &HashMap<BodyId, usize>,
+    bodies_hash: &SpatialHash<BodyId>,
+) -> Vec<usize> {
+    let area = [body.kind.radius * 2.0; 2];
+    let mut neighbours: Vec<usize> = bodies_hash
+        .query(body.space, position, area)
+        .iter()
+        .filter_map(|shape| indices.get(&shape.id).copied())
+        .collect();
+    neighbours.sort();
+    neighbours
+}
+
+/// Gradually pushes apart overlapping bodies, e.g. herd of animals
+/// gathered on same tile, without pushing them into holes or barriers.
+fn separate_bodies(
+    bodies: &mut [Body],
+    indices: &HashMap<BodyId, usize>,
+    holes_map: &Vec<Vec<u8>>,
+    barriers_hash: &SpatialHash<BarrierId>,
+    bodies_hash: &mut SpatialHash<BodyId>,
+    time: f32,
+) -> Vec<Physics> {
+    let mut events = vec![];
+    for i in 0..bodies.len() {
+        let body = &bodies[i];
+        for j in neighbours(body, body.position, indices, bodies_hash) {
+            if j <= i {
+                continue;
+            }
+            let (a, b) = (&bodies[i], &bodies[j]);
+            if !a.kind.collides(&b.kind) {
+                continue;
+            }
+            let collision =
+                collide_circle_to_circle(a.position, a.kind.radius, b.position, b.kind.radius);
+            let collision = match collision {
+                Some(collision) => collision,
+                None => continue,
+            };
+            let push = (-collision.penetration / 2.0).min(SEPARATION_SPEED * time);
+            let push = collision.normal.mul(push);
+            for (index, offset) in [(i, push.neg()), (j, push)] {
+                let body = &bodies[index];
+                let position = body.position.add(offset);
+                let holes = generate_holes(position, body.kind.radius, holes_map, &[1, 2]);
+                let area = [body.kind.radius * 2.0; 2];
+                let barriers = barriers_hash.query(body.space, position, area);
+                if test_collisions(&body, position, &holes).is_some()
+                    || test_collisions(&body, position, &barriers).is_some()
+                {
+                    continue;
+                }
+                let body = &mut bodies[index];
+                let idle = body.position == body.destination && body.path.is_empty();
+                let previous = body.shape();
+                body.position = position;
+                bodies_hash.replace(body.space, previous, body.shape());
+                if idle {
+                    body.destination = position;
+                }
+                events.push(Physics::BodyPositionChanged {
+                    id: body.id,
+                    space: body.space,
+                    position: body.position,
+                    destination: body.destination,
+                });
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Shared;
+    use crate::math::{Rotation, VectorMath};
+    use crate::physics::{BarrierKey, BarrierKind, BodyId, BodyKey, BodyKind, Physics};
+    use crate::physics::{
+        PhysicsDomain, PhysicsError, SensorKey, SensorKind, Shape, Space, SpaceId, SpaceKey,
+        SpaceKind,
+    };
+    use crate::physics::{RaycastFilter, RaycastTarget};
+
+    fn prepare_domain(layer: u8, mask: u8, positions: &[[f32; 2]]) -> PhysicsDomain {
+        prepare_domain_with_acceleration(layer, mask, 0.0, positions)
+    }
+
+    fn prepare_domain_with_acceleration(
+        layer: u8,
+        mask: u8,
+        acceleration: f32,
+        positions: &[[f32; 2]],
+    ) -> PhysicsDomain {
+        let mut domain = PhysicsDomain::default();
+        let space_kind = Shared::new(SpaceKind {
+            id: SpaceKey(1),
+            name: "space".to_string(),
+            bounds: [128.0, 128.0],
+        });
+        let (space, create_space) = domain.create_space(&space_kind).unwrap();
+        create_space();
+        let kind = Shared::new(BodyKind {
+            id: BodyKey(1),
+            name: "lama".to_string(),
+            speed: 1.0,
+            radius: 0.25,
+            collision_layer: layer,
+            collision_mask: mask,
+            acceleration,
+            damping: 4.0,
+        });
+        for (index, position) in positions.iter().enumerate() {
+            let id = BodyId(index + 1);
+            domain
+                .create_body(id, space, kind.clone(), *position)
+                .unwrap()();
+        }
+        domain
+    }
+
+    #[test]
+    fn test_body_blocked_by_other_body() {
+        let mut domain = prepare_domain(1, 1, &[[10.5, 10.5], [11.5, 10.5]]);
+        domain.move_body(BodyId(1), [12.5, 10.5]).unwrap()();
+        domain.update(2.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        let other = domain.get_body(BodyId(2)).unwrap();
+        assert!(body.position.distance(other.position) >= 0.49);
+    }
+
+    #[test]
+    fn test_bodies_pass_through_if_not_collide() {
+        let mut domain = prepare_domain(1, 0, &[[10.5, 10.5], [11.5, 10.5]]);
+        domain.move_body(BodyId(1), [12.5, 10.5]).unwrap()();
+        domain.update(2.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        assert!(body.position.distance([12.5, 10.5]) < 0.001);
+    }
+
+    #[test]
+    fn test_stacked_bodies_separated() {
+        let mut domain = prepare_domain(1, 1, &[[10.5, 10.5], [10.6, 10.5]]);
+        domain.update(1.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        let other = domain.get_body(BodyId(2)).unwrap();
+        assert!(body.position.distance(other.position) >= 0.49);
+        assert_eq!(body.position, body.destination);
+    }
+
+    #[test]
+    fn test_body_moves_faster_along_path() {
+        let mut domain = prepare_domain(1, 0, &[[10.5, 10.5], [10.5, 20.5]]);
+        for x in 10..20 {
+            domain.create_path(SpaceId(1), [x, 10]).unwrap()();
+        }
+        domain.move_body(BodyId(1), [30.5, 10.5]).unwrap()();
+        domain.move_body(BodyId(2), [30.5, 20.5]).unwrap()();
+        domain.update(1.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        let other = domain.get_body(BodyId(2)).unwrap();
+        assert!((body.position[0] - 10.5 - Space::PATH_SPEED).abs() < 0.001);
+        assert!((other.position[0] - 11.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_path_out_of_space_rejected() {
+        let mut domain = prepare_domain(1, 0, &[]);
+        let result = domain.create_path(SpaceId(1), [10, 1000]);
+        assert!(matches!(result, Err(PhysicsError::TileOutOfSpace { .. })));
+    }
+
+    fn add_sensor(domain: &mut PhysicsDomain, filter: Vec<BodyKey>, position: [f32; 2]) {
+        let barrier = Shared::new(BarrierKind {
+            id: BarrierKey(1),
+            name: "gate".to_string(),
+            bounds: [0.0, 0.0],
+        });
+        let sensor = Shared::new(SensorKind {
+            id: SensorKey(1),
+            name: "gate".to_string(),
+            radius: 0.5,
+            filter,
+        });
+        let (_, _, create) = domain
+            .create_barrier_sensor(SpaceId(1), &barrier, &sensor, position, true)
+            .unwrap();
+        create();
+    }
+
+    #[test]
+    fn test_sensor_enter_and_exit_events() {
+        let mut domain = prepare_domain(0, 0, &[[10.5, 10.5]]);
+        add_sensor(&mut domain, vec![], [12.5, 10.5]);
+        domain.move_body(BodyId(1), [12.5, 10.5]).unwrap()();
+        let events = domain.update(3.0);
+        let entered = events.iter().any(
+            |event| matches!(event, Physics::SensorEntered { body, .. } if *body == BodyId(1)),
+        );
+        assert!(entered);
+        domain.move_body(BodyId(1), [14.5, 10.5]).unwrap()();
+        let events = domain.update(3.0);
+        let exited = events
+            .iter()
+            .any(|event| matches!(event, Physics::SensorExited { body, .. } if *body == BodyId(1)));
+        assert!(exited);
+    }
+
+    #[test]
+    fn test_sensor_ignores_stale_hash_entry() {
+        let mut domain = prepare_domain(0, 0, &[]);
+        add_sensor(&mut domain, vec![], [10.5, 10.5]);
+        let stale = Shape {
+            id: BodyId(42),
+            position: [10.5, 10.5],
+            bounds: [0.5, 0.5],
+            solid: true,
+        };
+        domain.bodies_hash.insert(SpaceId(1), stale);
+
+        let events = domain.update(0.1);
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_sensor_ignores_filtered_body_kinds() {
+        let mut domain = prepare_domain(0, 0, &[[12.5, 10.5]]);
+        add_sensor(&mut domain, vec![BodyKey(2)], [12.5, 10.5]);
+        let events = domain.update(0.1);
+        let entered = events
+            .iter()
+            .any(|event| matches!(event, Physics::SensorEntered { .. }));
+        assert!(!entered);
+        assert!(domain.sensors[&SpaceId(1)][0].registered.is_empty());
+    }
+
+    #[test]
+    fn test_space_with_large_id_created_and_destroyed() {
+        let mut domain = prepare_domain(0, 0, &[[10.5, 10.5]]);
+        domain.spaces_sequence = 200;
+        let space_kind = domain.spaces[0].kind.clone();
+        let (space, create_space) = domain.create_space(&space_kind).unwrap();
+        create_space();
+        let body_kind = domain.get_body(BodyId(1)).unwrap().kind.clone();
+        domain
+            .create_body(BodyId(2), space, body_kind, [1.5, 1.5])
+            .unwrap()();
+        domain.move_body(BodyId(2), [3.5, 1.5]).unwrap()();
+        domain.update(0.1);
+        assert_eq!(space, SpaceId(201));
+        assert!(domain.get_body(BodyId(2)).unwrap().position[0] > 1.5);
+
+        domain.destroy_space(space).unwrap()();
+        assert!(domain.get_space(space).is_err());
+        assert!(domain.get_body(BodyId(2)).is_err());
+        assert!(domain.get_body(BodyId(1)).is_ok());
+    }
+
+    #[test]
+    fn test_impulse_pushes_idle_body_and_fades() {
+        let mut domain = prepare_domain(1, 1, &[[10.5, 10.5]]);
+        domain.apply_impulse(BodyId(1), [2.0, 0.0]).unwrap()();
+        domain.update(0.1);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        assert!(body.position[0] > 10.6);
+        assert!(body.impulse[0] < 2.0);
+        domain.update(2.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        let position = body.position;
+        assert!(body.impulse.is_zero());
+        domain.update(0.5);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        assert_eq!(body.position, position);
+    }
+
+    #[test]
+    fn test_accelerated_body_eases_in_and_stops_at_destination() {
+        let mut domain = prepare_domain_with_acceleration(1, 1, 2.0, &[[10.5, 10.5]]);
+        domain.move_body(BodyId(1), [12.5, 10.5]).unwrap()();
+        domain.update(0.03);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        let travelled = body.position[0] - 10.5;
+        assert!(travelled > 0.0 && travelled < 0.03);
+        domain.update(5.0);
+        let body = domain.get_body(BodyId(1)).unwrap();
+        assert_eq!(body.position, [12.5, 10.5]);
+        assert!(body.velocity.is_zero());
+    }
+
+    #[test]
+    fn test_raycast_hits_ordered_by_distance() {
+        let mut domain = prepare_domain(1, 1, &[[10.5, 10.5], [13.5, 10.5]]);
+        let space = SpaceId(1);
+        let kind = Shared::new(BarrierKind {
+            id: BarrierKey(1),
+            name: "fence".to_string(),
+            bounds: [1.0, 1.0],
+        });
+        let (barrier, create_barrier) = domain
+            .create_barrier(space, kind, [12.5, 10.5], Rotation::A000, true, false)
+            .unwrap();
+        create_barrier();
+        domain.create_hole(space, [15, 10], 1).unwrap()();
+        let filter = RaycastFilter {
+            holes: vec![1],
+            barriers: true,
+            bodies: true,
+        };
+        let hits = domain
+            .raycast(space, [10.5, 10.5], [20.5, 10.5], 8.0, &filter)
+            .unwrap();
+        let targets: Vec<RaycastTarget> = hits.iter().map(|hit| hit.target).collect();
+        assert_eq!(
+            targets,
+            vec![
+                RaycastTarget::Barrier(barrier),
+                RaycastTarget::Body(BodyId(2)),
+                RaycastTarget::Hole {
+                    tile: [15, 10],
+                    hole: 1
+                },
+            ]
+        );
+        assert!((hits[0].distance - 1.5).abs() < 0.001);
+        let hits = domain
+            .raycast(space, [10.5, 10.5], [20.5, 10.5], 2.5, &filter)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+}
@@ -1,13 +1,23 @@
 use crate::collections::Shared;
-use crate::planting::{Plant, PlantId, PlantKind, Planting, PlantingDomain, PlantingError, SoilId};
+use crate::math::ArrayIndex;
+use crate::planting::{
+    Plant, PlantId, PlantKind, PlantStage, PlantStress, Planting, PlantingDomain, PlantingError,
+    Soil, SoilId,
+};
 
 impl PlantingDomain {
     pub fn create_plant<'operation>(
         &'operation mut self,
         soil: SoilId,
+        tile: [usize; 2],
         kind: &Shared<PlantKind>,
         impact: f32,
     ) -> Result<(PlantId, impl FnOnce() -> Vec<Planting> + 'operation), PlantingError> {
+        let target = self.get_soil(soil)?;
+        let place = tile.fit(target.kind.width);
+        if place >= target.history.len() {
+            return Err(PlantingError::OutOfSoil { id: soil, tile });
+        }
         let id = PlantId(self.plants_sequence + 1);
         let plant = Plant {
             id,
@@ -19,11 +29,27 @@ impl PlantingDomain {
             health: 1.0,
             growth: 0.0,
             fruits: kind.max_fruits,
+            stage: PlantStage::Sprout,
+            in_season: true,
+            temperature: kind.optimal_temperature(),
+            stress: PlantStress::None,
+            place,
+            moisture: 0.0,
+            infection: 0.0,
+            quality: 1.0,
+            pollination: 0.0,
+            pollinators: 0,
+            wind: 0.0,
         };
         let operation = move || {
             let events = vec![];
             self.plants_sequence += 1;
-            self.plants[soil.0].push(plant);
+            let history = &mut self.get_soil_mut(soil).unwrap().history[place];
+            history.push(plant.kind.id);
+            if history.len() > Soil::HISTORY_LENGTH {
+                history.remove(0);
+            }
+            self.plants.entry(soil).or_default().push(plant);
             events
         };
         Ok((id, operation))
@@ -10,11 +10,15 @@ impl PlantingDomain {
         let soil = Soil {
             id,
             kind: kind.clone(),
-            fertility: vec![0.0; 128 * 128],
+            nutrients: kind
+                .nutrients
+                .map(|amount| vec![amount; kind.width * kind.height]),
+            history: vec![vec![]; kind.width * kind.height],
         };
         let operation = move || {
             let events = vec![];
             self.soils_sequence += 1;
+            self.plants.insert(id, vec![]);
             self.soils.push(soil);
             events
         };
@@ -9,11 +9,9 @@ impl PlantingDomain {
         let soil = plant.soil;
         let residue = plant.growth * plant.health;
         let command = move || {
-            let index = self.plants[soil.0]
-                .iter()
-                .position(|plant| plant.id == id)
-                .unwrap();
-            let _plant = self.plants[soil.0].remove(index);
+            let plants = self.plants.get_mut(&soil).unwrap();
+            let index = plants.iter().position(|plant| plant.id == id).unwrap();
+            let _plant = plants.remove(index);
             vec![]
         };
         Ok((residue, command))
@@ -0,0 +1,20 @@
+use crate::planting::{Planting, PlantingDomain, PlantingError, SoilId};
+
+impl PlantingDomain {
+    pub fn destroy_soil(
+        &mut self,
+        id: SoilId,
+    ) -> Result<impl FnOnce() -> Vec<Planting> + '_, PlantingError> {
+        let index = self
+            .soils
+            .iter()
+            .position(|soil| soil.id == id)
+            .ok_or(PlantingError::SoilNotFound { id })?;
+        let command = move || {
+            self.soils.remove(index);
+            self.plants.remove(&id);
+            vec![]
+        };
+        Ok(command)
+    }
+}
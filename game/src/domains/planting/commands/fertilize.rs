@@ -1,21 +1,22 @@
 use crate::math::ArrayIndex;
-use crate::planting::{Planting, PlantingDomain, PlantingError, SoilId};
+use crate::planting::{Nutrients, Planting, PlantingDomain, PlantingError, SoilId};
 
 impl PlantingDomain {
     pub fn fertilize(
         &mut self,
         id: SoilId,
         tile: [usize; 2],
-        quality: f32,
+        nutrients: Nutrients,
     ) -> Result<impl FnOnce() -> Vec<Planting> + '_, PlantingError> {
         let soil = self.get_soil_mut(id)?;
         let place = tile.fit(soil.kind.width);
-        if place >= soil.fertility.len() {
+        if place >= soil.history.len() {
             return Err(PlantingError::OutOfSoil { id, tile });
         }
         let command = move || {
-            let fertility = soil.fertility[place];
-            soil.fertility[place] = (fertility + quality).min(1.0);
+            for (channel, amount) in soil.nutrients.iter_mut().zip(nutrients) {
+                channel[place] = (channel[place] + amount).min(1.0);
+            }
             vec![]
         };
         Ok(command)
@@ -1,24 +1,32 @@
-use crate::planting::PlantId;
 use crate::planting::Planting;
 use crate::planting::Planting::PlantFruitsChanged;
 use crate::planting::PlantingDomain;
 use crate::planting::PlantingError;
 use crate::planting::PlantingError::{HasNoFruitsToHarvest, NotReadyToHarvest};
+use crate::planting::{Harvest, PlantId, PlantStage};
 
 impl PlantingDomain {
     pub fn harvest_plant(
         &mut self,
         id: PlantId,
         amount: u8,
-    ) -> Result<(u8, impl FnOnce() -> Vec<Planting> + '_), PlantingError> {
+    ) -> Result<(Harvest, impl FnOnce() -> Vec<Planting> + '_), PlantingError> {
         let plant = self.get_plant_mut(id)?;
-        if plant.growth < 3.0 || plant.growth >= 4.0 {
-            return Err(NotReadyToHarvest { id });
-        }
+        let overripe = match plant.stage {
+            PlantStage::Fruiting => false,
+            PlantStage::Overripe => true,
+            _ => return Err(NotReadyToHarvest { id }),
+        };
         let fruits = (amount as f32).min(plant.fruits).floor();
         if fruits < 1.0 {
             return Err(HasNoFruitsToHarvest { id });
         }
+        // late harvest gives seeds from fruits left to ripen
+        let harvest = Harvest {
+            fruits: fruits as u8,
+            seeds: if overripe { fruits as u8 } else { 0 },
+            quality: plant.get_fruits_quality(),
+        };
         let operation = move || {
             plant.fruits -= fruits;
             vec![PlantFruitsChanged {
@@ -26,6 +34,6 @@ impl PlantingDomain {
                 fruits: plant.fruits,
             }]
         };
-        Ok((fruits as u8, operation))
+        Ok((harvest, operation))
     }
 }
@@ -2,6 +2,8 @@ mod create_plant;
 mod create_soil;
 mod damage_plant;
 mod destroy_plant;
+mod destroy_soil;
 mod fertilize;
 mod harvest_plant;
+mod treat_plant;
 mod water_plant;
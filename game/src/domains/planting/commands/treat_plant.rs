@@ -0,0 +1,23 @@
+use crate::planting::Planting::PlantInfectionChanged;
+use crate::planting::{PlantId, Planting, PlantingDomain, PlantingError};
+
+impl PlantingDomain {
+    pub fn treat_plant(
+        &mut self,
+        id: PlantId,
+        efficiency: f32,
+    ) -> Result<impl FnOnce() -> Vec<Planting> + '_, PlantingError> {
+        let plant = self.get_plant_mut(id)?;
+        if plant.infection <= 0.0 {
+            return Err(PlantingError::PlantNotInfected { id });
+        }
+        let operation = move || {
+            plant.infection = (plant.infection - efficiency).max(0.0);
+            vec![PlantInfectionChanged {
+                id,
+                infection: plant.infection,
+            }]
+        };
+        Ok(operation)
+    }
+}
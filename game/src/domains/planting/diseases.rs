@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::math::Random;
+use crate::planting::Planting::PlantInfectionChanged;
+use crate::planting::{PlantId, PlantStage, Planting, PlantingDomain};
+
+/// Chance per second to get infected for fully moist plant without neighbours.
+pub const INFECTION_CHANCE: f32 = 0.0005;
+
+/// Chance per second to get infected from fully infected neighbour.
+pub const INFECTION_SPREAD_CHANCE: f32 = 0.01;
+
+/// Severity of just started infection.
+pub const INFECTION_ONSET: f32 = 0.1;
+
+/// Growth of infection severity per second.
+pub const INFECTION_GROWTH: f32 = 0.002;
+
+/// Distance in tiles at which plants are considered neighbours.
+pub const INFECTION_RADIUS: usize = 1;
+
+/// Developing infection is reported only when its severity crosses multiple of this step.
+pub const INFECTION_REPORT_STEP: f32 = 0.1;
+
+impl PlantingDomain {
+    /// Spawns infections on moist and crowded plants, spreads them to neighbours
+    /// and develops existing ones.
+    ///
+    /// Spreading uses infections of previous update, so result does not depend on plants order.
+    pub fn spread_diseases(&mut self, time: f32, random: &mut Random) -> Vec<Planting> {
+        let mut events = vec![];
+        for soil in &self.soils {
+            let plants = match self.plants.get_mut(&soil.id) {
+                Some(plants) => plants,
+                None => continue,
+            };
+            let width = soil.kind.width;
+            let height = soil.kind.height;
+            let mut sources: HashMap<usize, Vec<(PlantId, f32)>> = HashMap::new();
+            for plant in plants.iter() {
+                if plant.stage != PlantStage::Dead {
+                    let source = (plant.id, plant.infection);
+                    sources.entry(plant.place).or_default().push(source);
+                }
+            }
+            for plant in plants.iter_mut() {
+                if plant.stage == PlantStage::Dead {
+                    continue;
+                }
+                if plant.infection >= 1.0 {
+                    // fully developed infection changes nothing anymore
+                    continue;
+                }
+                if plant.infection > 0.0 {
+                    let previous = plant.infection;
+                    plant.infection = (previous + time * INFECTION_GROWTH).min(1.0);
+                    let step = (plant.infection / INFECTION_REPORT_STEP).floor();
+                    if step == (previous / INFECTION_REPORT_STEP).floor() {
+                        continue;
+                    }
+                } else {
+                    let [x, y] = [plant.place % width, plant.place / width];
+                    let mut neighbours = 0;
+                    let mut exposure = 0.0;
+                    let rows = y.saturating_sub(INFECTION_RADIUS)..=(y + INFECTION_RADIUS);
+                    for other_y in rows.take_while(|other_y| *other_y < height) {
+                        let columns = x.saturating_sub(INFECTION_RADIUS)..=(x + INFECTION_RADIUS);
+                        for other_x in columns.take_while(|other_x| *other_x < width) {
+                            let tile_sources = match sources.get(&(other_y * width + other_x)) {
+                                Some(tile_sources) => tile_sources,
+                                None => continue,
+                            };
+                            for (id, infection) in tile_sources {
+                                if *id != plant.id {
+                                    neighbours += 1;
+                                    exposure += infection;
+                                }
+                            }
+                        }
+                    }
+                    let crowding = 1.0 + neighbours as f32;
+                    let chance = INFECTION_CHANCE * plant.moisture * crowding
+                        + INFECTION_SPREAD_CHANCE * exposure;
+                    let chance = time * chance * (1.0 - plant.kind.resistance);
+                    if random.generate() >= chance {
+                        continue;
+                    }
+                    plant.infection = INFECTION_ONSET;
+                }
+                events.push(PlantInfectionChanged {
+                    id: plant.id,
+                    infection: plant.infection,
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::Random;
+    use crate::planting::tests::prepare_domain;
+
+    #[test]
+    fn test_infection_spreads_to_neighbours_only() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        let soil = domain.soils[0].id;
+        let kind = domain.get_plant(plant).unwrap().kind.clone();
+        let (neighbour, create_neighbour) = domain.create_plant(soil, [2, 1], &kind, 0.0).unwrap();
+        create_neighbour();
+        let (distant, create_distant) = domain.create_plant(soil, [3, 3], &kind, 0.0).unwrap();
+        create_distant();
+        domain.get_plant_mut(plant).unwrap().infection = 1.0;
+
+        let mut random = Random::with_seed(42);
+        for _ in 0..200 {
+            domain.spread_diseases(10.0, &mut random);
+        }
+
+        assert!(domain.get_plant(neighbour).unwrap().infection > 0.0);
+        assert_eq!(domain.get_plant(distant).unwrap().infection, 0.0);
+    }
+
+    #[test]
+    fn test_moist_plant_gets_infected_and_treated() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.integrate_moisture(plant, 1.0).unwrap();
+
+        let mut random = Random::with_seed(7);
+        let mut events = vec![];
+        while events.is_empty() {
+            events = domain.spread_diseases(10.0, &mut random);
+        }
+        assert!(domain.get_plant(plant).unwrap().infection > 0.0);
+
+        domain.treat_plant(plant, 1.0).unwrap()();
+        assert_eq!(domain.get_plant(plant).unwrap().infection, 0.0);
+        assert!(domain.treat_plant(plant, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_saturated_infection_not_reported() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.get_plant_mut(plant).unwrap().infection = 0.999;
+        let mut random = Random::with_seed(1);
+
+        assert_eq!(domain.spread_diseases(1.0, &mut random).len(), 1);
+        assert_eq!(domain.get_plant(plant).unwrap().infection, 1.0);
+        assert!(domain.spread_diseases(1.0, &mut random).is_empty());
+    }
+
+    #[test]
+    fn test_developing_infection_reported_by_steps() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.get_plant_mut(plant).unwrap().infection = 0.15;
+        let mut random = Random::with_seed(1);
+
+        assert!(domain.spread_diseases(1.0, &mut random).is_empty());
+        domain.get_plant_mut(plant).unwrap().infection = 0.199;
+        assert_eq!(domain.spread_diseases(1.0, &mut random).len(), 1);
+    }
+}
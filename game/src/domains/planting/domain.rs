@@ -1,27 +1,16 @@
 use crate::collections::Shared;
 use crate::math::Rect;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-pub const MAX_SOILS: usize = 128;
-
+#[derive(Default)]
 pub struct PlantingDomain {
     pub soils: Vec<Soil>,
     pub soils_sequence: usize,
-    pub plants: Vec<Vec<Plant>>,
+    pub plants: HashMap<SoilId, Vec<Plant>>,
     pub plants_sequence: usize,
 }
 
-impl Default for PlantingDomain {
-    fn default() -> Self {
-        Self {
-            soils: vec![],
-            soils_sequence: 0,
-            plants: vec![vec![]; MAX_SOILS],
-            plants_sequence: 0,
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SoilKey(pub(crate) usize);
 
@@ -30,15 +19,53 @@ pub struct SoilKind {
     pub name: String,
     pub width: usize,
     pub height: usize,
+    /// Initial nutrients of every tile of new soil.
+    pub nutrients: Nutrients,
+}
+
+/// Channels of soil nutrients.
+pub struct Nutrient;
+
+impl Nutrient {
+    pub const NITROGEN: usize = 0;
+
+    pub const PHOSPHORUS: usize = 1;
+
+    pub const POTASSIUM: usize = 2;
+
+    pub const CHANNELS: usize = 3;
 }
 
+/// Amount of every nutrient channel, indexed by `Nutrient` constants.
+pub type Nutrients = [f32; Nutrient::CHANNELS];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SoilId(pub usize);
 
 pub struct Soil {
     pub id: SoilId,
     pub kind: Shared<SoilKind>,
-    pub fertility: Vec<f32>,
+    pub nutrients: [Vec<f32>; Nutrient::CHANNELS],
+    /// Kinds of plants recently planted on every tile, the latest one is last.
+    pub history: Vec<Vec<PlantKey>>,
+}
+
+impl Soil {
+    pub const HISTORY_LENGTH: usize = 4;
+
+    /// Extra consumption of nutrients for every previous planting of the same kind in a row.
+    pub const MONOCULTURE_PENALTY: f32 = 0.5;
+
+    /// Returns consumption multiplier of plant kind growing on tile.
+    pub fn get_rotation_factor(&self, place: usize, kind: PlantKey) -> f32 {
+        let repeats = self.history[place]
+            .iter()
+            .rev()
+            .skip(1)
+            .take_while(|key| **key == kind)
+            .count();
+        1.0 + Self::MONOCULTURE_PENALTY * repeats as f32
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -51,6 +78,95 @@ pub struct PlantKind {
     pub flexibility: f32,
     pub transpiration: f32,
     pub max_fruits: f32,
+    /// Nutrients taken from soil per second of growth.
+    pub consumption: Nutrients,
+    /// Nutrients returned to soil per second of growth, e.g. nitrogen fixed by legumes.
+    pub restoration: Nutrients,
+    /// Growth thresholds at which plant enters vegetative, flowering, fruiting,
+    /// overripe, withered and dead stages.
+    pub stages: [f32; 6],
+    /// Keys of calendar seasons in which plant grows at full speed, any season if empty.
+    pub seasons: Vec<String>,
+    /// Range of temperature in which plant is able to grow.
+    pub temperature: [f32; 2],
+    /// Protection against diseases and pests, from 0.0 (none) to 1.0 (immune).
+    pub resistance: f32,
+    /// Share of fruits quality depending on pollination, 0.0 for self-pollinating plants.
+    pub pollination: f32,
+}
+
+impl PlantKind {
+    pub fn get_stage(&self, growth: f32, health: f32) -> PlantStage {
+        if health <= 0.0 {
+            return PlantStage::Dead;
+        }
+        let next = [
+            PlantStage::Vegetative,
+            PlantStage::Flowering,
+            PlantStage::Fruiting,
+            PlantStage::Overripe,
+            PlantStage::Withered,
+            PlantStage::Dead,
+        ];
+        let mut stage = PlantStage::Sprout;
+        for (threshold, next) in self.stages.iter().zip(next) {
+            if growth >= *threshold {
+                stage = next;
+            }
+        }
+        stage
+    }
+
+    pub fn optimal_temperature(&self) -> f32 {
+        let [min, max] = self.temperature;
+        (min + max) / 2.0
+    }
+
+    pub fn get_stress(&self, in_season: bool, temperature: f32) -> PlantStress {
+        let [min, max] = self.temperature;
+        if temperature < min {
+            PlantStress::Cold
+        } else if temperature > max {
+            PlantStress::Heat
+        } else if !in_season {
+            PlantStress::OutOfSeason
+        } else {
+            PlantStress::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PlantStage {
+    Sprout,
+    Vegetative,
+    Flowering,
+    Fruiting,
+    Overripe,
+    Withered,
+    Dead,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PlantStress {
+    None,
+    /// Plant grows slowly out of preferred seasons.
+    OutOfSeason,
+    /// Plant stops growing and slowly dies if temperature is out of tolerance.
+    Cold,
+    Heat,
+}
+
+impl PlantStress {
+    pub const OUT_OF_SEASON_GROWTH: f32 = 0.25;
+
+    pub fn growth_factor(&self) -> f32 {
+        match self {
+            PlantStress::None => 1.0,
+            PlantStress::OutOfSeason => Self::OUT_OF_SEASON_GROWTH,
+            PlantStress::Cold | PlantStress::Heat => 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -67,6 +183,47 @@ pub struct Plant {
     pub health: f32,
     pub growth: f32,
     pub fruits: f32,
+    pub stage: PlantStage,
+    pub in_season: bool,
+    pub temperature: f32,
+    pub stress: PlantStress,
+    /// Index of soil tile plant grows on.
+    pub place: usize,
+    pub moisture: f32,
+    /// Severity of disease or pests, 0.0 if plant is healthy.
+    pub infection: f32,
+    /// Quality of future fruits, decreases with every stress plant suffers during its life.
+    pub quality: f32,
+    /// Progress of flowers pollination, from 0.0 to 1.0.
+    pub pollination: f32,
+    /// Number of pollinators near plant.
+    pub pollinators: usize,
+    /// Strength of wind blowing on plant, 0.0 inside rooms.
+    pub wind: f32,
+}
+
+impl Plant {
+    /// Loss of quality per second of complete stress.
+    pub const QUALITY_LOSS: f32 = 0.002;
+
+    /// Pollination progress per second of single pollinator work.
+    pub const POLLINATION_SPEED: f32 = 0.05;
+
+    /// Loss of health per second of full strength wind.
+    pub const WIND_DAMAGE: f32 = 0.0005;
+
+    pub fn get_fruits_quality(&self) -> f32 {
+        let lack = self.kind.pollination * (1.0 - self.pollination);
+        self.quality * (1.0 - lack)
+    }
+}
+
+/// Result of plant harvesting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Harvest {
+    pub fruits: u8,
+    pub seeds: u8,
+    pub quality: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -86,10 +243,26 @@ pub enum Planting {
         id: PlantId,
         fruits: f32,
     },
-    SoilFertilityInspected {
+    PlantStageChanged {
+        id: PlantId,
+        stage: PlantStage,
+    },
+    PlantStressChanged {
+        id: PlantId,
+        stress: PlantStress,
+    },
+    PlantInfectionChanged {
+        id: PlantId,
+        infection: f32,
+    },
+    PlantPollinated {
+        id: PlantId,
+        pollination: f32,
+    },
+    SoilNutrientsInspected {
         soil: SoilId,
         rect: Rect,
-        fertility: Vec<f32>,
+        nutrients: [Vec<f32>; Nutrient::CHANNELS],
     },
 }
 
@@ -98,6 +271,7 @@ pub enum PlantingError {
     PlantNotFound { id: PlantId },
     NotReadyToHarvest { id: PlantId },
     HasNoFruitsToHarvest { id: PlantId },
+    PlantNotInfected { id: PlantId },
     SoilNotFound { id: SoilId },
     OutOfSoil { id: SoilId, tile: [usize; 2] },
 }
@@ -1,15 +1,26 @@
-use crate::planting::{Plant, PlantingDomain, Soil};
+use crate::planting::{Plant, PlantId, PlantingDomain, PlantingError, Soil};
 
 impl PlantingDomain {
     pub fn load_soils(&mut self, soils: Vec<Soil>, sequence: usize) {
         self.soils_sequence = sequence;
-        self.soils.extend(soils);
+        for soil in soils {
+            self.plants.entry(soil.id).or_default();
+            self.soils.push(soil);
+        }
     }
 
     pub fn load_plants(&mut self, plants: Vec<Plant>, sequence: usize) {
         self.plants_sequence = sequence;
         for plant in plants {
-            self.plants[plant.soil.0].push(plant);
+            self.plants.entry(plant.soil).or_default().push(plant);
         }
     }
+
+    /// Place of plant is not stored, it is restored from tile of entity owning the plant.
+    pub fn load_plant_place(&mut self, id: PlantId, tile: [usize; 2]) -> Result<(), PlantingError> {
+        let soil = self.get_plant(id)?.soil;
+        let place = self.get_soil(soil)?.get_place(tile)?;
+        self.get_plant_mut(id)?.place = place;
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
 pub use commands::*;
+pub use diseases::*;
 pub use domain::*;
 pub use loading::*;
 pub use queries::*;
 pub use update::*;
 
 mod commands;
+mod diseases;
 mod domain;
 mod loading;
 mod queries;
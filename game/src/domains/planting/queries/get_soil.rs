@@ -1,3 +1,4 @@
+use crate::math::ArrayIndex;
 use crate::planting::{PlantingDomain, PlantingError, Soil, SoilId};
 
 impl PlantingDomain {
@@ -15,3 +16,15 @@ impl PlantingDomain {
             .ok_or(PlantingError::SoilNotFound { id })
     }
 }
+
+impl Soil {
+    /// Returns index of tile in soil maps if tile is within soil bounds.
+    pub fn get_place(&self, tile: [usize; 2]) -> Result<usize, PlantingError> {
+        let [x, y] = tile;
+        if x < self.kind.width && y < self.kind.height {
+            Ok(tile.fit(self.kind.width))
+        } else {
+            Err(PlantingError::OutOfSoil { id: self.id, tile })
+        }
+    }
+}
@@ -1,20 +1,40 @@
-use crate::planting::Planting::{PlantDamaged, PlantFruitsChanged, PlantUpdated};
-use crate::planting::{PlantId, Planting, PlantingDomain, PlantingError, SoilId};
+use crate::math::Random;
+use crate::planting::Planting::{
+    PlantDamaged, PlantFruitsChanged, PlantPollinated, PlantStageChanged, PlantStressChanged,
+    PlantUpdated,
+};
+use crate::planting::{
+    Plant, PlantId, PlantStage, PlantStress, Planting, PlantingDomain, PlantingError, SoilId,
+};
 
 impl PlantingDomain {
     pub fn update_impact(&mut self) {}
 
-    pub fn request_fertility_consumption(
+    /// Takes nutrients required by plant growth from soil tile and restores ones it produces.
+    ///
+    /// Returns lack and expected consumption summed over all channels.
+    pub fn request_nutrients_consumption(
         &mut self,
-        soil: SoilId,
-        place: usize,
-        expected: f32,
-    ) -> Result<f32, PlantingError> {
-        let soil = self.get_soil_mut(soil)?;
-        let fertility = soil.fertility[place];
-        let delta = expected.min(fertility);
-        soil.fertility[place] -= delta;
-        Ok(delta)
+        id: SoilId,
+        tile: [usize; 2],
+        plant: PlantId,
+        time: f32,
+    ) -> Result<(f32, f32), PlantingError> {
+        let kind = self.get_plant(plant)?.kind.clone();
+        let soil = self.get_soil_mut(id)?;
+        let place = soil.get_place(tile)?;
+        let rotation = soil.get_rotation_factor(place, kind.id);
+        let mut lack = 0.0;
+        let mut consumption = 0.0;
+        for (channel, nutrients) in soil.nutrients.iter_mut().enumerate() {
+            let expected = kind.consumption[channel] * rotation * time;
+            let delta = expected.min(nutrients[place]);
+            let restored = kind.restoration[channel] * time;
+            nutrients[place] = (nutrients[place] - delta + restored).min(1.0);
+            lack += expected - delta;
+            consumption += expected;
+        }
+        Ok((lack, consumption))
     }
 
     pub fn integrate_thirst(
@@ -47,23 +67,73 @@ impl PlantingDomain {
         Ok(())
     }
 
+    /// Greenhouse lets plant grow regardless of its seasons preferences.
+    pub fn integrate_climate(
+        &mut self,
+        id: PlantId,
+        season: &str,
+        temperature: f32,
+        greenhouse: bool,
+    ) -> Result<(), PlantingError> {
+        let plant = self.get_plant_mut(id)?;
+        let seasons = &plant.kind.seasons;
+        plant.in_season =
+            greenhouse || seasons.is_empty() || seasons.iter().any(|key| key == season);
+        plant.temperature = temperature;
+        Ok(())
+    }
+
+    pub fn integrate_moisture(&mut self, id: PlantId, moisture: f32) -> Result<(), PlantingError> {
+        let plant = self.get_plant_mut(id)?;
+        plant.moisture = moisture;
+        Ok(())
+    }
+
+    pub fn integrate_wind(&mut self, id: PlantId, wind: f32) -> Result<(), PlantingError> {
+        let plant = self.get_plant_mut(id)?;
+        plant.wind = wind;
+        Ok(())
+    }
+
+    pub fn integrate_pollinators(
+        &mut self,
+        id: PlantId,
+        pollinators: usize,
+    ) -> Result<(), PlantingError> {
+        let plant = self.get_plant_mut(id)?;
+        plant.pollinators = pollinators;
+        Ok(())
+    }
+
     pub fn integrate_impact(&mut self, id: PlantId, impact: f32) -> Result<(), PlantingError> {
         let plant = self.get_plant_mut(id)?;
-        plant.impact += impact;
-        if plant.impact < -1.0 {
-            plant.impact = -1.0;
-        }
-        if plant.impact > 1.0 {
-            plant.impact = 1.0;
-        }
+        plant.impact = (plant.impact + impact).clamp(-1.0, 1.0);
         Ok(())
     }
 
-    pub fn update(&mut self, time: f32) -> Vec<Planting> {
-        let mut events = vec![];
+    pub fn update(&mut self, time: f32, random: &mut Random) -> Vec<Planting> {
+        let mut events = self.spread_diseases(time, random);
 
-        for plants in &mut self.plants {
+        // iterates in order of soils to keep events order deterministic
+        for soil in &self.soils {
+            let plants = match self.plants.get_mut(&soil.id) {
+                Some(plants) => plants,
+                None => continue,
+            };
             for plant in plants.iter_mut() {
+                if plant.stage == PlantStage::Dead {
+                    // dead plant remains until universe turns it into residue
+                    continue;
+                }
+                let stress = plant.kind.get_stress(plant.in_season, plant.temperature);
+                if stress != plant.stress {
+                    plant.stress = stress;
+                    events.push(PlantStressChanged {
+                        id: plant.id,
+                        stress,
+                    });
+                }
+
                 let mut plant_updated = false;
                 if plant.impact.abs() > 0.001 {
                     let delta = time * plant.kind.flexibility;
@@ -72,7 +142,8 @@ impl PlantingDomain {
                     } else {
                         plant.impact - (plant.impact.signum() * time * plant.kind.flexibility)
                     };
-                    plant.health = (plant.health - time * 0.06).clamp(0.1, 1.0);
+                    // trampling never kills plant by itself
+                    plant.health = (plant.health - time * 0.06).max(plant.health.min(0.1));
                     plant_updated = true;
 
                     events.push(PlantDamaged {
@@ -81,17 +152,63 @@ impl PlantingDomain {
                     });
                 }
 
-                plant_updated = true;
+                if plant.wind > 0.0 {
+                    let damage = time * plant.wind * Plant::WIND_DAMAGE;
+                    // storm breaks plant but never kills it by itself
+                    plant.health = (plant.health - damage).max(plant.health.min(0.1));
+                    events.push(PlantDamaged {
+                        id: plant.id,
+                        health: plant.health,
+                    });
+                }
+
+                // completely dry, starving or freezing plant slowly dies
+                let extreme = matches!(stress, PlantStress::Cold | PlantStress::Heat);
+                if plant.thirst >= 1.0 || plant.hunger >= 1.0 || extreme {
+                    let damage = time * (1.0 / (plant.kind.growth * 60.0));
+                    plant.health = (plant.health - damage).max(0.0);
+                    events.push(PlantDamaged {
+                        id: plant.id,
+                        health: plant.health,
+                    });
+                }
 
                 let growth = time
                     * (1.0 / (plant.kind.growth * 60.0))
                     * (1.0 - plant.thirst)
-                    * (1.0 - plant.hunger);
+                    * (1.0 - plant.hunger)
+                    * (1.0 - plant.infection)
+                    * stress.growth_factor();
+
+                let [.., dead] = plant.kind.stages;
+                let growth = (plant.growth + growth).min(dead);
+                if growth != plant.growth {
+                    plant.growth = growth;
+                    plant_updated = true;
+                }
+
+                // every kind of stress leaves its mark on fruits
+                let suffering =
+                    (plant.thirst + plant.hunger + plant.infection + plant.impact.abs())
+                        .max(1.0 - plant.health)
+                        .min(1.0);
+                plant.quality = (plant.quality - time * Plant::QUALITY_LOSS * suffering).max(0.0);
 
-                plant.growth = (plant.growth + growth).min(5.0);
+                if plant.stage == PlantStage::Flowering
+                    && plant.pollinators > 0
+                    && plant.pollination < 1.0
+                {
+                    let speed = Plant::POLLINATION_SPEED * plant.pollinators as f32;
+                    plant.pollination = (plant.pollination + time * speed).min(1.0);
+                    events.push(PlantPollinated {
+                        id: plant.id,
+                        pollination: plant.pollination,
+                    });
+                }
 
-                if plant.growth >= 2.0 && plant.growth < 3.0 {
-                    plant.fruits -= time * 2.0 * plant.hunger * (1.0 / (plant.kind.growth * 60.0));
+                if plant.stage == PlantStage::Flowering {
+                    let loss = plant.hunger + plant.infection;
+                    plant.fruits -= time * 2.0 * loss * (1.0 / (plant.kind.growth * 60.0));
                     plant.fruits = plant.fruits.clamp(0.0, plant.kind.max_fruits);
                     events.push(PlantFruitsChanged {
                         id: plant.id,
@@ -108,8 +225,261 @@ impl PlantingDomain {
                         growth: plant.growth,
                     })
                 }
+
+                let stage = plant.kind.get_stage(plant.growth, plant.health);
+                if stage != plant.stage {
+                    plant.stage = stage;
+                    events.push(PlantStageChanged {
+                        id: plant.id,
+                        stage,
+                    });
+                }
             }
         }
         events
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::collections::Shared;
+    use crate::math::Random;
+    use crate::planting::Planting::{
+        PlantPollinated, PlantStageChanged, PlantStressChanged, PlantUpdated,
+    };
+    use crate::planting::{
+        Nutrient, Plant, PlantId, PlantKey, PlantKind, PlantStage, PlantStress, PlantingDomain,
+        Soil, SoilKey, SoilKind,
+    };
+
+    pub(crate) fn prepare_domain(seasons: Vec<String>) -> (PlantingDomain, PlantId) {
+        let mut domain = PlantingDomain::default();
+        let soil = Shared::new(SoilKind {
+            id: SoilKey(1),
+            name: "test".to_string(),
+            width: 4,
+            height: 4,
+            nutrients: [1.0, 1.0, 1.0],
+        });
+        let (soil, create_soil) = domain.create_soil(&soil).unwrap();
+        create_soil();
+        let kind = Shared::new(PlantKind {
+            id: PlantKey(1),
+            name: "test".to_string(),
+            growth: 1.0,
+            flexibility: 1.0,
+            transpiration: 1.0,
+            max_fruits: 1.0,
+            consumption: [0.01, 0.0, 0.0],
+            restoration: [0.0, 0.0, 0.01],
+            stages: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            seasons,
+            temperature: [5.0, 35.0],
+            resistance: 0.5,
+            pollination: 0.0,
+        });
+        let (plant, create_plant) = domain.create_plant(soil, [1, 1], &kind, 0.0).unwrap();
+        create_plant();
+        (domain, plant)
+    }
+
+    #[test]
+    fn test_starving_plant_dies() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.integrate_hunger(plant, 1.0, 0.0).unwrap();
+
+        domain.update(30.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_plant(plant).unwrap().stage, PlantStage::Sprout);
+
+        let events = domain.update(30.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_plant(plant).unwrap().health, 0.0);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            PlantStageChanged { id, stage: PlantStage::Dead } if *id == plant
+        )));
+        assert!(domain.update(30.0, &mut Random::with_seed(1)).is_empty());
+    }
+
+    #[test]
+    fn test_plant_grows_slowly_out_of_season() {
+        let (mut domain, plant) = prepare_domain(vec!["summer".to_string()]);
+        domain
+            .integrate_climate(plant, "summer", 20.0, false)
+            .unwrap();
+        domain.update(6.0, &mut Random::with_seed(1));
+        let growth = domain.get_plant(plant).unwrap().growth;
+
+        domain
+            .integrate_climate(plant, "winter", 20.0, false)
+            .unwrap();
+        let events = domain.update(6.0, &mut Random::with_seed(1));
+
+        let plant_state = domain.get_plant(plant).unwrap();
+        assert_eq!(plant_state.stress, PlantStress::OutOfSeason);
+        let expected = growth * (1.0 + PlantStress::OUT_OF_SEASON_GROWTH);
+        assert!((plant_state.growth - expected).abs() < 0.0001);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            PlantStressChanged {
+                stress: PlantStress::OutOfSeason,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_greenhouse_shelters_plant_out_of_season() {
+        let (mut domain, plant) = prepare_domain(vec!["summer".to_string()]);
+        domain
+            .integrate_climate(plant, "winter", 20.0, true)
+            .unwrap();
+        let events = domain.update(6.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_plant(plant).unwrap().stress, PlantStress::None);
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, PlantStressChanged { .. })));
+    }
+
+    #[test]
+    fn test_frozen_plant_stops_growing_and_loses_health() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain
+            .integrate_climate(plant, "winter", -20.0, false)
+            .unwrap();
+        domain.update(6.0, &mut Random::with_seed(1));
+        let plant = domain.get_plant(plant).unwrap();
+        assert_eq!(plant.stress, PlantStress::Cold);
+        assert_eq!(plant.growth, 0.0);
+        assert!(plant.health < 1.0);
+    }
+
+    #[test]
+    fn test_not_growing_plant_not_updated() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        let events = domain.update(6.0, &mut Random::with_seed(1));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, PlantUpdated { .. })));
+
+        domain
+            .integrate_climate(plant, "winter", -20.0, false)
+            .unwrap();
+        let events = domain.update(6.0, &mut Random::with_seed(1));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, PlantUpdated { .. })));
+    }
+
+    #[test]
+    fn test_plant_consumes_and_restores_specific_nutrients() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        let soil = domain.soils[0].id;
+        let place = 5;
+        domain.get_soil_mut(soil).unwrap().nutrients[Nutrient::POTASSIUM][place] = 0.5;
+
+        let (lack, consumption) = domain
+            .request_nutrients_consumption(soil, [1, 1], plant, 10.0)
+            .unwrap();
+
+        assert_eq!(lack, 0.0);
+        assert!((consumption - 0.1).abs() < 0.0001);
+        let soil = domain.get_soil(soil).unwrap();
+        assert!((soil.nutrients[Nutrient::NITROGEN][place] - 0.9).abs() < 0.0001);
+        assert_eq!(soil.nutrients[Nutrient::PHOSPHORUS][place], 1.0);
+        assert!((soil.nutrients[Nutrient::POTASSIUM][place] - 0.6).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_monoculture_depletes_soil_faster() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        let soil = domain.soils[0].id;
+        let kind = domain.get_plant(plant).unwrap().kind.clone();
+        for _ in 0..2 {
+            domain.create_plant(soil, [1, 1], &kind, 0.0).unwrap().1();
+        }
+        let place = 5;
+        assert_eq!(domain.get_soil(soil).unwrap().history[place].len(), 3);
+
+        let (_, consumption) = domain
+            .request_nutrients_consumption(soil, [1, 1], plant, 10.0)
+            .unwrap();
+
+        let rotation = 1.0 + 2.0 * Soil::MONOCULTURE_PENALTY;
+        assert!((consumption - 0.1 * rotation).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_fertilizer_adds_nutrients_to_channels() {
+        let (mut domain, _plant) = prepare_domain(vec![]);
+        let soil = domain.soils[0].id;
+        domain.get_soil_mut(soil).unwrap().nutrients[Nutrient::PHOSPHORUS][0] = 0.0;
+        domain.fertilize(soil, [0, 0], [0.5, 0.25, 0.0]).unwrap()();
+        let soil = domain.get_soil(soil).unwrap();
+        assert_eq!(soil.nutrients[Nutrient::NITROGEN][0], 1.0);
+        assert_eq!(soil.nutrients[Nutrient::PHOSPHORUS][0], 0.25);
+        assert_eq!(soil.nutrients[Nutrient::POTASSIUM][0], 1.0);
+    }
+
+    #[test]
+    fn test_stressed_plant_loses_fruits_quality() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.update(10.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_plant(plant).unwrap().get_fruits_quality(), 1.0);
+
+        domain.integrate_thirst(plant, 0.5, 0.0).unwrap();
+        domain.update(10.0, &mut Random::with_seed(1));
+
+        let quality = domain.get_plant(plant).unwrap().get_fruits_quality();
+        assert!((quality - (1.0 - 10.0 * 0.5 * Plant::QUALITY_LOSS)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_pollinators_improve_fruits_quality() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        {
+            let plant = domain.get_plant_mut(plant).unwrap();
+            plant.growth = 2.0;
+            plant.stage = PlantStage::Flowering;
+        }
+        domain
+            .get_plant_mut(plant)
+            .unwrap()
+            .kind
+            .borrow_mut()
+            .pollination = 0.5;
+        assert_eq!(domain.get_plant(plant).unwrap().get_fruits_quality(), 0.5);
+
+        domain.integrate_pollinators(plant, 2).unwrap();
+        let events = domain.update(10.0, &mut Random::with_seed(1));
+
+        let plant_state = domain.get_plant(plant).unwrap();
+        assert_eq!(plant_state.pollination, 1.0);
+        assert_eq!(plant_state.get_fruits_quality(), 1.0);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, PlantPollinated { .. })));
+
+        let events = domain.update(1.0, &mut Random::with_seed(1));
+        assert_eq!(
+            domain.get_plant(plant).unwrap().stage,
+            PlantStage::Flowering
+        );
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, PlantPollinated { .. })));
+    }
+
+    #[test]
+    fn test_storm_wind_damages_plant() {
+        let (mut domain, plant) = prepare_domain(vec![]);
+        domain.integrate_wind(plant, 1.0).unwrap();
+
+        domain.update(100.0, &mut Random::with_seed(1));
+        let health = domain.get_plant(plant).unwrap().health;
+        assert!((health - (1.0 - 100.0 * Plant::WIND_DAMAGE)).abs() < 0.0001);
+
+        domain.integrate_wind(plant, 0.0).unwrap();
+        domain.update(1.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_plant(plant).unwrap().health, health);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::raising::{
-    Animal, AnimalId, AnimalKind, Behaviour, Raising, RaisingDomain, RaisingError,
+    Animal, AnimalId, AnimalKind, Behaviour, Raising, RaisingDomain, RaisingError, Sex,
 };
 use crate::timing::Shared;
 
@@ -7,12 +7,14 @@ impl RaisingDomain {
     pub fn create_animal(
         &mut self,
         kind: Shared<AnimalKind>,
+        sex: Sex,
     ) -> Result<(AnimalId, impl FnOnce() -> Vec<Raising> + '_), RaisingError> {
         let id = AnimalId(self.animals_id + 1);
         let command = move || {
             self.animals.push(Animal {
                 id,
                 kind,
+                sex,
                 age: 0.0,
                 weight: 0.0,
                 thirst: 0.0,
@@ -21,6 +23,8 @@ impl RaisingDomain {
                 health: 1.0,
                 stress: 0.0,
                 behaviour: Behaviour::Idle,
+                pregnancy: None,
+                partners: vec![],
             });
             self.animals_id += 1;
             vec![]
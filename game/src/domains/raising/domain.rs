@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::collections::Shared;
+use crate::math::Random;
 
 #[derive(Default)]
 pub struct RaisingDomain {
@@ -9,6 +10,7 @@ pub struct RaisingDomain {
     pub animals_id: usize,
     pub animals: Vec<Animal>,
     pub dead_animals: Vec<Animal>,
+    pub births: Vec<AnimalId>,
     pub tethers_id: usize,
     pub tethers: Vec<Tether>,
 }
@@ -21,11 +23,22 @@ pub struct Herd {
     pub herdsman: HerdsmanId,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Sex {
     Male,
     Female,
 }
 
+impl Sex {
+    pub fn random(random: &mut Random) -> Self {
+        if random.generate() < 0.5 {
+            Sex::Male
+        } else {
+            Sex::Female
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AnimalKey(pub(crate) usize);
 
@@ -38,6 +51,17 @@ pub struct AnimalKind {
     pub thirst_damage: f32,
     pub death_threshold: f32,
     pub voracity: f32,
+    /// Age in seconds from which animal can breed.
+    pub maturity: f32,
+    /// Duration of pregnancy in seconds.
+    pub gestation: f32,
+    /// Chance of conception per second of mating.
+    pub fertility: f32,
+}
+
+impl AnimalKind {
+    /// Hunger above which animal is not interested in breeding.
+    pub const BREEDING_HUNGER: f32 = 0.35;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -57,7 +81,7 @@ pub struct Animal {
     // pub flock: HerdId,
     pub age: f32,
     pub weight: f32,
-    // pub sex: Sex,
+    pub sex: Sex,
     pub thirst: f32,
     pub hunger: f32,
     pub voracity: f32,
@@ -66,6 +90,21 @@ pub struct Animal {
     pub stress: f32,
 
     pub behaviour: Behaviour,
+
+    /// Elapsed time of pregnancy, if animal is pregnant.
+    pub pregnancy: Option<f32>,
+    /// Animals close enough to mate with, integrated by game every update.
+    pub partners: Vec<AnimalId>,
+}
+
+impl Animal {
+    pub fn is_mature(&self) -> bool {
+        self.age >= self.kind.maturity
+    }
+
+    pub fn is_ready_to_breed(&self) -> bool {
+        self.is_mature() && self.hunger < AnimalKind::BREEDING_HUNGER && self.pregnancy.is_none()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -122,6 +161,13 @@ pub enum Raising {
         id: AnimalId,
         tether: TetherId,
     },
+    AnimalConceived {
+        id: AnimalId,
+        father: AnimalId,
+    },
+    AnimalGaveBirth {
+        id: AnimalId,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1,6 +1,8 @@
 use crate::math::Random;
-use crate::raising::Raising::{AnimalChanged, AnimalHealthChanged};
-use crate::raising::{Animal, Raising, RaisingDomain};
+use crate::raising::Raising::{
+    AnimalChanged, AnimalConceived, AnimalGaveBirth, AnimalHealthChanged,
+};
+use crate::raising::{Animal, AnimalId, Raising, RaisingDomain, RaisingError, Sex};
 use log::error;
 
 use std::mem::take;
@@ -10,6 +12,21 @@ impl RaisingDomain {
         take(&mut self.dead_animals)
     }
 
+    /// Returns mothers of animals born since last call.
+    pub fn take_births(&mut self) -> Vec<AnimalId> {
+        take(&mut self.births)
+    }
+
+    pub fn integrate_partners(
+        &mut self,
+        id: AnimalId,
+        partners: Vec<AnimalId>,
+    ) -> Result<(), RaisingError> {
+        let animal = self.get_animal_mut(id)?;
+        animal.partners = partners;
+        Ok(())
+    }
+
     pub fn update(&mut self, time: f32, random: &mut Random) -> Vec<Raising> {
         let mut events = vec![];
         let mut dead_animals = vec![];
@@ -65,6 +82,15 @@ impl RaisingDomain {
 
             if animal.health <= 0.0 {
                 dead_animals.push(animal.id);
+            } else if let Some(pregnancy) = animal.pregnancy {
+                let pregnancy = pregnancy + time;
+                if pregnancy >= kind.gestation {
+                    animal.pregnancy = None;
+                    self.births.push(animal.id);
+                    events.push(AnimalGaveBirth { id: animal.id });
+                } else {
+                    animal.pregnancy = Some(pregnancy);
+                }
             }
 
             // info!(
@@ -83,6 +109,114 @@ impl RaisingDomain {
             let animal = self.animals.remove(index);
             self.dead_animals.push(animal);
         }
+        events.extend(self.breed_animals(time, random));
+        events
+    }
+
+    fn breed_animals(&mut self, time: f32, random: &mut Random) -> Vec<Raising> {
+        let mut conceptions = vec![];
+        for animal in &self.animals {
+            if animal.sex != Sex::Female || !animal.is_ready_to_breed() {
+                continue;
+            }
+            let father = animal.partners.iter().find(|partner| {
+                self.get_animal(**partner).is_ok_and(|partner| {
+                    partner.sex == Sex::Male
+                        && partner.kind.id == animal.kind.id
+                        && partner.is_ready_to_breed()
+                })
+            });
+            if let Some(father) = father {
+                if random.generate() < animal.kind.fertility * time {
+                    conceptions.push((animal.id, *father));
+                }
+            }
+        }
+        let mut events = vec![];
+        for (id, father) in conceptions {
+            if let Ok(animal) = self.get_animal_mut(id) {
+                animal.pregnancy = Some(0.0);
+                events.push(AnimalConceived { id, father });
+            }
+        }
         events
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Shared;
+    use crate::math::Random;
+    use crate::raising::Raising::{AnimalConceived, AnimalGaveBirth};
+    use crate::raising::{AnimalId, AnimalKey, AnimalKind, RaisingDomain, Sex};
+
+    fn prepare_domain(sexes: Vec<Sex>) -> (RaisingDomain, Vec<AnimalId>) {
+        let mut domain = RaisingDomain::default();
+        let kind = Shared::new(AnimalKind {
+            id: AnimalKey(1),
+            name: "test".to_string(),
+            hunger_speed: 0.0,
+            thirst_speed: 0.0,
+            hunger_damage: 0.0,
+            thirst_damage: 0.0,
+            death_threshold: 0.0,
+            voracity: 0.0,
+            maturity: 10.0,
+            gestation: 20.0,
+            fertility: 1.0,
+        });
+        let mut animals = vec![];
+        for sex in sexes {
+            let (animal, create_animal) = domain.create_animal(kind.clone(), sex).unwrap();
+            create_animal();
+            animals.push(animal);
+        }
+        for &animal in &animals {
+            let partners = animals.iter().cloned().filter(|id| *id != animal).collect();
+            domain.integrate_partners(animal, partners).unwrap();
+        }
+        (domain, animals)
+    }
+
+    #[test]
+    fn test_mature_pair_breeds_offspring() {
+        let (mut domain, animals) = prepare_domain(vec![Sex::Female, Sex::Male]);
+        let [female, male] = [animals[0], animals[1]];
+
+        domain.update(5.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_animal(female).unwrap().pregnancy, None);
+
+        let events = domain.update(5.0, &mut Random::with_seed(1));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AnimalConceived { id, father } if *id == female && *father == male
+        )));
+        assert_eq!(domain.get_animal(female).unwrap().pregnancy, Some(0.0));
+        assert_eq!(domain.get_animal(male).unwrap().pregnancy, None);
+
+        domain.update(15.0, &mut Random::with_seed(1));
+        assert!(domain.take_births().is_empty());
+
+        let events = domain.update(5.0, &mut Random::with_seed(1));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, AnimalGaveBirth { id } if *id == female)));
+        assert_eq!(domain.take_births(), vec![female]);
+    }
+
+    #[test]
+    fn test_hungry_or_same_sex_animals_do_not_breed() {
+        let (mut domain, animals) = prepare_domain(vec![Sex::Female, Sex::Female]);
+        domain.update(10.0, &mut Random::with_seed(1));
+        domain.update(10.0, &mut Random::with_seed(1));
+        assert!(animals
+            .iter()
+            .all(|id| domain.get_animal(*id).unwrap().pregnancy.is_none()));
+
+        let (mut domain, animals) = prepare_domain(vec![Sex::Female, Sex::Male]);
+        domain.get_animal_mut(animals[1]).unwrap().hunger = 0.9;
+        domain.update(10.0, &mut Random::with_seed(1));
+        domain.update(10.0, &mut Random::with_seed(1));
+        assert_eq!(domain.get_animal(animals[0]).unwrap().pregnancy, None);
+    }
+}
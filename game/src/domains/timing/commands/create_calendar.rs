@@ -1,5 +1,7 @@
 use crate::collections::Shared;
-use crate::timing::{Calendar, CalendarId, CalendarKind, Timing, TimingDomain, TimingError};
+use crate::timing::{
+    Calendar, CalendarId, CalendarKind, Timing, TimingDomain, TimingError, Weather,
+};
 
 impl TimingDomain {
     pub fn create_calendar(
@@ -7,13 +9,17 @@ impl TimingDomain {
         kind: &Shared<CalendarKind>,
     ) -> Result<(CalendarId, impl FnOnce() -> Vec<Timing> + '_), TimingError> {
         let id = self.calendars_id.introduce().one(CalendarId);
-        let space = Calendar {
+        let mut space = Calendar {
             id,
             kind: kind.clone(),
             season: 0,
             season_day: 0.0,
             times_of_day: 0.0,
+            day: 0,
+            weather: Weather::Clear,
+            seed: self.seed,
         };
+        space.weather = space.get_weather(space.day, space.season);
         let command = move || {
             let events = vec![];
             self.calendars_id.register(id.0);
@@ -1,4 +1,5 @@
 pub use crate::collections::{Sequence, Shared};
+use crate::math::Random;
 use serde::{Deserialize, Serialize};
 
 pub struct TimingDomain {
@@ -7,6 +8,8 @@ pub struct TimingDomain {
     pub speed: f32,
     pub calendars_id: Sequence,
     pub calendars: Vec<Calendar>,
+    /// Seed of game, so weather differs between games with the same calendars.
+    pub seed: u64,
 }
 
 impl Default for TimingDomain {
@@ -17,6 +20,7 @@ impl Default for TimingDomain {
             speed: 1.0,
             calendars_id: Sequence::default(),
             calendars: vec![],
+            seed: 0,
         }
     }
 }
@@ -37,6 +41,106 @@ impl MinGameMinute {
 pub struct Season {
     pub duration: MinGameMinute,
     pub key: String,
+    #[serde(default = "Season::default_temperature")]
+    pub temperature: f32,
+    #[serde(default)]
+    pub weather: WeatherChances,
+}
+
+impl Season {
+    fn default_temperature() -> f32 {
+        20.0
+    }
+}
+
+/// Chances of weather for every day of season, the rest of days are clear.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(default)]
+pub struct WeatherChances {
+    pub rain: f32,
+    pub heat: f32,
+    pub drought: f32,
+    pub storm: f32,
+}
+
+impl Default for WeatherChances {
+    fn default() -> Self {
+        Self {
+            rain: 0.2,
+            heat: 0.1,
+            drought: 0.05,
+            storm: 0.05,
+        }
+    }
+}
+
+impl WeatherChances {
+    pub fn choose(&self, roll: f32) -> Weather {
+        let chances = [
+            (self.rain, Weather::Rain),
+            (self.heat, Weather::Heat),
+            (self.drought, Weather::Drought),
+            (self.storm, Weather::Storm),
+        ];
+        let mut threshold = 0.0;
+        for (chance, weather) in chances {
+            threshold += chance;
+            if roll < threshold {
+                return weather;
+            }
+        }
+        Weather::Clear
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Heat,
+    Drought,
+    /// Heavy rain with wind breaking plants outside rooms.
+    Storm,
+}
+
+impl Weather {
+    /// Volume of water falling on every place of land per second.
+    pub fn rainfall(&self) -> f32 {
+        match self {
+            Weather::Rain => 0.005,
+            Weather::Storm => 0.01,
+            _ => 0.0,
+        }
+    }
+
+    /// Multiplier of moisture evaporation.
+    pub fn evaporation(&self) -> f32 {
+        match self {
+            Weather::Clear => 1.0,
+            Weather::Rain | Weather::Storm => 0.25,
+            Weather::Heat => 1.5,
+            Weather::Drought => 2.5,
+        }
+    }
+
+    /// Deviation of temperature from season one.
+    pub fn temperature(&self) -> f32 {
+        match self {
+            Weather::Clear => 0.0,
+            Weather::Rain => -2.0,
+            Weather::Heat => 8.0,
+            Weather::Drought => 5.0,
+            Weather::Storm => -5.0,
+        }
+    }
+
+    /// Strength of wind in range [0.0, 1.0].
+    pub fn wind(&self) -> f32 {
+        match self {
+            Weather::Storm => 1.0,
+            _ => 0.0,
+        }
+    }
 }
 
 pub struct CalendarKind {
@@ -55,6 +159,48 @@ pub struct Calendar {
     pub season: u8,
     pub season_day: f32,
     pub times_of_day: f32,
+    /// Number of days passed since calendar creation.
+    pub day: u32,
+    pub weather: Weather,
+    pub seed: u64,
+}
+
+impl Calendar {
+    pub const FORECAST_DAYS: u32 = 3;
+
+    #[inline]
+    pub fn get_season(&self) -> &Season {
+        &self.kind.seasons[self.season as usize]
+    }
+
+    /// Weather is rolled from game seed, calendar and day numbers only,
+    /// so forecast always comes true.
+    pub fn get_weather(&self, day: u32, season: u8) -> Weather {
+        let seed = self.seed ^ ((self.id.0 as u64) << 32 | day as u64);
+        let roll = Random::with_seed(seed).generate();
+        self.kind.seasons[season as usize].weather.choose(roll)
+    }
+
+    /// Returns weather of next days, season of every day is determined at its beginning.
+    pub fn get_forecast(&self, days: u32) -> Vec<Weather> {
+        let day_duration = self.kind.day_duration.as_f32();
+        let mut season = self.season;
+        let mut season_day = self.season_day + (1.0 - self.times_of_day) * day_duration;
+        let mut forecast = Vec::with_capacity(days as usize);
+        for day in 1..=days {
+            loop {
+                let duration = self.kind.seasons[season as usize].duration.as_f32();
+                if season_day < duration {
+                    break;
+                }
+                season_day -= duration;
+                season = (season + 1) % self.kind.seasons.len() as u8;
+            }
+            forecast.push(self.get_weather(self.day + day, season));
+            season_day += day_duration;
+        }
+        forecast
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -69,6 +215,11 @@ pub enum Timing {
         season_day: f32,
         times_of_day: f32,
     },
+    WeatherChanged {
+        id: CalendarId,
+        weather: Weather,
+        forecast: Vec<Weather>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
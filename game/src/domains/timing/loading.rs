@@ -1,10 +1,21 @@
 use crate::timing::{Calendar, TimingDomain};
 
 impl TimingDomain {
-    pub fn load_calendars(&mut self, calendars: Vec<Calendar>) {
-        for calendar in &calendars {
+    pub fn load_calendars(&mut self, mut calendars: Vec<Calendar>) {
+        for calendar in &mut calendars {
             self.calendars_id.register(calendar.id.0);
+            calendar.seed = self.seed;
+            calendar.weather = calendar.get_weather(calendar.day, calendar.season);
         }
         self.calendars.extend(calendars);
     }
+
+    /// Changes game seed and rerolls current weather of all calendars.
+    pub fn load_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        for calendar in &mut self.calendars {
+            calendar.seed = seed;
+            calendar.weather = calendar.get_weather(calendar.day, calendar.season);
+        }
+    }
 }
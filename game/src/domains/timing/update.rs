@@ -1,4 +1,4 @@
-use crate::timing::{Timing, TimingDomain};
+use crate::timing::{Calendar, Timing, TimingDomain};
 
 impl TimingDomain {
     pub fn get_colonization_date(&self, real_seconds: f32, speed: f32) -> f32 {
@@ -13,8 +13,11 @@ impl TimingDomain {
         }];
         for calendar in self.calendars.iter_mut() {
             calendar.times_of_day += colonization_date_delta / calendar.kind.day_duration.as_f32();
+            let mut new_day = false;
             while calendar.times_of_day >= 1.0 {
                 calendar.times_of_day -= 1.0;
+                calendar.day += 1;
+                new_day = true;
             }
             calendar.season_day += colonization_date_delta;
             let season = &calendar.kind.seasons[calendar.season as usize];
@@ -27,8 +30,87 @@ impl TimingDomain {
                 season: calendar.season,
                 season_day: calendar.season_day,
                 times_of_day: calendar.times_of_day,
-            })
+            });
+            if new_day {
+                calendar.weather = calendar.get_weather(calendar.day, calendar.season);
+                events.push(Timing::WeatherChanged {
+                    id: calendar.id,
+                    weather: calendar.weather,
+                    forecast: calendar.get_forecast(Calendar::FORECAST_DAYS),
+                })
+            }
         }
         events
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Shared;
+    use crate::timing::{
+        Calendar, CalendarKey, CalendarKind, MinGameMinute, Season, Timing, TimingDomain,
+        WeatherChances,
+    };
+
+    fn season(key: &str, rain: f32, drought: f32) -> Season {
+        Season {
+            duration: MinGameMinute(2),
+            key: key.to_string(),
+            temperature: 20.0,
+            weather: WeatherChances {
+                rain,
+                heat: 0.0,
+                drought,
+                storm: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_weather_comes_as_forecast() {
+        let mut domain = TimingDomain::default();
+        let kind = Shared::new(CalendarKind {
+            id: CalendarKey(1),
+            name: "test".to_string(),
+            day_duration: MinGameMinute(1),
+            seasons: vec![season("wet", 0.5, 0.0), season("dry", 0.0, 0.5)],
+        });
+        let (id, create_calendar) = domain.create_calendar(&kind).unwrap();
+        create_calendar();
+        domain.update(0.25, 1.0);
+
+        let forecast = domain
+            .get_calendar(id)
+            .unwrap()
+            .get_forecast(Calendar::FORECAST_DAYS * 3);
+        let mut weather = vec![];
+        for _ in 0..forecast.len() {
+            for event in domain.update(1.0, 1.0) {
+                if let Timing::WeatherChanged { weather: value, .. } = event {
+                    weather.push(value);
+                }
+            }
+        }
+
+        assert_eq!(weather, forecast);
+    }
+
+    #[test]
+    fn test_weather_depends_on_game_seed() {
+        let kind = Shared::new(CalendarKind {
+            id: CalendarKey(1),
+            name: "test".to_string(),
+            day_duration: MinGameMinute(1),
+            seasons: vec![season("wet", 0.5, 0.0)],
+        });
+        let mut forecasts = vec![];
+        for seed in [1, 2] {
+            let mut domain = TimingDomain::default();
+            domain.load_seed(seed);
+            let (id, create_calendar) = domain.create_calendar(&kind).unwrap();
+            create_calendar();
+            forecasts.push(domain.get_calendar(id).unwrap().get_forecast(30));
+        }
+        assert_ne!(forecasts[0], forecasts[1]);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::working::Working::DeviceUpdated;
+use crate::working::{DeviceId, Working, WorkingDomain, WorkingError};
+
+impl WorkingDomain {
+    pub fn consume_input(
+        &mut self,
+        id: DeviceId,
+    ) -> Result<impl FnOnce() -> Vec<Working> + '_, WorkingError> {
+        let device = self.get_device_mut(id)?;
+        if !device.enabled || device.input {
+            return Err(WorkingError::DeviceInputNotAccepted { id });
+        }
+        let command = move || {
+            device.input = true;
+            vec![DeviceUpdated {
+                device: device.id,
+                enabled: device.enabled,
+                broken: device.broken,
+                progress: device.progress,
+                input: device.input,
+                output: device.output,
+                deprecation: device.deprecation,
+            }]
+        };
+        Ok(command)
+    }
+}
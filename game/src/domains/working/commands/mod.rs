@@ -1,3 +1,5 @@
+mod consume_input;
 mod create_device;
+mod produce_output;
 mod repair_device;
 mod toggle_device;
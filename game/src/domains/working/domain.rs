@@ -47,4 +47,6 @@ pub enum Working {
 #[derive(Debug, Serialize, Deserialize)]
 pub enum WorkingError {
     DeviceNotFound { id: DeviceId },
+    DeviceInputNotAccepted { id: DeviceId },
+    DeviceOutputNotReady { id: DeviceId },
 }
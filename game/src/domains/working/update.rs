@@ -1,32 +1,9 @@
-use rand::Rng;
-
+use crate::math::Random;
 use crate::working::Working::DeviceUpdated;
-use crate::working::{DeviceId, Working, WorkingDomain, WorkingError};
+use crate::working::{Working, WorkingDomain};
 
 impl WorkingDomain {
-    pub fn consume_input(&mut self, id: DeviceId) -> Result<bool, WorkingError> {
-        let device = self.get_device_mut(id)?;
-        let consumed = if device.enabled && !device.input {
-            device.input = true;
-            true
-        } else {
-            false
-        };
-        Ok(consumed)
-    }
-
-    pub fn produce_output(&mut self, id: DeviceId) -> Result<bool, WorkingError> {
-        let device = self.get_device_mut(id)?;
-        let produced = if device.output {
-            device.output = false;
-            true
-        } else {
-            false
-        };
-        Ok(produced)
-    }
-
-    pub fn update(&mut self, time: f32, mut random: impl Rng) -> Vec<Working> {
+    pub fn update(&mut self, time: f32, random: &mut Random) -> Vec<Working> {
         let mut events = vec![];
         for device in self.devices.iter_mut() {
             if device.broken || !device.enabled {
@@ -43,7 +20,7 @@ impl WorkingDomain {
                 continue;
             }
 
-            let jam = random.gen_range(0.0..=1.0);
+            let jam = random.generate();
             let deprecation = device.deprecation / device.kind.durability;
             if deprecation > 0.25 && deprecation >= jam {
                 device.broken = true;
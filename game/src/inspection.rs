@@ -1,14 +1,15 @@
-use crate::api::{ActionError, Event};
+use crate::api::{ActionError, Entity, Event};
 use crate::inventory::{Inventory, ItemData};
 use crate::landscaping::Landscaping;
 use crate::math::{Array, ArrayIndex, VectorMath};
 use crate::model::{
     Assembly, Cementer, Composter, Construction, Corpse, Creature, Crop, Door, Equipment, Farmer,
-    Farmland, PlayerId, Rest, Stack, Theodolite, Universe,
+    Farmland, Irrigator, PlayerId, Rest, Stack, Theodolite, Tree, Universe, UniverseError,
 };
 use crate::physics::Physics;
 use crate::planting::Planting;
-use crate::Game;
+use crate::timing::Calendar;
+use crate::{Game, Subsystem};
 
 impl Game {
     pub fn inspect_player_private_space(
@@ -16,16 +17,16 @@ impl Game {
         player: PlayerId,
     ) -> Result<Vec<Event>, ActionError> {
         let mut events = vec![];
-        let limit_x = 128;
-        let limit_y = 128;
         let range = [37, 21];
         let farmer = self.universe.get_player_farmer(player)?;
         let body = self.physics.get_body(farmer.body)?;
         let farmland = self.universe.get_farmland_by_space(body.space)?;
-        let rect = body.position.to_tile().rect([limit_x, limit_y], range);
         let land = self.landscaping.get_land(farmland.land)?;
+        let limit = [land.kind.width, land.kind.height];
+        let rect = body.position.to_tile().rect(limit, range);
         let surface = land.surface.copy(land.kind.width, rect);
-        let moisture = land.moisture.copy(land.kind.width, rect);
+        let elevation = land.elevation.copy(land.kind.width, rect);
+        // moisture changes are streamed by landscaping update
         let moisture_capacity = land.moisture_capacity.copy(land.kind.width, rect);
         events.push(
             vec![
@@ -33,27 +34,26 @@ impl Game {
                     land: land.id,
                     rect,
                     surface,
+                    elevation,
                 },
                 Landscaping::MoistureCapacityInspected {
                     land: land.id,
                     rect,
                     moisture_capacity,
                 },
-                Landscaping::MoistureInspected {
-                    land: land.id,
-                    rect,
-                    moisture,
-                },
             ]
             .into(),
         );
         let soil = self.planting.get_soil(farmland.soil)?;
-        let fertility = soil.fertility.copy(soil.kind.width, rect);
+        let nutrients = soil
+            .nutrients
+            .each_ref()
+            .map(|channel| channel.copy(soil.kind.width, rect));
         events.push(
-            vec![Planting::SoilFertilityInspected {
+            vec![Planting::SoilNutrientsInspected {
                 soil: soil.id,
                 rect,
-                fertility,
+                nutrients,
             }]
             .into(),
         );
@@ -71,11 +71,25 @@ impl Game {
             growth: plant.growth,
             health: plant.health,
             fruits: plant.fruits,
+            stage: plant.stage,
+            stress: plant.stress,
+            infection: plant.infection,
             position: barrier.position,
         };
         Ok(event)
     }
 
+    pub fn inspect_tree(&self, tree: Tree) -> Result<Universe, ActionError> {
+        let plant = self.planting.get_plant(tree.plant)?;
+        let barrier = self.physics.get_barrier(tree.barrier)?;
+        let event = Universe::TreeAppeared {
+            tree,
+            position: barrier.position,
+            growth: plant.growth,
+        };
+        Ok(event)
+    }
+
     pub fn inspect_creature(&self, entity: Creature) -> Result<Universe, ActionError> {
         let animal = self.raising.get_animal(entity.animal)?;
         let body = self.physics.get_body(entity.body)?;
@@ -87,6 +101,8 @@ impl Game {
             hunger: animal.hunger,
             age: animal.age,
             weight: animal.weight,
+            sex: animal.sex,
+            pregnancy: animal.pregnancy,
             position: body.position,
             behaviour: animal.behaviour,
         };
@@ -184,6 +200,16 @@ impl Game {
         Ok(event)
     }
 
+    pub fn inspect_irrigator(&self, irrigator: Irrigator) -> Result<Universe, ActionError> {
+        let irrigation = self.landscaping.get_irrigation(irrigator.irrigation)?;
+        let event = Universe::IrrigatorAppeared {
+            entity: irrigator,
+            place: irrigation.place,
+            pressure: irrigation.pressure,
+        };
+        Ok(event)
+    }
+
     pub fn inspect_construction(
         &self,
         construction: Construction,
@@ -212,7 +238,9 @@ impl Game {
             .players
             .iter()
             .find(|player| player.id == farmer.player)
-            .unwrap();
+            .ok_or(UniverseError::PlayerNotFound {
+                player: farmer.player,
+            })?;
         Ok(Universe::FarmerAppeared {
             farmer,
             player: player.name.clone(),
@@ -221,11 +249,11 @@ impl Game {
     }
 
     pub fn inspect_farmland(&self, farmland: Farmland) -> Result<Universe, ActionError> {
-        let _soil = self.planting.get_soil(farmland.soil).unwrap();
-        let grid = self.building.get_grid(farmland.grid).unwrap();
-        let space = self.physics.get_space(farmland.space).unwrap();
-        let _land = self.landscaping.get_land(farmland.land).unwrap();
-        let calendar = self.timing.get_calendar(farmland.calendar).unwrap();
+        let _soil = self.planting.get_soil(farmland.soil)?;
+        let grid = self.building.get_grid(farmland.grid)?;
+        let space = self.physics.get_space(farmland.space)?;
+        let _land = self.landscaping.get_land(farmland.land)?;
+        let calendar = self.timing.get_calendar(farmland.calendar)?;
         Ok(Universe::FarmlandAppeared {
             farmland,
             cells: grid.cells.clone(),
@@ -234,18 +262,44 @@ impl Game {
             season: calendar.season,
             season_day: calendar.season_day,
             times_of_day: calendar.times_of_day,
+            weather: calendar.weather,
+            forecast: calendar.get_forecast(Calendar::FORECAST_DAYS),
         })
     }
 
-    pub fn look_around(&self) -> Vec<Event> {
+    fn inspect_farmland_moisture(
+        &self,
+        farmland: Farmland,
+    ) -> Result<(Universe, Landscaping), ActionError> {
+        let land = self.landscaping.get_land(farmland.land)?;
+        let rect = [0, 0, land.kind.width, land.kind.height];
+        let moisture = self.landscaping.inspect_moisture(land.id, rect)?;
+        Ok((self.inspect_farmland(farmland)?, moisture))
+    }
+
+    /// Inspects whole world for new player.
+    ///
+    /// Entity failed to be inspected is skipped and reported as update failure.
+    pub fn look_around(&mut self) -> Vec<Event> {
         let mut stream = vec![];
+        let mut lands = vec![];
+        let mut failures = vec![];
 
         for farmland in self.universe.farmlands.iter() {
-            stream.push(self.inspect_farmland(*farmland).unwrap());
+            match self.inspect_farmland_moisture(*farmland) {
+                Ok((farmland, moisture)) => {
+                    stream.push(farmland);
+                    lands.push(moisture);
+                }
+                Err(error) => failures.push((None, error)),
+            }
         }
 
         for farmer in self.universe.farmers.iter() {
-            stream.push(self.inspect_farmer(*farmer).unwrap());
+            match self.inspect_farmer(*farmer) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Farmer(*farmer)), error)),
+            }
         }
 
         for stack in &self.universe.stacks {
@@ -253,19 +307,38 @@ impl Game {
         }
 
         for construction in &self.universe.constructions {
-            stream.push(self.inspect_construction(*construction).unwrap());
+            match self.inspect_construction(*construction) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((None, error)),
+            }
+        }
+
+        for tree in &self.universe.trees {
+            match self.inspect_tree(*tree) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Tree(*tree)), error)),
+            }
         }
 
         for crop in &self.universe.crops {
-            stream.push(self.inspect_crop(*crop).unwrap());
+            match self.inspect_crop(*crop) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Crop(*crop)), error)),
+            }
         }
 
         for creature in &self.universe.creatures {
-            stream.push(self.inspect_creature(*creature).unwrap());
+            match self.inspect_creature(*creature) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Creature(*creature)), error)),
+            }
         }
 
         for corpse in &self.universe.corpses {
-            stream.push(self.inspect_corpse(*corpse).unwrap());
+            match self.inspect_corpse(*corpse) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Corpse(*corpse)), error)),
+            }
         }
 
         for equipment in &self.universe.equipments {
@@ -273,7 +346,17 @@ impl Game {
         }
 
         for theodolite in &self.universe.theodolites {
-            stream.push(self.inspect_theodolite(*theodolite).unwrap());
+            match self.inspect_theodolite(*theodolite) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Theodolite(*theodolite)), error)),
+            }
+        }
+
+        for irrigator in &self.universe.irrigators {
+            match self.inspect_irrigator(*irrigator) {
+                Ok(event) => stream.push(event),
+                Err(error) => failures.push((Some(Entity::Irrigator(*irrigator)), error)),
+            }
         }
 
         for assembly in &self.universe.assembly {
@@ -304,6 +387,7 @@ impl Game {
                     key: item.kind.id,
                     container: item.container,
                     quantity: item.quantity,
+                    quality: item.quality,
                 })
             }
         }
@@ -312,19 +396,31 @@ impl Game {
 
         // client uses barrier hints to simulate physics locally to smooth network lag
         // so, send information about all barriers like it just created
-        let barriers_hint = self.physics.barriers[1]
+        let barriers_hint = self
+            .physics
+            .spaces
             .iter()
+            .flat_map(|space| self.physics.barriers.get(&space.id).into_iter().flatten())
             .map(|barrier| Physics::BarrierCreated {
                 id: barrier.id,
                 key: barrier.kind.id,
                 space: barrier.space,
                 position: barrier.position,
                 active: barrier.active,
+                rotation: barrier.rotation,
             })
             .collect();
 
+        for (entity, error) in failures {
+            match entity {
+                Some(entity) => self.quarantine(Subsystem::Inspection, entity, error),
+                None => self.report_failure(Subsystem::Inspection, None, error),
+            }
+        }
+
         vec![
             Event::UniverseStream(stream),
+            Event::LandscapingStream(lands),
             Event::PhysicsStream(barriers_hint),
             Event::InventoryStream(all_game_items),
         ]
@@ -2,14 +2,14 @@ use crate::api::ActionError;
 use crate::assembling::PlacementId;
 use crate::building::{GridId, SurveyorId};
 use crate::inventory::ContainerId;
-use crate::landscaping::LandId;
+use crate::landscaping::{IrrigationId, LandId};
 use crate::model::*;
 use crate::physics::{BarrierId, BodyId, SensorId, SpaceId};
 use crate::planting::{PlantId, SoilId};
 use crate::raising::{AnimalId, TetherId};
 use crate::timing::CalendarId;
 use crate::working::DeviceId;
-use crate::Game;
+use crate::{Game, SensorTrigger};
 
 impl Game {
     pub fn appear_crop(
@@ -28,9 +28,42 @@ impl Game {
             sensor,
         };
         self.universe.crops.push(entity);
+        self.subscribe_sensor(sensor, SensorTrigger::CROP);
         self.inspect_crop(entity)
     }
 
+    pub fn appear_tree(
+        &mut self,
+        kind: TreeKey,
+        barrier: BarrierId,
+        plant: PlantId,
+    ) -> Result<Universe, ActionError> {
+        self.universe.trees_id += 1;
+        let entity = Tree {
+            id: self.universe.trees_id,
+            kind,
+            plant,
+            barrier,
+        };
+        self.universe.trees.push(entity);
+        self.inspect_tree(entity)
+    }
+
+    pub fn appear_irrigator(
+        &mut self,
+        key: IrrigatorKey,
+        irrigation: IrrigationId,
+    ) -> Result<Universe, ActionError> {
+        self.universe.irrigators_id += 1;
+        let irrigator = Irrigator {
+            id: self.universe.irrigators_id,
+            key,
+            irrigation,
+        };
+        self.universe.irrigators.push(irrigator);
+        self.inspect_irrigator(irrigator)
+    }
+
     pub fn appear_farmer(
         &mut self,
         kind: FarmerKey,
@@ -137,7 +170,8 @@ impl Game {
         &mut self,
         key: CorpseKey,
         barrier: BarrierId,
-    ) -> Result<Universe, ActionError> {
+        position: [f32; 2],
+    ) -> Universe {
         self.universe.corpses_id += 1;
         let entity = Corpse {
             id: self.universe.corpses_id,
@@ -145,7 +179,7 @@ impl Game {
             barrier,
         };
         self.universe.corpses.push(entity);
-        self.inspect_corpse(entity)
+        Universe::CorpseAppeared { entity, position }
     }
 
     pub fn appear_assembling_activity(
@@ -0,0 +1,285 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bincode::error::{DecodeError, EncodeError};
+use log::{error, info};
+
+use crate::api::{Action, ActionError, Event};
+use crate::math::Randomness;
+use crate::Game;
+
+/// Single step of game session in order of occurrence.
+///
+/// Every step holds digest of produced events to detect desync on replay.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub enum Record {
+    Start {
+        random: Randomness,
+    },
+    Accept {
+        tick: usize,
+        player: String,
+        digest: u64,
+    },
+    Perform {
+        tick: usize,
+        player: String,
+        action: Action,
+        digest: u64,
+    },
+    Update {
+        tick: usize,
+        time: f32,
+        digest: u64,
+    },
+}
+
+/// Append-only log of game session stored next to save file.
+///
+/// Every session writes own journal, so log of crashed session
+/// is kept after server restart.
+pub struct Journal {
+    pub path: String,
+    pub tick: usize,
+    file: File,
+}
+
+impl Journal {
+    pub fn path_of(save_file: &str) -> String {
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        format!("{save_file}.{session}.journal")
+    }
+
+    /// Creates new journal file, existing one is never overwritten.
+    pub fn create(path: &str) -> Result<Self, JournalError> {
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        Ok(Self {
+            path: path.to_string(),
+            tick: 0,
+            file,
+        })
+    }
+
+    pub fn append(&mut self, record: &Record) -> Result<(), JournalError> {
+        let config = bincode::config::standard();
+        let data = bincode::serde::encode_to_vec(record, config)?;
+        self.file.write_all(&data)?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    pub fn read(path: &str) -> Result<Vec<Record>, JournalError> {
+        let mut data = vec![];
+        File::open(path)?.read_to_end(&mut data)?;
+        let config = bincode::config::standard();
+        let mut records = vec![];
+        let mut offset = 0;
+        while offset < data.len() {
+            let (record, length) = bincode::serde::decode_from_slice(&data[offset..], config)?;
+            records.push(record);
+            offset += length;
+        }
+        Ok(records)
+    }
+
+    pub fn digest(events: &[Event]) -> u64 {
+        let config = bincode::config::standard();
+        let data = bincode::serde::encode_to_vec(events, config).unwrap_or_default();
+        fnv1a(&data)
+    }
+}
+
+/// FNV-1a hash, unlike std hashers it is stable across toolchains,
+/// so journals recorded by one build can be replayed by another.
+fn fnv1a(data: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+impl Game {
+    pub fn start_journal(&mut self, path: &str) -> Result<(), JournalError> {
+        info!("Starts game journal {path}");
+        let mut journal = Journal::create(path)?;
+        journal.append(&Record::Start {
+            random: self.random,
+        })?;
+        self.journal = Some(journal);
+        Ok(())
+    }
+
+    pub(crate) fn record(&mut self, record: impl FnOnce(usize) -> Record) {
+        if let Some(journal) = self.journal.as_mut() {
+            let record = record(journal.tick);
+            if let Err(error) = journal.append(&record) {
+                error!(
+                    "Unable to append record to journal {}, {error:?}",
+                    journal.path
+                );
+            }
+            if let Record::Update { .. } = record {
+                journal.tick += 1;
+            }
+        }
+    }
+
+    /// Reapplies journal records to game loaded from the same starting save.
+    ///
+    /// Returns number of replayed records or first record at which
+    /// produced events differ from recorded ones.
+    pub fn replay(&mut self, records: Vec<Record>) -> Result<usize, JournalError> {
+        let mut replayed = 0;
+        for (position, record) in records.into_iter().enumerate() {
+            let (tick, expected, events) = match record {
+                Record::Start { random } => {
+                    self.random = random;
+                    self.timing.load_seed(random.seed);
+                    replayed += 1;
+                    continue;
+                }
+                Record::Accept {
+                    tick,
+                    player,
+                    digest,
+                } => {
+                    let events = self
+                        .accept_player(&player)
+                        .map_err(|error| JournalError::Rejected { position, error })?;
+                    (tick, digest, events)
+                }
+                Record::Perform {
+                    tick,
+                    player,
+                    action,
+                    digest,
+                } => {
+                    let events = self
+                        .perform_action(&player, action)
+                        .map_err(|error| JournalError::Rejected { position, error })?;
+                    (tick, digest, events)
+                }
+                Record::Update { tick, time, digest } => (tick, digest, self.update(time)),
+            };
+            let actual = Journal::digest(&events);
+            if actual != expected {
+                return Err(JournalError::Desync {
+                    position,
+                    tick,
+                    events,
+                });
+            }
+            replayed += 1;
+        }
+        Ok(replayed)
+    }
+}
+
+#[derive(Debug)]
+pub enum JournalError {
+    Io(std::io::Error),
+    Encode(EncodeError),
+    Decode(DecodeError),
+    Rejected {
+        position: usize,
+        error: ActionError,
+    },
+    Desync {
+        position: usize,
+        tick: usize,
+        events: Vec<Event>,
+    },
+}
+
+impl From<std::io::Error> for JournalError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<EncodeError> for JournalError {
+    fn from(error: EncodeError) -> Self {
+        Self::Encode(error)
+    }
+}
+
+impl From<DecodeError> for JournalError {
+    fn from(error: DecodeError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+
+    use crate::api::{Action, FarmerBound};
+    use crate::journal::{fnv1a, Journal, Record};
+    use crate::math::Randomness;
+
+    #[test]
+    fn test_digest_hash_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_records_read_in_append_order() {
+        let path = temp_dir().join(format!("farmisto-{}-test.journal", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = remove_file(path);
+        let mut journal = Journal::create(path).unwrap();
+        let records = [
+            Record::Start {
+                random: Randomness::new(42),
+            },
+            Record::Perform {
+                tick: 0,
+                player: "Alice".to_string(),
+                action: Action::Farmer {
+                    action: FarmerBound::ToggleBackpack,
+                },
+                digest: 1,
+            },
+            Record::Update {
+                tick: 0,
+                time: 0.02,
+                digest: 2,
+            },
+        ];
+        for record in &records {
+            journal.append(record).unwrap();
+        }
+        let restored = Journal::read(path).unwrap();
+        remove_file(path).unwrap();
+        assert_eq!(format!("{records:?}"), format!("{restored:?}"));
+    }
+
+    #[test]
+    fn test_existing_journal_not_overwritten() {
+        let path = temp_dir().join(format!("farmisto-{}-existing.journal", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = remove_file(path);
+        let mut journal = Journal::create(path).unwrap();
+        journal
+            .append(&Record::Start {
+                random: Randomness::new(42),
+            })
+            .unwrap();
+        let result = Journal::create(path);
+        let restored = Journal::read(path).unwrap();
+        remove_file(path).unwrap();
+        assert!(result.is_err());
+        assert_eq!(restored.len(), 1);
+    }
+}
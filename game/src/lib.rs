@@ -1,27 +1,30 @@
 extern crate alloc;
 extern crate core;
 
+use std::collections::{HashMap, HashSet};
+
 use log::{error, info};
 
 use datamap::Storage;
 pub use domains::*;
-pub use rules::*;
+pub use triggers::*;
 pub use update::*;
 
 use crate::api::ActionError::PlayerFarmerNotFound;
-use crate::api::{Action, ActionError, Cheat, Event, FarmerBound};
+use crate::api::{Action, ActionError, Cheat, Entity, Event, FarmerBound};
 use crate::assembling::AssemblingDomain;
 use crate::building::BuildingDomain;
 use crate::inventory::{ContainerId, InventoryDomain};
+use crate::journal::{Journal, Record};
 use crate::landscaping::LandscapingDomain;
-use crate::math::{Position, Tile, TileMath};
+use crate::math::{Position, Randomness, Rotation, Tile, TileMath};
 use crate::model::Activity::Idle;
 use crate::model::PlayerId;
 use crate::model::UniverseDomain;
 use crate::model::{Farmer, Universe};
 use crate::model::{Farmland, Player};
 use crate::model::{Knowledge, TheodoliteKey};
-use crate::physics::{BodyId, PhysicsDomain};
+use crate::physics::{BodyId, PhysicsDomain, SensorId};
 use crate::planting::PlantingDomain;
 use crate::raising::RaisingDomain;
 use crate::timing::TimingDomain;
@@ -35,9 +38,12 @@ mod data;
 mod domains;
 mod inspection;
 mod instantiation;
+pub mod journal;
 pub mod math;
 pub mod model;
+mod querying;
 mod rules;
+mod triggers;
 mod update;
 
 #[macro_export]
@@ -66,6 +72,11 @@ pub struct Game {
     pub raising: RaisingDomain,
     pub assembling: AssemblingDomain,
     pub working: WorkingDomain,
+    pub random: Randomness,
+    pub journal: Option<Journal>,
+    pub quarantine: HashSet<Entity>,
+    update_failures: Vec<UpdateFailure>,
+    sensor_triggers: HashMap<SensorId, SensorTrigger>,
     storage: Storage,
     pub players_id: usize,
     pub players: Vec<Player>,
@@ -73,9 +84,13 @@ pub struct Game {
 
 impl Game {
     pub fn new(storage: Storage) -> Self {
+        let random = Randomness::default();
         Self {
             known: Knowledge::default(),
-            timing: TimingDomain::default(),
+            timing: TimingDomain {
+                seed: random.seed,
+                ..Default::default()
+            },
             universe: UniverseDomain::default(),
             physics: PhysicsDomain::default(),
             planting: PlantingDomain::default(),
@@ -85,6 +100,11 @@ impl Game {
             raising: RaisingDomain::default(),
             assembling: AssemblingDomain::default(),
             working: WorkingDomain::default(),
+            random,
+            journal: None,
+            quarantine: HashSet::default(),
+            update_failures: vec![],
+            sensor_triggers: HashMap::new(),
             storage,
             players_id: 0,
             players: vec![],
@@ -92,6 +112,16 @@ impl Game {
     }
 
     pub fn accept_player(&mut self, player_name: &str) -> Result<Vec<Event>, ActionError> {
+        let events = self.accept_player_internal(player_name)?;
+        self.record(|tick| Record::Accept {
+            tick,
+            player: player_name.to_string(),
+            digest: Journal::digest(&events),
+        });
+        Ok(events)
+    }
+
+    fn accept_player_internal(&mut self, player_name: &str) -> Result<Vec<Event>, ActionError> {
         if !self.players.iter().any(|player| player.name == player_name) {
             if player_name == "<AI>" {
                 info!("Accepts <AI> player");
@@ -181,6 +211,7 @@ impl Game {
             farmland.space,
             kind.barrier.clone(),
             position,
+            Rotation::A000,
             true,
             false,
         )?;
@@ -197,8 +228,20 @@ impl Game {
         action: Action,
     ) -> Result<Vec<Event>, ActionError> {
         let action_debug = format!("{action:?}");
+        let recording = self.journal.as_ref().map(|_| action.clone());
         match self.perform_action_internal(player_name, action) {
-            Ok(events) => Ok(events),
+            Ok(events) => {
+                self.prune_quarantine(&events);
+                if let Some(action) = recording {
+                    self.record(|tick| Record::Perform {
+                        tick,
+                        player: player_name.to_string(),
+                        action,
+                        digest: Journal::digest(&events),
+                    });
+                }
+                Ok(events)
+            }
             Err(error) => {
                 error!("Player {player_name} action {action_debug} error {error:?}");
                 Err(error)
@@ -271,6 +314,9 @@ impl Game {
                 match action {
                     FarmerBound::Move { destination } => self.move_farmer(farmer, destination)?,
                     FarmerBound::TieCreature { creature } => self.tie_creature(farmer, creature)?,
+                    FarmerBound::PushCreature { creature } => {
+                        self.push_creature(farmer, creature)?
+                    }
                     FarmerBound::UntieCreature { creature } => {
                         self.untie_creature(farmer, creature)?
                     }
@@ -331,6 +377,9 @@ impl Game {
                     FarmerBound::UninstallTheodolite { theodolite } => {
                         self.uninstall_theodolite(farmer, farmland, theodolite)?
                     }
+                    FarmerBound::UninstallIrrigator { irrigator } => {
+                        self.uninstall_irrigator(farmer, farmland, irrigator)?
+                    }
                     FarmerBound::Install { tile } => {
                         self.install_equipment(farmer, farmland, tile)?
                     }
@@ -347,9 +396,12 @@ impl Game {
                     FarmerBound::PlantCrop { tile } => self.plant_crop(farmer, farmland, tile)?,
                     FarmerBound::DigUpCrop { crop } => self.dig_up_crop(farmer, farmland, crop)?,
                     FarmerBound::WaterCrop { crop } => self.water_crop(farmer, crop)?,
+                    FarmerBound::TreatCrop { crop } => self.treat_crop(farmer, crop)?,
                     FarmerBound::HarvestCrop { crop } => {
                         self.harvest_crop(farmer, farmland, crop)?
                     }
+                    FarmerBound::PlantTree { tile } => self.plant_tree(farmer, farmland, tile)?,
+                    FarmerBound::ChopTree { tree } => self.chop_tree(farmer, farmland, tree)?,
                     FarmerBound::StartAssembly {
                         pivot: tile,
                         rotation,
@@ -376,6 +428,10 @@ impl Game {
                     }
                     FarmerBound::DigPlace { place } => self.dig_place(farmer, farmland, place)?,
                     FarmerBound::FillBasin { place } => self.fill_basin(farmer, farmland, place)?,
+                    FarmerBound::PloughPlace { place } => {
+                        self.plough_place(farmer, farmland, place)?
+                    }
+                    FarmerBound::PavePlace { place } => self.pave_place(farmer, farmland, place)?,
                     FarmerBound::PourWater { place } => self.pour_water(farmer, farmland, place)?,
                     FarmerBound::Fertilize { tile } => self.fertilize(farmer, farmland, tile)?,
                     FarmerBound::Relax { rest } => self.relax(farmer, farmland, rest)?,
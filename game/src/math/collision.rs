@@ -61,8 +61,8 @@ fn collide_aabb_to_circle(
 #[derive(Debug)]
 pub struct Collision {
     _point: [f32; 2],
-    normal: [f32; 2],
-    penetration: f32,
+    pub normal: [f32; 2],
+    pub penetration: f32,
 }
 
 pub fn collide_circle_to_circle(p1: [f32; 2], r1: f32, p2: [f32; 2], r2: f32) -> Option<Collision> {
@@ -1,11 +1,15 @@
 pub use array::*;
 pub use collision::*;
+pub use pathfinding::*;
 pub use random::*;
 pub use raycast::*;
+pub use rotation::*;
 pub use vector::*;
 
 mod array;
 mod collision;
+mod pathfinding;
 mod random;
 mod raycast;
+mod rotation;
 mod vector;
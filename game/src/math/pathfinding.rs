@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::math::{rasterize_line, Tile};
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+#[derive(PartialEq, Eq)]
+struct Node {
+    tile: Tile,
+    cost: u32,
+    estimation: u32,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed to make binary heap min-heap by estimation
+        other
+            .estimation
+            .cmp(&self.estimation)
+            .then_with(|| other.cost.cmp(&self.cost))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn octile_distance(a: Tile, b: Tile) -> u32 {
+    let dx = a[0].abs_diff(b[0]) as u32;
+    let dy = a[1].abs_diff(b[1]) as u32;
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+fn is_blocked(
+    holes: &[Vec<u8>],
+    filter: &[u8],
+    barriers: &HashSet<Tile>,
+    tile: [isize; 2],
+) -> bool {
+    let [x, y] = tile;
+    if x < 0 || y < 0 || barriers.contains(&[x as usize, y as usize]) {
+        return true;
+    }
+    match holes.get(y as usize).and_then(|row| row.get(x as usize)) {
+        Some(hole) => filter.contains(hole),
+        None => true,
+    }
+}
+
+/// Finds shortest path between tiles using A* over 8-connected grid.
+///
+/// Diagonal steps are allowed only if both adjacent straight tiles are free,
+/// so path never cuts corners of walls. Start tile is never considered blocked.
+/// Tiles occupied by barriers are blocked in addition to filtered holes.
+/// Returns tiles of path excluding start one.
+pub fn find_path(
+    start: Tile,
+    goal: Tile,
+    holes: &[Vec<u8>],
+    filter: &[u8],
+    barriers: &HashSet<Tile>,
+) -> Option<Vec<Tile>> {
+    let goal_offset = [goal[0] as isize, goal[1] as isize];
+    if is_blocked(holes, filter, barriers, goal_offset) {
+        return None;
+    }
+    if start == goal {
+        return Some(vec![]);
+    }
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<Tile, u32> = HashMap::new();
+    let mut came_from: HashMap<Tile, Tile> = HashMap::new();
+    costs.insert(start, 0);
+    open.push(Node {
+        tile: start,
+        cost: 0,
+        estimation: octile_distance(start, goal),
+    });
+    while let Some(Node { tile, cost, .. }) = open.pop() {
+        if tile == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(previous) = came_from.get(&current) {
+                if *previous == start {
+                    break;
+                }
+                path.push(*previous);
+                current = *previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if cost > costs[&tile] {
+            continue;
+        }
+        let [x, y] = [tile[0] as isize, tile[1] as isize];
+        for [dx, dy] in [
+            [1, 0],
+            [-1, 0],
+            [0, 1],
+            [0, -1],
+            [1, 1],
+            [1, -1],
+            [-1, 1],
+            [-1, -1],
+        ] {
+            let neighbour = [x + dx, y + dy];
+            if is_blocked(holes, filter, barriers, neighbour) {
+                continue;
+            }
+            let step = if dx != 0 && dy != 0 {
+                if is_blocked(holes, filter, barriers, [x + dx, y])
+                    || is_blocked(holes, filter, barriers, [x, y + dy])
+                {
+                    continue;
+                }
+                DIAGONAL_COST
+            } else {
+                STRAIGHT_COST
+            };
+            let neighbour = [neighbour[0] as usize, neighbour[1] as usize];
+            let cost = cost + step;
+            if costs
+                .get(&neighbour)
+                .map(|known| cost < *known)
+                .unwrap_or(true)
+            {
+                costs.insert(neighbour, cost);
+                came_from.insert(neighbour, tile);
+                open.push(Node {
+                    tile: neighbour,
+                    cost,
+                    estimation: cost + octile_distance(neighbour, goal),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Tests that straight line between tiles crosses free tiles only.
+pub fn test_line_of_sight(
+    start: Tile,
+    end: Tile,
+    holes: &[Vec<u8>],
+    filter: &[u8],
+    barriers: &HashSet<Tile>,
+) -> bool {
+    let line = rasterize_line(start, end);
+    let mut previous = start;
+    for tile in line {
+        let offset = [tile[0] as isize, tile[1] as isize];
+        if tile != start && is_blocked(holes, filter, barriers, offset) {
+            return false;
+        }
+        // diagonal step of line must not squeeze between two blocked tiles
+        if tile[0] != previous[0] && tile[1] != previous[1] {
+            let a = [tile[0] as isize, previous[1] as isize];
+            let b = [previous[0] as isize, tile[1] as isize];
+            if is_blocked(holes, filter, barriers, a) || is_blocked(holes, filter, barriers, b) {
+                return false;
+            }
+        }
+        previous = tile;
+    }
+    true
+}
+
+/// Removes intermediate tiles of path which can be skipped by walking straight.
+pub fn smooth_path(
+    start: Tile,
+    path: Vec<Tile>,
+    holes: &[Vec<u8>],
+    filter: &[u8],
+    barriers: &HashSet<Tile>,
+) -> Vec<Tile> {
+    let mut waypoints = vec![];
+    let mut anchor = start;
+    let mut index = 0;
+    while index < path.len() {
+        let mut next = index;
+        while next + 1 < path.len()
+            && test_line_of_sight(anchor, path[next + 1], holes, filter, barriers)
+        {
+            next += 1;
+        }
+        anchor = path[next];
+        waypoints.push(anchor);
+        index = next + 1;
+    }
+    waypoints
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::math::{find_path, smooth_path};
+
+    fn parse(map: &[&str]) -> Vec<Vec<u8>> {
+        map.iter()
+            .map(|row| row.chars().map(|c| if c == '#' { 1 } else { 0 }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_path_goes_around_wall() {
+        let holes = parse(&[
+            "......", //
+            "..#...", //
+            "..#...", //
+            "..#...", //
+            "......", //
+        ]);
+        let path = find_path([0, 2], [4, 2], &holes, &[1], &HashSet::new()).unwrap();
+        assert_eq!(path.last(), Some(&[4, 2]));
+        for tile in &path {
+            assert_eq!(holes[tile[1]][tile[0]], 0);
+        }
+        let path = smooth_path([0, 2], path, &holes, &[1], &HashSet::new());
+        assert!(path.len() >= 2);
+        assert_eq!(path.last(), Some(&[4, 2]));
+    }
+
+    #[test]
+    fn test_no_path_to_enclosed_tile() {
+        let holes = parse(&[
+            ".....", //
+            ".###.", //
+            ".#.#.", //
+            ".###.", //
+            ".....", //
+        ]);
+        assert_eq!(
+            find_path([0, 0], [2, 2], &holes, &[1], &HashSet::new()),
+            None
+        );
+        assert_eq!(
+            find_path([0, 0], [1, 1], &holes, &[1], &HashSet::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_corner_cutting() {
+        let holes = parse(&[
+            ".#", //
+            "#.", //
+        ]);
+        assert_eq!(
+            find_path([0, 0], [1, 1], &holes, &[1], &HashSet::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_straight_path_smoothed_to_goal() {
+        let holes = parse(&["......"]);
+        let path = find_path([0, 0], [5, 0], &holes, &[1], &HashSet::new()).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(
+            smooth_path([0, 0], path, &holes, &[1], &HashSet::new()),
+            vec![[5, 0]]
+        );
+    }
+}
@@ -1,21 +1,122 @@
 use nanorand::{Rng, WyRand};
+use serde::{Deserialize, Serialize};
 
+/// WyRand pseudo random generator with explicit state,
+/// so it can be saved and restored to reproduce game session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Random {
-    generator: WyRand,
+    state: u64,
 }
 
 impl Random {
     pub fn new() -> Self {
-        Self {
-            generator: WyRand::new(),
-        }
+        let seed = WyRand::new().generate();
+        Self::with_seed(seed)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0xa0761d6478bd642f);
+        let t = (self.state as u128).wrapping_mul((self.state ^ 0xe7037ed1a0b428db) as u128);
+        (t.wrapping_shr(64) ^ t) as u64
+    }
+
+    /// Creates independent generator seeded from this one.
+    pub fn substream(&mut self) -> Random {
+        Random::with_seed(self.next_u64())
     }
 
     pub fn max(&mut self, max: f32) -> f32 {
-        max * self.generator.generate::<f32>()
+        max * self.generate()
+    }
+
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.generate()
     }
 
+    /// Generates number in range [0.0, 1.0).
     pub fn generate(&mut self) -> f32 {
-        self.generator.generate()
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Random streams of game simulation.
+///
+/// Every domain consumes own stream, so changes in one domain
+/// do not shift random numbers of another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Randomness {
+    pub seed: u64,
+    pub actions: Random,
+    pub working: Random,
+    pub landscaping: Random,
+    pub raising: Random,
+    pub planting: Random,
+}
+
+impl Randomness {
+    pub fn new(seed: u64) -> Self {
+        let mut root = Random::with_seed(seed);
+        Self {
+            seed,
+            actions: root.substream(),
+            working: root.substream(),
+            landscaping: root.substream(),
+            raising: root.substream(),
+            planting: root.substream(),
+        }
+    }
+}
+
+impl Default for Randomness {
+    fn default() -> Self {
+        Self::new(Random::new().next_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{Random, Randomness};
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Random::with_seed(42);
+        let mut b = Random::with_seed(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_restore_from_state() {
+        let mut random = Random::with_seed(7);
+        random.next_u64();
+        let mut restored = Random::with_seed(random.state());
+        assert_eq!(random.next_u64(), restored.next_u64());
+    }
+
+    #[test]
+    fn test_generate_in_unit_range() {
+        let mut random = Random::with_seed(0);
+        for _ in 0..1000 {
+            let value = random.generate();
+            assert!(value >= 0.0 && value < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut a = Randomness::new(1);
+        let mut b = Randomness::new(1);
+        a.working.next_u64();
+        assert_ne!(a.working, b.working);
+        assert_eq!(a.raising.next_u64(), b.raising.next_u64());
     }
 }
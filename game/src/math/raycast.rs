@@ -18,11 +18,17 @@ pub fn cast_ray(
     contacts
 }
 
-pub fn cast_ray2(start: [f32; 2], end: [f32; 2], holes: &Vec<u8>, filter: &[u8]) -> Vec<[f32; 2]> {
+pub fn cast_ray2(
+    start: [f32; 2],
+    end: [f32; 2],
+    holes: &Vec<u8>,
+    width: usize,
+    filter: &[u8],
+) -> Vec<[f32; 2]> {
     let mut contacts = vec![];
     let line = rasterize_line(start.to_tile(), end.to_tile());
     for point in line {
-        let h = holes[point.fit(128)];
+        let h = holes[point.fit(width)];
         if filter.contains(&h) && point.position() != end {
             contacts.push(point.position())
         }
@@ -30,6 +36,57 @@ pub fn cast_ray2(start: [f32; 2], end: [f32; 2], holes: &Vec<u8>, filter: &[u8])
     contacts
 }
 
+/// Returns distance along ray of normalized direction to entry point of axis aligned box.
+pub fn intersect_ray_aabb(
+    start: [f32; 2],
+    direction: [f32; 2],
+    position: [f32; 2],
+    bounds: [f32; 2],
+) -> Option<f32> {
+    let min = position.sub(bounds.mul(0.5));
+    let max = position.add(bounds.mul(0.5));
+    let mut near = f32::NEG_INFINITY;
+    let mut far = f32::INFINITY;
+    for axis in 0..2 {
+        if direction[axis].abs() < f32::EPSILON {
+            if start[axis] < min[axis] || start[axis] > max[axis] {
+                return None;
+            }
+        } else {
+            let a = (min[axis] - start[axis]) / direction[axis];
+            let b = (max[axis] - start[axis]) / direction[axis];
+            near = near.max(a.min(b));
+            far = far.min(a.max(b));
+        }
+    }
+    if near > far || far < 0.0 {
+        None
+    } else {
+        Some(near.max(0.0))
+    }
+}
+
+/// Returns distance along ray of normalized direction to entry point of circle.
+pub fn intersect_ray_circle(
+    start: [f32; 2],
+    direction: [f32; 2],
+    center: [f32; 2],
+    radius: f32,
+) -> Option<f32> {
+    let offset = start.sub(center);
+    let projection = offset.dot(direction);
+    let c = offset.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let discriminant = projection * projection - c;
+    if discriminant < 0.0 || projection > 0.0 {
+        None
+    } else {
+        Some(-projection - discriminant.sqrt())
+    }
+}
+
 pub fn rasterize_line(start: [usize; 2], end: [usize; 2]) -> Vec<[usize; 2]> {
     let [x, y] = start;
     let mut x = x as isize;
@@ -60,7 +117,7 @@ pub fn rasterize_line(start: [usize; 2], end: [usize; 2]) -> Vec<[usize; 2]> {
     }
     let mut longest = w.abs();
     let mut shortest = h.abs();
-    if !(longest > shortest) {
+    if longest <= shortest {
         longest = h.abs();
         shortest = w.abs();
         if h < 0 {
@@ -75,7 +132,7 @@ pub fn rasterize_line(start: [usize; 2], end: [usize; 2]) -> Vec<[usize; 2]> {
     for _ in 0..=longest {
         result.push([x as usize, y as usize]);
         numerator += shortest;
-        if !(numerator < longest) {
+        if numerator >= longest {
             numerator -= longest;
             x += dx1;
             y += dy1;
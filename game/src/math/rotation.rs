@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Rotation {
+    A000,
+    A090,
+    A180,
+    A270,
+}
+
+impl Rotation {
+    pub fn index(&self) -> usize {
+        match self {
+            Rotation::A000 => 0,
+            Rotation::A090 => 1,
+            Rotation::A180 => 2,
+            Rotation::A270 => 3,
+        }
+    }
+
+    pub fn from_index(index: u8) -> Rotation {
+        match index {
+            0 => Rotation::A000,
+            1 => Rotation::A090,
+            2 => Rotation::A180,
+            3 => Rotation::A270,
+            _ => Rotation::A000,
+        }
+    }
+
+    pub fn next(&self) -> Rotation {
+        match self {
+            Rotation::A000 => Rotation::A090,
+            Rotation::A090 => Rotation::A180,
+            Rotation::A180 => Rotation::A270,
+            Rotation::A270 => Rotation::A000,
+        }
+    }
+
+    /// Swaps bounds of object for quarter turns.
+    pub fn apply_bounds(&self, bounds: [f32; 2]) -> [f32; 2] {
+        let [w, h] = bounds;
+        match self {
+            Rotation::A000 | Rotation::A180 => [w, h],
+            Rotation::A090 | Rotation::A270 => [h, w],
+        }
+    }
+
+    pub fn apply_i8(&self, value: [i8; 2]) -> [i8; 2] {
+        let [x, y] = value;
+        match self {
+            Rotation::A000 => [x, y],
+            Rotation::A090 => [y, -x],
+            Rotation::A180 => [-x, -y],
+            Rotation::A270 => [-y, x],
+        }
+    }
+}
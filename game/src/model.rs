@@ -6,15 +6,17 @@ use crate::building::{
 };
 use crate::collections::{Dictionary, Shared};
 use crate::inventory::{ContainerId, ContainerKey, ContainerKind, ItemKey, ItemKind};
-use crate::landscaping::{LandId, LandKey, LandKind};
+use crate::landscaping::{IrrigationId, IrrigationKey, IrrigationKind, LandId, LandKey, LandKind};
 use crate::math::{Position, Tile};
 use crate::physics::{
     BarrierId, BarrierKey, BarrierKind, BodyId, BodyKey, BodyKind, SensorId, SensorKey, SensorKind,
     SpaceId, SpaceKey, SpaceKind,
 };
-use crate::planting::{PlantId, PlantKey, PlantKind, SoilId, SoilKey, SoilKind};
-use crate::raising::{AnimalId, AnimalKey, AnimalKind, Behaviour, TetherId};
-use crate::timing::{CalendarId, CalendarKey, CalendarKind};
+use crate::planting::{
+    PlantId, PlantKey, PlantKind, PlantStage, PlantStress, SoilId, SoilKey, SoilKind,
+};
+use crate::raising::{AnimalId, AnimalKey, AnimalKind, Behaviour, Sex, TetherId};
+use crate::timing::{CalendarId, CalendarKey, CalendarKind, Weather};
 use crate::working::{DeviceId, DeviceKey, DeviceKind};
 
 #[derive(Default)]
@@ -32,6 +34,7 @@ pub struct Knowledge {
     pub composters: Dictionary<ComposterKey, ComposterKind>,
     pub rests: Dictionary<RestKey, RestKind>,
     pub theodolites: Dictionary<TheodoliteKey, TheodoliteKind>,
+    pub irrigators: Dictionary<IrrigatorKey, IrrigatorKind>,
     // timing
     pub calendars: Dictionary<CalendarKey, CalendarKind>,
     // physics
@@ -54,6 +57,7 @@ pub struct Knowledge {
     pub devices: Dictionary<DeviceKey, DeviceKind>,
     // landscaping
     pub lands: Dictionary<LandKey, LandKind>,
+    pub irrigation: Dictionary<IrrigationKey, IrrigationKind>,
 }
 
 #[derive(Default)]
@@ -70,6 +74,8 @@ pub struct UniverseDomain {
     pub constructions_id: usize,
     pub theodolites: Vec<Theodolite>,
     pub theodolites_id: usize,
+    pub irrigators: Vec<Irrigator>,
+    pub irrigators_id: usize,
     pub stacks: Vec<Stack>,
     pub stacks_id: usize,
     pub equipments: Vec<Equipment>,
@@ -114,6 +120,8 @@ pub enum Universe {
         season: u8,
         season_day: f32,
         times_of_day: f32,
+        weather: Weather,
+        forecast: Vec<Weather>,
     },
     FarmlandVanished(Farmland),
     FarmerAppeared {
@@ -135,6 +143,9 @@ pub enum Universe {
         growth: f32,
         health: f32,
         fruits: f32,
+        stage: PlantStage,
+        stress: PlantStress,
+        infection: f32,
         position: Position,
     },
     CropVanished(Crop),
@@ -145,6 +156,8 @@ pub enum Universe {
         hunger: f32,
         age: f32,
         weight: f32,
+        sex: Sex,
+        pregnancy: Option<f32>,
         position: Position,
         behaviour: Behaviour,
     },
@@ -170,6 +183,12 @@ pub enum Universe {
     TheodoliteVanished {
         id: Theodolite,
     },
+    IrrigatorAppeared {
+        entity: Irrigator,
+        place: Tile,
+        pressure: f32,
+    },
+    IrrigatorVanished(Irrigator),
     EquipmentAppeared {
         entity: Equipment,
         position: Position,
@@ -233,6 +252,9 @@ pub enum UniverseError {
     CreatureByAnimalNotFound {
         animal: AnimalId,
     },
+    CropBySensorNotFound {
+        sensor: SensorId,
+    },
     FarmlandBySpaceNotFound {
         space: SpaceId,
     },
@@ -256,6 +278,9 @@ pub enum UniverseError {
     FarmerNotFound {
         container: ContainerId,
     },
+    PlayerNotFound {
+        player: PlayerId,
+    },
 }
 
 impl UniverseDomain {
@@ -269,6 +294,11 @@ impl UniverseDomain {
         self.theodolites.extend(theodolites);
     }
 
+    pub fn load_irrigators(&mut self, irrigators: Vec<Irrigator>, irrigators_id: usize) {
+        self.irrigators_id = irrigators_id;
+        self.irrigators.extend(irrigators);
+    }
+
     pub fn get_stack_by(&self, container: ContainerId) -> Result<Stack, UniverseError> {
         self.stacks
             .iter()
@@ -301,6 +331,14 @@ impl UniverseDomain {
             .ok_or(UniverseError::FarmlandBySpaceNotFound { space })
     }
 
+    pub fn get_crop_by_sensor(&self, sensor: SensorId) -> Result<Crop, UniverseError> {
+        self.crops
+            .iter()
+            .find(|crop| crop.sensor == sensor)
+            .cloned()
+            .ok_or(UniverseError::CropBySensorNotFound { sensor })
+    }
+
     pub fn get_creature_by_animal(&self, animal: AnimalId) -> Result<Creature, UniverseError> {
         self.creatures
             .iter()
@@ -430,6 +468,15 @@ impl UniverseDomain {
         }
     }
 
+    pub(crate) fn vanish_tree(&mut self, id: Tree) -> Vec<Universe> {
+        if let Some(index) = self.trees.iter().position(|tree| tree == &id) {
+            self.trees.remove(index);
+            vec![Universe::TreeVanished(id)]
+        } else {
+            vec![]
+        }
+    }
+
     pub(crate) fn vanish_crop(&mut self, id: Crop) -> Vec<Universe> {
         if let Some(index) = self.crops.iter().position(|crop| crop == &id) {
             self.crops.remove(index);
@@ -483,6 +530,19 @@ impl UniverseDomain {
         }
     }
 
+    pub(crate) fn vanish_irrigator(&mut self, id: Irrigator) -> Vec<Universe> {
+        if let Some(index) = self
+            .irrigators
+            .iter()
+            .position(|irrigator| irrigator == &id)
+        {
+            self.irrigators.remove(index);
+            vec![Universe::IrrigatorVanished(id)]
+        } else {
+            vec![]
+        }
+    }
+
     pub(crate) fn vanish_theodolite(&mut self, id: Theodolite) -> Vec<Universe> {
         if let Some(index) = self
             .theodolites
@@ -595,6 +655,26 @@ pub struct TreeKind {
     pub name: String,
     pub barrier: Shared<BarrierKind>,
     pub plant: Shared<PlantKind>,
+    /// Dropped on chopping, expected to be building material like wood or planks.
+    pub wood: Shared<ItemKind>,
+    /// Dropped on chopping of mature tree.
+    pub sapling: Option<Shared<ItemKind>>,
+}
+
+impl TreeKind {
+    /// Number of saplings dropped by mature tree.
+    pub const SAPLINGS: u8 = 2;
+
+    /// Tree is mature when it reaches flowering stage.
+    pub fn get_maturity(&self, growth: f32) -> f32 {
+        let [_, flowering, ..] = self.plant.stages;
+        (growth / flowering).min(1.0)
+    }
+
+    pub fn get_wood_quantity(&self, growth: f32) -> u8 {
+        let quantity = self.wood.max_quantity as f32 * self.get_maturity(growth);
+        (quantity.ceil() as u8).max(1)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -684,6 +764,8 @@ pub struct TheodoliteKind {
     pub surveyor: Shared<SurveyorKind>,
     pub barrier: Shared<BarrierKind>,
     pub item: Shared<ItemKind>,
+    /// Distance at which farmer can operate theodolite.
+    pub reach: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -694,6 +776,23 @@ pub struct Theodolite {
     pub barrier: BarrierId,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct IrrigatorKey(pub usize);
+
+pub struct IrrigatorKind {
+    pub id: IrrigatorKey,
+    pub name: String,
+    pub irrigation: Shared<IrrigationKind>,
+    pub item: Shared<ItemKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Irrigator {
+    pub id: usize,
+    pub key: IrrigatorKey,
+    pub irrigation: IrrigationId,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Stack {
     pub id: usize,
@@ -712,6 +811,8 @@ pub struct CropKind {
     pub sensor: Shared<SensorKind>,
     pub fruits: Shared<ItemKind>,
     pub residue: Shared<ItemKind>,
+    /// Dropped on harvest of overripe crop.
+    pub seeds: Option<Shared<ItemKind>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -732,6 +833,8 @@ pub struct CreatureKind {
     pub body: Shared<BodyKind>,
     pub animal: Shared<AnimalKind>,
     pub corpse: Shared<CorpseKind>,
+    /// Whether creature pollinates flowering crops it stays near.
+    pub pollinator: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,304 @@
+use crate::api::{
+    ActionError, AnimalView, ContainerView, CropView, Entity, EntityView, Query, View,
+};
+use crate::inventory::{ItemData, ItemKey};
+use crate::math::{Position, TileMath};
+use crate::physics::SpaceId;
+use crate::planting::PlantStage;
+use crate::Game;
+
+impl Game {
+    pub fn perform_query(&self, query: Query) -> Result<View, ActionError> {
+        let view = match query {
+            Query::EntitiesWithin { space, min, max } => {
+                self.physics.get_space(space)?;
+                View::Entities(self.query_entities_within(space, min, max))
+            }
+            Query::CropsByGrowth { stage } => View::Crops(self.query_crops_by_growth(stage)),
+            Query::ContainersHolding { item } => {
+                self.known.items.get(item)?;
+                View::Containers(self.query_containers_holding(item))
+            }
+            Query::AnimalsByHealth { min, max } => {
+                View::Animals(self.query_animals_by_health(min, max))
+            }
+        };
+        Ok(view)
+    }
+
+    /// Entities with broken body, barrier or irrigation are skipped,
+    /// so they never fail the whole query.
+    pub fn query_entities_within(
+        &self,
+        space: SpaceId,
+        min: Position,
+        max: Position,
+    ) -> Vec<EntityView> {
+        let universe = &self.universe;
+        let mut bodies = vec![];
+        for farmer in &universe.farmers {
+            bodies.push((Entity::Farmer(*farmer), farmer.body));
+        }
+        for creature in &universe.creatures {
+            bodies.push((Entity::Creature(*creature), creature.body));
+        }
+        let mut barriers = vec![];
+        for tree in &universe.trees {
+            barriers.push((Entity::Tree(*tree), tree.barrier));
+        }
+        for crop in &universe.crops {
+            barriers.push((Entity::Crop(*crop), crop.barrier));
+        }
+        for corpse in &universe.corpses {
+            barriers.push((Entity::Corpse(*corpse), corpse.barrier));
+        }
+        for stack in &universe.stacks {
+            barriers.push((Entity::Stack(*stack), stack.barrier));
+        }
+        for equipment in &universe.equipments {
+            barriers.push((Entity::Equipment(*equipment), equipment.barrier));
+        }
+        for theodolite in &universe.theodolites {
+            barriers.push((Entity::Theodolite(*theodolite), theodolite.barrier));
+        }
+        for door in &universe.doors {
+            barriers.push((Entity::Door(*door), door.barrier));
+        }
+        for rest in &universe.rests {
+            barriers.push((Entity::Rest(*rest), rest.barrier));
+        }
+        for cementer in &universe.cementers {
+            barriers.push((Entity::Cementer(*cementer), cementer.barrier));
+        }
+        for composter in &universe.composters {
+            barriers.push((Entity::Composter(*composter), composter.barrier));
+        }
+
+        let within = |position: Position| {
+            position[0] >= min[0]
+                && position[0] <= max[0]
+                && position[1] >= min[1]
+                && position[1] <= max[1]
+        };
+        let mut views = vec![];
+        for (entity, body) in bodies {
+            let body = match self.physics.get_body(body) {
+                Ok(body) => body,
+                Err(_) => continue,
+            };
+            if body.space == space && within(body.position) {
+                views.push(EntityView {
+                    entity,
+                    position: body.position,
+                });
+            }
+        }
+        for (entity, barrier) in barriers {
+            let barrier = match self.physics.get_barrier(barrier) {
+                Ok(barrier) => barrier,
+                Err(_) => continue,
+            };
+            if barrier.space == space && within(barrier.position) {
+                views.push(EntityView {
+                    entity,
+                    position: barrier.position,
+                });
+            }
+        }
+        // irrigators have no barrier, they are placed on land of farmland
+        let land = universe
+            .farmlands
+            .iter()
+            .find(|farmland| farmland.space == space)
+            .map(|farmland| farmland.land);
+        for irrigator in &universe.irrigators {
+            let irrigation = match self.landscaping.get_irrigation(irrigator.irrigation) {
+                Ok(irrigation) => irrigation,
+                Err(_) => continue,
+            };
+            let position = irrigation.place.position();
+            if Some(irrigation.land) == land && within(position) {
+                views.push(EntityView {
+                    entity: Entity::Irrigator(*irrigator),
+                    position,
+                });
+            }
+        }
+        views
+    }
+
+    pub fn query_crops_by_growth(&self, stage: PlantStage) -> Vec<CropView> {
+        let mut views = vec![];
+        for crop in &self.universe.crops {
+            let plant = match self.planting.get_plant(crop.plant) {
+                Ok(plant) => plant,
+                Err(_) => continue,
+            };
+            if plant.stage != stage {
+                continue;
+            }
+            let barrier = match self.physics.get_barrier(crop.barrier) {
+                Ok(barrier) => barrier,
+                Err(_) => continue,
+            };
+            views.push(CropView {
+                entity: *crop,
+                position: barrier.position,
+                growth: plant.growth,
+                health: plant.health,
+                thirst: plant.thirst,
+                hunger: plant.hunger,
+                fruits: plant.fruits,
+            });
+        }
+        views
+    }
+
+    pub fn query_containers_holding(&self, item: ItemKey) -> Vec<ContainerView> {
+        let mut views = vec![];
+        for container in self.inventory.containers.values() {
+            if !container.items.iter().any(|other| other.kind.id == item) {
+                continue;
+            }
+            let items = container
+                .items
+                .iter()
+                .map(|item| ItemData {
+                    id: item.id,
+                    key: item.kind.id,
+                    container: item.container,
+                    quantity: item.quantity,
+                    quality: item.quality,
+                })
+                .collect();
+            views.push(ContainerView {
+                id: container.id,
+                items,
+            });
+        }
+        views
+    }
+
+    pub fn query_animals_by_health(&self, min: f32, max: f32) -> Vec<AnimalView> {
+        let mut views = vec![];
+        for creature in &self.universe.creatures {
+            let animal = match self.raising.get_animal(creature.animal) {
+                Ok(animal) => animal,
+                Err(_) => continue,
+            };
+            if animal.health < min || animal.health > max {
+                continue;
+            }
+            let body = match self.physics.get_body(creature.body) {
+                Ok(body) => body,
+                Err(_) => continue,
+            };
+            views.push(AnimalView {
+                entity: *creature,
+                position: body.position,
+                health: animal.health,
+                hunger: animal.hunger,
+                thirst: animal.thirst,
+                age: animal.age,
+                weight: animal.weight,
+            });
+        }
+        views
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::tests::{plant_crop, prepare_game};
+    use crate::api::{Entity, Query};
+    use crate::collections::Shared;
+    use crate::landscaping::{IrrigationKey, IrrigationKind, IrrigationPart};
+    use crate::model::{Creature, CreatureKey, Crop, CropKey, IrrigatorKey};
+    use crate::physics::{BarrierId, BodyId, SensorId, SpaceId};
+    use crate::planting::{PlantId, PlantStage};
+    use crate::raising::{AnimalId, Sex};
+    use crate::update::tests::{prepare_creatures, spawn_creature};
+
+    #[test]
+    fn test_broken_entity_skipped_within_area() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.universe.crops.push(Crop {
+            id: 2,
+            key: CropKey(1),
+            plant: PlantId(42),
+            barrier: BarrierId(42),
+            sensor: SensorId(42),
+        });
+
+        let views = game.query_entities_within(farmland.space, [0.0, 0.0], [3.0, 3.0]);
+
+        let entities: Vec<Entity> = views.iter().map(|view| view.entity).collect();
+        assert_eq!(entities, vec![Entity::Farmer(farmer), Entity::Crop(crop)]);
+    }
+
+    #[test]
+    fn test_crops_queried_by_stage() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        plant_crop(&mut game, farmland, [2.5, 1.5]);
+        let ripe = plant_crop(&mut game, farmland, [4.5, 1.5]);
+        game.planting.get_plant_mut(ripe.plant).unwrap().stage = PlantStage::Fruiting;
+
+        let views = game.query_crops_by_growth(PlantStage::Fruiting);
+
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].entity.plant, ripe.plant);
+    }
+
+    #[test]
+    fn test_broken_animal_skipped_by_health() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        prepare_creatures(&mut game);
+        let creature = spawn_creature(&mut game, farmland, Sex::Female);
+        game.universe.creatures.push(Creature {
+            id: 42,
+            key: CreatureKey(1),
+            body: BodyId(42),
+            animal: AnimalId(42),
+        });
+
+        let views = game.query_animals_by_health(0.0, 1.0);
+
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].entity, creature);
+    }
+
+    #[test]
+    fn test_irrigator_queried_within_area() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let kind = Shared::new(IrrigationKind {
+            id: IrrigationKey(1),
+            name: "pipe".to_string(),
+            part: IrrigationPart::Pipe,
+        });
+        let (irrigation, create_irrigation) = game
+            .landscaping
+            .create_irrigation(farmland.land, [2, 1], &kind)
+            .unwrap();
+        create_irrigation();
+        game.appear_irrigator(IrrigatorKey(1), irrigation).unwrap();
+        let irrigator = game.universe.irrigators[0];
+
+        let views = game.query_entities_within(farmland.space, [2.0, 1.0], [3.0, 2.0]);
+
+        let entities: Vec<Entity> = views.iter().map(|view| view.entity).collect();
+        assert_eq!(entities, vec![Entity::Irrigator(irrigator)]);
+    }
+
+    #[test]
+    fn test_query_of_missing_space_fails() {
+        let (game, _farmer, _farmland) = prepare_game(vec![]);
+        let query = Query::EntitiesWithin {
+            space: SpaceId(42),
+            min: [0.0, 0.0],
+            max: [3.0, 3.0],
+        };
+
+        assert!(game.perform_query(query).is_err());
+    }
+}
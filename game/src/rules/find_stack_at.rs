@@ -0,0 +1,37 @@
+use crate::api::ActionError;
+use crate::math::{Position, VectorMath};
+use crate::model::Stack;
+use crate::physics::{BarrierId, SpaceId};
+use crate::Game;
+
+impl Game {
+    /// Finds stack lying on tile of position, items dropped there are put into it.
+    pub(crate) fn find_stack_at(&self, space: SpaceId, position: Position) -> Option<Stack> {
+        let tile = position.to_tile();
+        self.universe.stacks.iter().copied().find(|stack| {
+            self.physics
+                .get_barrier(stack.barrier)
+                .map(|barrier| barrier.space == space && barrier.position.to_tile() == tile)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Ensures that items can be dropped at position by [Game::drop_items].
+    ///
+    /// New drop may overlap only given barrier, which is expected to be destroyed
+    /// or to stay under drop by the same operation.
+    pub(crate) fn ensure_items_droppable(
+        &self,
+        space: SpaceId,
+        position: Position,
+        except: BarrierId,
+    ) -> Result<(), ActionError> {
+        if self.find_stack_at(space, position).is_none() {
+            self.physics
+                .ensure_no_overlap_except(space, position, except)?;
+            self.known.barriers.find("<drop>")?;
+            self.known.containers.find("<drop>")?;
+        }
+        Ok(())
+    }
+}
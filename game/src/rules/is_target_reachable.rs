@@ -1,18 +1,30 @@
 use crate::api::ActionError;
 use crate::math::{Position, VectorMath};
 
-use crate::physics::{BodyId, SpaceId};
+use crate::physics::{BodyId, RaycastFilter, RaycastTarget, SpaceId};
 use crate::Game;
 
+/// Distance at which actions without own reach interact with targets.
+const INTERACTION_RANGE: f32 = 2.0;
+
 impl Game {
     pub fn ensure_target_reachable(
         &self,
         body: BodyId,
         target: Position,
+    ) -> Result<(), ActionError> {
+        self.ensure_target_within_reach(body, target, INTERACTION_RANGE)
+    }
+
+    pub fn ensure_target_within_reach(
+        &self,
+        body: BodyId,
+        target: Position,
+        reach: f32,
     ) -> Result<(), ActionError> {
         let body = self.physics.get_body(body)?;
         let actor = body.position;
-        if self.is_target_reachable(body.space, actor, target)? {
+        if self.is_target_reachable(body.space, actor, target, reach)? {
             Ok(())
         } else {
             Err(ActionError::TargetUnreachable)
@@ -24,12 +36,30 @@ impl Game {
         space: SpaceId,
         actor: Position,
         target: Position,
+        reach: f32,
     ) -> Result<bool, ActionError> {
-        if actor.distance(target) > 2.0 {
-            Ok(false)
-        } else {
-            let contacts = self.physics.cast_ray(space, actor, target, &[1])?;
-            Ok(contacts.is_empty())
+        if actor.distance(target) > reach {
+            return Ok(false);
         }
+        let filter = RaycastFilter {
+            holes: vec![1],
+            barriers: true,
+            bodies: false,
+        };
+        let hits = self.physics.raycast(space, actor, target, reach, &filter)?;
+        // target itself may be barrier or wall, only obstacles in between matter
+        let blocked = hits.iter().any(|hit| match hit.target {
+            // items lying on ground never block reach
+            RaycastTarget::Barrier(barrier) => {
+                let is_stack = self
+                    .universe
+                    .stacks
+                    .iter()
+                    .any(|stack| stack.barrier == barrier);
+                !is_stack && !hit.covers(target)
+            }
+            _ => !hit.covers(target),
+        });
+        Ok(!blocked)
     }
 }
@@ -1,3 +1,4 @@
+mod find_stack_at;
 mod is_placement_valid;
 mod is_target_reachable;
 mod is_tile_empty;
@@ -0,0 +1,115 @@
+use crate::api::{ActionError, Event};
+use crate::math::VectorMath;
+use crate::physics::{BodyId, Physics, SensorId};
+use crate::{Game, Subsystem};
+
+/// Reaction of universe to body entering or leaving sensor area.
+pub type SensorHandler = fn(&mut Game, SensorId, BodyId) -> Result<Vec<Event>, ActionError>;
+
+#[derive(Clone, Copy, Default)]
+pub struct SensorTrigger {
+    pub on_enter: Option<SensorHandler>,
+    pub on_exit: Option<SensorHandler>,
+}
+
+impl SensorTrigger {
+    /// Crop is trampled by every body stepping on it.
+    pub const CROP: SensorTrigger = SensorTrigger {
+        on_enter: Some(Game::trample_crop),
+        on_exit: None,
+    };
+}
+
+impl Game {
+    pub fn subscribe_sensor(&mut self, sensor: SensorId, trigger: SensorTrigger) {
+        self.sensor_triggers.insert(sensor, trigger);
+    }
+
+    pub fn unsubscribe_sensor(&mut self, sensor: SensorId) {
+        self.sensor_triggers.remove(&sensor);
+    }
+
+    pub(crate) fn dispatch_sensor_events(&mut self, events: &[Physics]) -> Vec<Event> {
+        let mut trigger_events = vec![];
+        for event in events {
+            let (sensor, body, entered) = match *event {
+                Physics::SensorEntered { sensor, body } => (sensor, body, true),
+                Physics::SensorExited { sensor, body } => (sensor, body, false),
+                _ => continue,
+            };
+            let trigger = match self.sensor_triggers.get(&sensor) {
+                Some(trigger) => *trigger,
+                None => continue,
+            };
+            let handler = if entered {
+                trigger.on_enter
+            } else {
+                trigger.on_exit
+            };
+            if let Some(handler) = handler {
+                match handler(self, sensor, body) {
+                    Ok(events) => trigger_events.extend(events),
+                    Err(error) => self.report_failure(Subsystem::Sensors, None, error),
+                }
+            }
+        }
+        trigger_events
+    }
+
+    fn trample_crop(&mut self, sensor: SensorId, body: BodyId) -> Result<Vec<Event>, ActionError> {
+        let crop = self.universe.get_crop_by_sensor(sensor)?;
+        let sensor = self.physics.get_sensor(sensor)?;
+        let body = self.physics.get_body(body)?;
+        let impact = body.position.sub(sensor.position).normalize().neg();
+        self.planting.integrate_impact(crop.plant, impact[0])?;
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::tests::{plant_crop, prepare_game};
+    use crate::physics::{BodyId, Physics};
+    use crate::{SensorTrigger, Subsystem};
+
+    #[test]
+    fn test_subscribed_crop_trampled_by_entered_body() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        let entered = [Physics::SensorEntered {
+            sensor: crop.sensor,
+            body: farmer.body,
+        }];
+
+        game.dispatch_sensor_events(&entered);
+        assert_eq!(game.planting.get_plant(crop.plant).unwrap().impact, 0.0);
+
+        game.subscribe_sensor(crop.sensor, SensorTrigger::CROP);
+        game.dispatch_sensor_events(&entered);
+        assert_eq!(game.planting.get_plant(crop.plant).unwrap().impact, 1.0);
+
+        let exited = [Physics::SensorExited {
+            sensor: crop.sensor,
+            body: farmer.body,
+        }];
+        game.dispatch_sensor_events(&exited);
+        assert_eq!(game.planting.get_plant(crop.plant).unwrap().impact, 1.0);
+        assert!(game.take_update_failures().is_empty());
+    }
+
+    #[test]
+    fn test_trigger_failure_reported() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.subscribe_sensor(crop.sensor, SensorTrigger::CROP);
+
+        game.dispatch_sensor_events(&[Physics::SensorEntered {
+            sensor: crop.sensor,
+            body: BodyId(42),
+        }]);
+
+        let failures = game.take_update_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].subsystem, Subsystem::Sensors);
+    }
+}
@@ -1,22 +1,111 @@
-use rand::thread_rng;
+use core::mem::take;
+use std::collections::HashSet;
 
-use crate::api::Event;
-use crate::inventory::ItemId;
-use crate::math::{ArrayIndex, Random, TileMath, VectorMath};
-use crate::model::Activity;
+use log::error;
+
+use crate::api::{ActionError, Entity, Event};
+use crate::building::Material;
+use crate::collections::Shared;
+use crate::inventory::{ContainerId, Item, ItemId, ItemKind};
+use crate::journal::{Journal, Record};
+use crate::math::{Rotation, Tile, VectorMath};
+use crate::model::{
+    Activity, Cementer, Composter, Creature, Crop, Farmer, Farmland, Stack, Tree, Universe,
+};
+use crate::physics::BodyId;
+use crate::planting::{PlantId, PlantStage};
+use crate::raising::{Animal, AnimalId, Sex};
+use crate::working::DeviceId;
 use crate::{occur, Game};
 
+/// Temperature rise inside enclosed room relative to outside.
+const ROOM_WARMING: f32 = 5.0;
+
+/// Glass room keeps more heat and lets plants grow in any season.
+const GREENHOUSE_WARMING: f32 = 15.0;
+
+/// Distance between creatures at which they can mate.
+const MATING_DISTANCE: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    Players,
+    Farmlands,
+    Farmers,
+    Stacks,
+    Trees,
+    Crops,
+    Cementers,
+    Composters,
+    DeadAnimals,
+    Creatures,
+    Births,
+    Sensors,
+    Inspection,
+}
+
+impl Subsystem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Players => "players",
+            Subsystem::Farmlands => "farmlands",
+            Subsystem::Farmers => "farmers",
+            Subsystem::Stacks => "stacks",
+            Subsystem::Trees => "trees",
+            Subsystem::Crops => "crops",
+            Subsystem::Cementers => "cementers",
+            Subsystem::Composters => "composters",
+            Subsystem::DeadAnimals => "dead_animals",
+            Subsystem::Creatures => "creatures",
+            Subsystem::Births => "births",
+            Subsystem::Sensors => "sensors",
+            Subsystem::Inspection => "inspection",
+        }
+    }
+}
+
+/// Error of single entity processing during game update.
+///
+/// Entity is quarantined (excluded from further updates) to keep the rest of the world running.
+#[derive(Debug)]
+pub struct UpdateFailure {
+    pub subsystem: Subsystem,
+    pub entity: Option<Entity>,
+    pub error: ActionError,
+}
+
+impl UpdateFailure {
+    pub fn kind(&self) -> &'static str {
+        match self.error {
+            ActionError::Timing(_) => "timing",
+            ActionError::Building(_) => "building",
+            ActionError::Inventory(_) => "inventory",
+            ActionError::Universe(_) => "universe",
+            ActionError::Physics(_) => "physics",
+            ActionError::Planting(_) => "planting",
+            ActionError::Raising(_) => "raising",
+            ActionError::Assembling(_) => "assembling",
+            ActionError::Working(_) => "working",
+            ActionError::Landscaping(_) => "landscaping",
+            ActionError::Inconsistency(_) => "inconsistency",
+            _ => "other",
+        }
+    }
+}
+
 impl Game {
     pub fn update(&mut self, real_seconds: f32) -> Vec<Event> {
         let mut boosts = vec![];
-        for player in self.players.iter() {
-            let farmer = self.universe.get_player_farmer(player.id).unwrap();
-            let activity = self.universe.get_farmer_activity(farmer).unwrap();
-            let boost = match activity {
-                Activity::Resting { comfort } => comfort,
-                _ => 1,
-            };
-            boosts.push(boost);
+        for farmer in self.universe.farmers.clone() {
+            let entity = Entity::Farmer(farmer);
+            let is_player = self.players.iter().any(|player| player.id == farmer.player);
+            if !is_player || self.quarantine.contains(&entity) {
+                continue;
+            }
+            match self.get_farmer_boost(farmer) {
+                Ok(boost) => boosts.push(boost),
+                Err(error) => self.quarantine(Subsystem::Players, entity, error),
+            }
         }
         let game_speed = boosts.iter().max().cloned().unwrap_or(1) as f32;
         let physics_time = real_seconds * game_speed;
@@ -24,193 +113,946 @@ impl Game {
 
         let timing_events = self.timing.update(time, game_speed);
         let physics_events = self.physics.update(physics_time);
+        let trigger_events = self.dispatch_sensor_events(&physics_events);
 
-        // Change farmer activity after item usage
-        // HACK: to eliminate boilerplate code from actions before new activity system will created
-        let mut to_change = vec![];
-        for farmer in self.universe.farmers.iter() {
-            if self.universe.get_farmer_activity(*farmer).unwrap() == Activity::Usage {
-                let hands = self.inventory.get_container(farmer.hands).unwrap();
-                if hands.items.len() == 0 {
-                    to_change.push((*farmer, Activity::Idle));
-                }
+        for farmland in self.universe.farmlands.clone() {
+            let entity = Entity::Farmland(farmland);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            if let Err(error) = self.integrate_weather(farmland) {
+                self.quarantine(Subsystem::Farmlands, entity, error);
             }
         }
+
+        // Change farmer activity after item usage
+        // HACK: to eliminate boilerplate code from actions before new activity system will created
         let mut activity_events = vec![];
-        for (farmer, activity) in to_change {
-            let event = self.universe.change_activity(farmer, activity);
-            activity_events.push(event);
+        for farmer in self.universe.farmers.clone() {
+            let entity = Entity::Farmer(farmer);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            match self.update_farmer_activity(farmer) {
+                Ok(events) => activity_events.extend(events),
+                Err(error) => self.quarantine(Subsystem::Farmers, entity, error),
+            }
         }
 
         let mut destroy_empty_stacks = vec![];
         for stack in self.universe.stacks.clone() {
-            let container = self.inventory.get_container(stack.container).unwrap();
-            if container.items.is_empty() {
-                let destroy_container = self
-                    .inventory
-                    .destroy_containers(vec![stack.container], false)
-                    .unwrap();
-                let destroy_barrier = self.physics.destroy_barrier(stack.barrier).unwrap();
-                destroy_empty_stacks.extend([
-                    destroy_container().into(),
-                    destroy_barrier().into(),
-                    self.universe.vanish_stack(stack).into(),
-                ])
+            let entity = Entity::Stack(stack);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            match self.destroy_empty_stack(stack) {
+                Ok(events) => destroy_empty_stacks.extend(events),
+                Err(error) => self.quarantine(Subsystem::Stacks, entity, error),
+            }
+        }
+
+        for tree in self.universe.trees.clone() {
+            let entity = Entity::Tree(tree);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            if let Err(error) = self.integrate_tree(tree, physics_time) {
+                self.quarantine(Subsystem::Trees, entity, error);
             }
         }
 
+        let pollinators = self.get_pollinators();
         // TODO: optimize by farmland
-        for crop in &self.universe.crops {
-            let sensor = self.physics.get_sensor(crop.sensor).unwrap();
-            let farmland = self.universe.get_farmland_by_space(sensor.space).unwrap();
-            let farmland_kind = self.known.farmlands.get(farmland.kind).unwrap();
-            let place = sensor.position.to_tile().fit(farmland_kind.land.width);
-            let plant = self.planting.get_plant(crop.plant).unwrap();
-            let plant_transpiration = plant.kind.transpiration;
-
-            let mut impact = [0.0, 0.0];
-            for signal in &sensor.signals {
-                impact = impact.add(*signal);
-            }
-            impact = impact.normalize().neg();
-
-            let consumption = plant_transpiration * physics_time;
-            let consumed = self
-                .landscaping
-                .request_consumption(farmland.land, place, consumption)
-                .unwrap();
-            let lack = consumption - consumed;
-            self.planting
-                .integrate_thirst(crop.plant, lack, consumption)
-                .unwrap();
-
-            let consumption = (1.0 / (360.0 * 3.0)) * physics_time;
-            let consumed = self
-                .planting
-                .request_fertility_consumption(farmland.soil, place, consumption)
-                .unwrap();
-            let lack = consumption - consumed;
-            self.planting
-                .integrate_hunger(crop.plant, lack, consumption)
-                .unwrap();
-
-            self.planting
-                .integrate_impact(crop.plant, impact[0])
-                .unwrap();
+        for crop in self.universe.crops.clone() {
+            let entity = Entity::Crop(crop);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            if let Err(error) = self.integrate_crop(crop, &pollinators, physics_time) {
+                self.quarantine(Subsystem::Crops, entity, error);
+            }
         }
 
         let mut cementer_events = vec![];
-        for cementer in &self.universe.cementers {
-            // TODO: transactional with working
-            // TODO: errors, unwraps
-            let has_input = !self
-                .inventory
-                .get_container(cementer.input)
-                .unwrap()
-                .items
-                .is_empty();
-            if has_input {
-                let consumed = self.working.consume_input(cementer.device).unwrap();
-                if consumed {
-                    let use_items = self.inventory.pop_use_item(cementer.input).unwrap();
-                    cementer_events.push(use_items().into());
-                }
+        for cementer in self.universe.cementers.clone() {
+            let entity = Entity::Cementer(cementer);
+            if self.quarantine.contains(&entity) {
+                continue;
             }
-            let output = self.inventory.get_container(cementer.output).unwrap();
-            let can_output = output.items.len() < output.kind.capacity;
-            if can_output {
-                let produced = self.working.produce_output(cementer.device).unwrap();
-                if produced {
-                    let cementer_kind = self.known.cementers.get(cementer.key).unwrap();
-                    let item = self.inventory.items_id.introduce().one(ItemId);
-                    let create_item = self
-                        .inventory
-                        .create_item(item, &cementer_kind.cement, cementer.output, 1)
-                        .unwrap();
-                    cementer_events.push(create_item().into())
-                }
+            match self.process_cementer(cementer) {
+                Ok(events) => cementer_events.extend(events),
+                Err(error) => self.quarantine(Subsystem::Cementers, entity, error),
             }
         }
 
         let mut composter_events = vec![];
-        for composter in &self.universe.composters {
-            // TODO: transactional with working
-            // TODO: errors, unwraps
-            // TODO: generalize with cementer ?
-            let has_input = !self
-                .inventory
-                .get_container(composter.input)
-                .unwrap()
-                .items
-                .is_empty();
-            if has_input {
-                let consumed = self.working.consume_input(composter.device).unwrap();
-                if consumed {
-                    let use_items = self.inventory.pop_use_item(composter.input).unwrap();
-                    composter_events.push(use_items().into());
-                }
+        for composter in self.universe.composters.clone() {
+            let entity = Entity::Composter(composter);
+            if self.quarantine.contains(&entity) {
+                continue;
             }
-            let output = self.inventory.get_container(composter.output).unwrap();
-            let can_output = output.items.len() < output.kind.capacity;
-            if can_output {
-                let produced = self.working.produce_output(composter.device).unwrap();
-                if produced {
-                    let composter_kind = self.known.composters.get(composter.key).unwrap();
-                    let item = self.inventory.items_id.introduce().one(ItemId);
-                    let create_item = self
-                        .inventory
-                        .create_item(
-                            item,
-                            &composter_kind.compost,
-                            composter.output,
-                            composter_kind.compost.max_quantity,
-                        )
-                        .unwrap();
-                    composter_events.push(create_item().into())
-                }
+            match self.process_composter(composter) {
+                Ok(events) => composter_events.extend(events),
+                Err(error) => self.quarantine(Subsystem::Composters, entity, error),
             }
         }
 
-        let deprecated_random = thread_rng();
-        let working_events = self.working.update(physics_time, deprecated_random.clone());
-        let random = &mut Random::new();
+        let planting_events = self
+            .planting
+            .update(physics_time, &mut self.random.planting);
+        let mut dead_crops_events = vec![];
+        for crop in self.universe.crops.clone() {
+            let entity = Entity::Crop(crop);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            match self.decay_dead_crop(crop) {
+                Ok(events) => dead_crops_events.extend(events),
+                Err(error) => self.quarantine(Subsystem::Crops, entity, error),
+            }
+        }
+        let mut dead_trees_events = vec![];
+        for tree in self.universe.trees.clone() {
+            let entity = Entity::Tree(tree);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            match self.fall_dead_tree(tree) {
+                Ok(events) => dead_trees_events.extend(events),
+                Err(error) => self.quarantine(Subsystem::Trees, entity, error),
+            }
+        }
 
-        let raising_events = self.raising.update(time, random);
+        let working_events = self.working.update(physics_time, &mut self.random.working);
+        for creature in self.universe.creatures.clone() {
+            let entity = Entity::Creature(creature);
+            if self.quarantine.contains(&entity) {
+                continue;
+            }
+            if let Err(error) = self.integrate_partners(creature) {
+                self.quarantine(Subsystem::Creatures, entity, error);
+            }
+        }
+        let raising_events = self.raising.update(time, &mut self.random.raising);
+        let mut births_events = vec![];
+        for mother in self.raising.take_births() {
+            let entity = self.get_animal_entity(mother);
+            if entity.is_some_and(|entity| self.quarantine.contains(&entity)) {
+                continue;
+            }
+            match self.deliver_offspring(mother) {
+                Ok(events) => births_events.extend(events),
+                Err(error) => self.quarantine_or_report(Subsystem::Births, entity, error),
+            }
+        }
         let dead_animals = self.raising.take_dead_animals();
         let mut dead_animals_events = vec![];
         for animal in dead_animals {
-            let creature = self.universe.get_creature_by_animal(animal.id).unwrap();
-            let creature_kind = self.known.creatures.get(creature.key).unwrap();
-            let body = self.physics.get_body(creature.body).unwrap();
-            let position = body.position;
-            let space = body.space;
-            let events = self.universe.vanish_creature(creature);
-            dead_animals_events.push(events.into());
-
-            let corpse_kind = &creature_kind.corpse;
-            let (barrier, create_barrier) = self
-                .physics
-                .create_barrier(space, corpse_kind.barrier.clone(), position, true, false)
-                .unwrap();
-            dead_animals_events.extend(occur![
-                create_barrier(),
-                self.appear_corpse(corpse_kind.id, barrier).unwrap(),
-            ]);
+            let entity = self.get_animal_entity(animal.id);
+            if entity.is_some_and(|entity| self.quarantine.contains(&entity)) {
+                continue;
+            }
+            match self.transform_dead_animal(animal) {
+                Ok(events) => dead_animals_events.extend(events),
+                Err(error) => self.quarantine_or_report(Subsystem::DeadAnimals, entity, error),
+            }
         }
 
         let mut events = occur![
             timing_events,
             activity_events,
             physics_events,
-            self.planting.update(physics_time),
+            planting_events,
             raising_events,
-            self.landscaping.update(physics_time, deprecated_random),
+            self.landscaping
+                .update(physics_time, &mut self.random.landscaping),
             working_events,
         ];
+        events.extend(trigger_events);
+        events.extend(dead_crops_events);
+        events.extend(dead_trees_events);
         events.extend(cementer_events);
         events.extend(composter_events);
         events.extend(destroy_empty_stacks);
+        events.extend(births_events);
         events.extend(dead_animals_events);
+        self.prune_quarantine(&events);
+        self.record(|tick| Record::Update {
+            tick,
+            time: real_seconds,
+            digest: Journal::digest(&events),
+        });
         events
     }
+
+    pub fn take_update_failures(&mut self) -> Vec<UpdateFailure> {
+        take(&mut self.update_failures)
+    }
+
+    pub(crate) fn quarantine(&mut self, subsystem: Subsystem, entity: Entity, error: ActionError) {
+        self.quarantine.insert(entity);
+        self.report_failure(subsystem, Some(entity), error);
+    }
+
+    /// Quarantines entity failed to be updated, failure of unknown entity is only reported.
+    fn quarantine_or_report(
+        &mut self,
+        subsystem: Subsystem,
+        entity: Option<Entity>,
+        error: ActionError,
+    ) {
+        match entity {
+            Some(entity) => self.quarantine(subsystem, entity, error),
+            None => self.report_failure(subsystem, None, error),
+        }
+    }
+
+    /// Releases quarantined entities vanished from universe, so quarantine never outgrows world.
+    pub(crate) fn prune_quarantine(&mut self, events: &[Event]) {
+        if self.quarantine.is_empty() {
+            return;
+        }
+        for event in events {
+            if let Event::UniverseStream(events) = event {
+                for event in events {
+                    let entity = match *event {
+                        Universe::FarmlandVanished(farmland) => Entity::Farmland(farmland),
+                        Universe::FarmerVanished(farmer) => Entity::Farmer(farmer),
+                        Universe::CreatureVanished(creature) => Entity::Creature(creature),
+                        Universe::TreeVanished(tree) => Entity::Tree(tree),
+                        Universe::CropVanished(crop) => Entity::Crop(crop),
+                        Universe::CorpseVanished(corpse) => Entity::Corpse(corpse),
+                        Universe::StackVanished(stack) => Entity::Stack(stack),
+                        Universe::EquipmentVanished(equipment) => Entity::Equipment(equipment),
+                        Universe::TheodoliteVanished { id } => Entity::Theodolite(id),
+                        Universe::DoorVanished(door) => Entity::Door(door),
+                        Universe::RestVanished(rest) => Entity::Rest(rest),
+                        Universe::CementerVanished(cementer) => Entity::Cementer(cementer),
+                        Universe::ComposterVanished(composter) => Entity::Composter(composter),
+                        Universe::IrrigatorVanished(irrigator) => Entity::Irrigator(irrigator),
+                        _ => continue,
+                    };
+                    self.quarantine.remove(&entity);
+                }
+            }
+        }
+    }
+
+    fn get_animal_entity(&self, animal: AnimalId) -> Option<Entity> {
+        self.universe
+            .get_creature_by_animal(animal)
+            .ok()
+            .map(Entity::Creature)
+    }
+
+    pub(crate) fn report_failure(
+        &mut self,
+        subsystem: Subsystem,
+        entity: Option<Entity>,
+        error: ActionError,
+    ) {
+        error!("Unable to update {entity:?} of {subsystem:?}, {error:?}");
+        self.update_failures.push(UpdateFailure {
+            subsystem,
+            entity,
+            error,
+        });
+    }
+
+    fn get_farmer_boost(&self, farmer: Farmer) -> Result<u8, ActionError> {
+        let activity = self.universe.get_farmer_activity(farmer)?;
+        let boost = match activity {
+            Activity::Resting { comfort } => comfort,
+            _ => 1,
+        };
+        Ok(boost)
+    }
+
+    fn update_farmer_activity(&mut self, farmer: Farmer) -> Result<Vec<Universe>, ActionError> {
+        if self.universe.get_farmer_activity(farmer)? == Activity::Usage {
+            let hands = self.inventory.get_container(farmer.hands)?;
+            if hands.items.is_empty() {
+                return Ok(vec![self.universe.change_activity(farmer, Activity::Idle)]);
+            }
+        }
+        Ok(vec![])
+    }
+
+    fn destroy_empty_stack(&mut self, stack: Stack) -> Result<Vec<Event>, ActionError> {
+        let container = self.inventory.get_container(stack.container)?;
+        if !container.items.is_empty() {
+            return Ok(vec![]);
+        }
+        let destroy_barrier = self.physics.destroy_barrier(stack.barrier)?;
+        let destroy_container = self
+            .inventory
+            .destroy_containers(vec![stack.container], false)?;
+        Ok(occur![
+            destroy_container(),
+            destroy_barrier(),
+            self.universe.vanish_stack(stack),
+        ])
+    }
+
+    /// Integrates weather of farmland calendar into its land.
+    fn integrate_weather(&mut self, farmland: Farmland) -> Result<(), ActionError> {
+        let weather = self.timing.get_calendar(farmland.calendar)?.weather;
+        let land_kind = &self.landscaping.get_land(farmland.land)?.kind;
+        let sheltered = self
+            .building
+            .get_grid(farmland.grid)?
+            .get_rooms_mask(land_kind.width, land_kind.height);
+        self.landscaping.integrate_weather(
+            farmland.land,
+            weather.rainfall(),
+            weather.evaporation(),
+        )?;
+        self.landscaping
+            .integrate_shelter(farmland.land, sheltered)?;
+        Ok(())
+    }
+
+    fn integrate_tree(&mut self, tree: Tree, physics_time: f32) -> Result<(), ActionError> {
+        let barrier = self.physics.get_barrier(tree.barrier)?;
+        let farmland = self.universe.get_farmland_by_space(barrier.space)?;
+        let tile = barrier.position.to_tile();
+        self.integrate_plant(tree.plant, farmland, tile, physics_time)
+    }
+
+    /// Returns bodies of all pollinating creatures, collected once per update for all crops.
+    fn get_pollinators(&self) -> HashSet<BodyId> {
+        self.universe
+            .creatures
+            .iter()
+            .filter(|creature| {
+                self.known
+                    .creatures
+                    .get(creature.key)
+                    .map(|kind| kind.pollinator)
+                    .unwrap_or(false)
+            })
+            .map(|creature| creature.body)
+            .collect()
+    }
+
+    fn integrate_crop(
+        &mut self,
+        crop: Crop,
+        pollinators: &HashSet<BodyId>,
+        physics_time: f32,
+    ) -> Result<(), ActionError> {
+        let sensor = self.physics.get_sensor(crop.sensor)?;
+        let farmland = self.universe.get_farmland_by_space(sensor.space)?;
+        let tile = sensor.position.to_tile();
+
+        let pollinators = sensor
+            .registered
+            .iter()
+            .filter(|body| pollinators.contains(body))
+            .count();
+
+        self.integrate_plant(crop.plant, farmland, tile, physics_time)?;
+        self.planting
+            .integrate_pollinators(crop.plant, pollinators)?;
+        Ok(())
+    }
+
+    /// Integrates water, nutrients and climate of farmland tile into plant of crop or tree.
+    fn integrate_plant(
+        &mut self,
+        plant: PlantId,
+        farmland: Farmland,
+        tile: Tile,
+        physics_time: f32,
+    ) -> Result<(), ActionError> {
+        // everything is looked up before any change, so plant is integrated all or nothing,
+        // land and soil of farmland may differ in size, so each one checks tile by itself
+        let plant_transpiration = self.planting.get_plant(plant)?.kind.transpiration;
+        self.landscaping
+            .get_land(farmland.land)?
+            .get_moisture(tile)?;
+        self.planting.get_soil(farmland.soil)?.get_place(tile)?;
+        let calendar = self.timing.get_calendar(farmland.calendar)?;
+        let season = calendar.get_season();
+        let weather = calendar.weather;
+        let grid = self.building.get_grid(farmland.grid)?;
+        // rooms shelter plants from weather
+        let (temperature, greenhouse, wind) = match grid.get_room(tile) {
+            Some(room) if room.material.index() == Material::GLASS => {
+                (season.temperature + GREENHOUSE_WARMING, true, 0.0)
+            }
+            Some(_) => (season.temperature + ROOM_WARMING, false, 0.0),
+            None => (
+                season.temperature + weather.temperature(),
+                false,
+                weather.wind(),
+            ),
+        };
+        let season = season.key.clone();
+
+        let consumption = plant_transpiration * physics_time;
+        let consumed = self
+            .landscaping
+            .request_consumption(farmland.land, tile, consumption)?;
+        let lack = consumption - consumed;
+        self.planting.integrate_thirst(plant, lack, consumption)?;
+        let land = self.landscaping.get_land(farmland.land)?;
+        self.planting
+            .integrate_moisture(plant, land.get_moisture(tile)?)?;
+
+        let (lack, consumption) = self.planting.request_nutrients_consumption(
+            farmland.soil,
+            tile,
+            plant,
+            physics_time,
+        )?;
+        self.planting.integrate_hunger(plant, lack, consumption)?;
+        self.planting
+            .integrate_climate(plant, &season, temperature, greenhouse)?;
+        self.planting.integrate_wind(plant, wind)?;
+        Ok(())
+    }
+
+    fn decay_dead_crop(&mut self, crop: Crop) -> Result<Vec<Event>, ActionError> {
+        let plant = self.planting.get_plant(crop.plant)?;
+        if plant.stage != PlantStage::Dead {
+            return Ok(vec![]);
+        }
+        let crop_kind = self.known.crops.get(crop.key)?;
+        let barrier = self.physics.get_barrier(crop.barrier)?;
+        let position = barrier.position;
+        let space = barrier.space;
+
+        // crop barrier is destroyed within the same update, so it is the only allowed overlap
+        self.ensure_items_droppable(space, position, crop.barrier)?;
+
+        self.physics.get_sensor(crop.sensor)?; // ensure sensor valid before barrier destroyed
+
+        let (residue, destroy_plant) = self.planting.destroy_plant(crop.plant)?;
+        let destroy_plant_barrier = self.physics.destroy_barrier(crop.barrier)?;
+        let mut events = occur![
+            destroy_plant_barrier(),
+            destroy_plant(),
+            self.universe.vanish_crop(crop),
+        ];
+        let destroy_plant_sensor = self.physics.destroy_sensor(crop.sensor)?;
+        events.extend(occur![destroy_plant_sensor(),]);
+        self.unsubscribe_sensor(crop.sensor);
+
+        let container = self.get_drop_container(space, position);
+        let items = vec![Item {
+            id: self.inventory.items_id.introduce().one(ItemId),
+            kind: crop_kind.residue.clone(),
+            container,
+            quantity: 1 + (9.0 * residue / 5.0) as u8,
+            quality: 1.0,
+        }];
+        events.extend(self.drop_items(space, position, container, items)?);
+        Ok(events)
+    }
+
+    fn fall_dead_tree(&mut self, tree: Tree) -> Result<Vec<Event>, ActionError> {
+        let plant = self.planting.get_plant(tree.plant)?;
+        if plant.stage != PlantStage::Dead {
+            return Ok(vec![]);
+        }
+        let growth = plant.growth;
+        let tree_kind = self.known.trees.get(tree.kind)?;
+        let barrier = self.physics.get_barrier(tree.barrier)?;
+        let position = barrier.position;
+        let space = barrier.space;
+
+        // tree barrier is destroyed within the same update, so it is the only allowed overlap
+        self.ensure_items_droppable(space, position, tree.barrier)?;
+
+        let destroy_tree_barrier = self.physics.destroy_barrier(tree.barrier)?;
+        let (_residue, destroy_plant) = self.planting.destroy_plant(tree.plant)?;
+        let mut events = occur![
+            destroy_tree_barrier(),
+            destroy_plant(),
+            self.universe.vanish_tree(tree),
+        ];
+
+        // dead tree gives only wood, saplings are never collected from it
+        let container = self.get_drop_container(space, position);
+        let items = vec![Item {
+            id: self.inventory.items_id.introduce().one(ItemId),
+            kind: tree_kind.wood.clone(),
+            container,
+            quantity: tree_kind.get_wood_quantity(growth),
+            quality: 1.0,
+        }];
+        events.extend(self.drop_items(space, position, container, items)?);
+        Ok(events)
+    }
+
+    fn process_cementer(&mut self, cementer: Cementer) -> Result<Vec<Event>, ActionError> {
+        let cementer_kind = self.known.cementers.get(cementer.key)?;
+        self.process_device(
+            cementer.input,
+            cementer.device,
+            cementer.output,
+            &cementer_kind.cement,
+            1,
+        )
+    }
+
+    fn process_composter(&mut self, composter: Composter) -> Result<Vec<Event>, ActionError> {
+        let composter_kind = self.known.composters.get(composter.key)?;
+        self.process_device(
+            composter.input,
+            composter.device,
+            composter.output,
+            &composter_kind.compost,
+            composter_kind.compost.max_quantity,
+        )
+    }
+
+    fn process_device(
+        &mut self,
+        input: ContainerId,
+        device: DeviceId,
+        output: ContainerId,
+        product: &Shared<ItemKind>,
+        quantity: u8,
+    ) -> Result<Vec<Event>, ActionError> {
+        // device and both containers are validated before any command runs,
+        // so input is never consumed if output can not be produced
+        let state = self.working.get_device(device)?;
+        let input_container = self.inventory.get_container(input)?;
+        let output_container = self.inventory.get_container(output)?;
+        let consumes_input = state.enabled && !state.input && !input_container.items.is_empty();
+        let produces_output =
+            state.output && output_container.items.len() < output_container.kind.capacity;
+
+        let mut events = vec![];
+        if consumes_input {
+            let use_item = self.inventory.pop_use_item(input)?;
+            let consume_input = self.working.consume_input(device)?;
+            events.extend(occur![use_item(), consume_input(),]);
+        }
+        if produces_output {
+            let item = self.inventory.items_id.introduce().one(ItemId);
+            let create_item = self
+                .inventory
+                .create_item(item, product, output, quantity, 1.0)?;
+            let produce_output = self.working.produce_output(device)?;
+            events.extend(occur![produce_output(), create_item(),]);
+        }
+        Ok(events)
+    }
+
+    /// Integrates creatures of same kind nearby as mating partners of creature animal.
+    fn integrate_partners(&mut self, creature: Creature) -> Result<(), ActionError> {
+        let body = self.physics.get_body(creature.body)?;
+        let mut partners = vec![];
+        for other in &self.universe.creatures {
+            if other.id == creature.id || other.key != creature.key {
+                continue;
+            }
+            // broken partner is quarantined by itself, so it must not fail its herd
+            let other_body = match self.physics.get_body(other.body) {
+                Ok(other_body) => other_body,
+                Err(_) => continue,
+            };
+            if other_body.space == body.space
+                && other_body.position.distance(body.position) < MATING_DISTANCE
+            {
+                partners.push(other.animal);
+            }
+        }
+        self.raising.integrate_partners(creature.animal, partners)?;
+        Ok(())
+    }
+
+    fn deliver_offspring(&mut self, mother: AnimalId) -> Result<Vec<Event>, ActionError> {
+        let creature = self.universe.get_creature_by_animal(mother)?;
+        let creature_kind = self.known.creatures.get(creature.key)?;
+        let body = self.physics.get_body(creature.body)?;
+        let position = body.position;
+        let space = body.space;
+        let offspring = self.physics.bodies_sequence.introduce().one(BodyId);
+        let create_body =
+            self.physics
+                .create_body(offspring, space, creature_kind.body.clone(), position)?;
+        let sex = Sex::random(&mut self.random.raising);
+        let (animal, create_animal) = self
+            .raising
+            .create_animal(creature_kind.animal.clone(), sex)?;
+        let mut events = occur![create_body(), create_animal(),];
+        events.push(
+            self.appear_creature(creature_kind.id, offspring, animal)?
+                .into(),
+        );
+        Ok(events)
+    }
+
+    fn transform_dead_animal(&mut self, animal: Animal) -> Result<Vec<Event>, ActionError> {
+        let creature = self.universe.get_creature_by_animal(animal.id)?;
+        let creature_kind = self.known.creatures.get(creature.key)?;
+        let body = self.physics.get_body(creature.body)?;
+        let position = body.position;
+        let space = body.space;
+        let corpse_kind = &creature_kind.corpse;
+        let (barrier, create_barrier) = self.physics.create_barrier(
+            space,
+            corpse_kind.barrier.clone(),
+            position,
+            Rotation::A000,
+            true,
+            false,
+        )?;
+        let barrier_events = create_barrier();
+        Ok(occur![
+            self.universe.vanish_creature(creature),
+            barrier_events,
+            self.appear_corpse(corpse_kind.id, barrier, position),
+        ])
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::actions::tests::{item_kind, plant_crop, prepare_game, prepare_tree_kind};
+    use crate::api::Entity;
+    use crate::collections::Shared;
+    use crate::inventory::Function;
+    use crate::math::ArrayIndex;
+    use crate::model::{Activity, Farmland, Player};
+    use crate::model::{CorpseKey, CorpseKind, Creature, CreatureKey, CreatureKind, Crop, CropKey};
+    use crate::physics::{BarrierId, BodyId, BodyKey, BodyKind, SensorId};
+    use crate::planting::{Nutrient, PlantId, PlantStage, SoilKey, SoilKind};
+    use crate::raising::{AnimalKey, AnimalKind, Sex};
+    use crate::timing::CalendarId;
+    use crate::{occur, Game, SensorTrigger, Subsystem};
+
+    pub(crate) fn spawn_creature(game: &mut Game, farmland: Farmland, sex: Sex) -> Creature {
+        let kind = game.known.creatures.find("lama").unwrap();
+        let body = game.physics.bodies_sequence.introduce().one(BodyId);
+        game.physics
+            .create_body(body, farmland.space, kind.body.clone(), [4.5, 4.5])
+            .unwrap()();
+        let (animal, create_animal) = game
+            .raising
+            .create_animal(kind.animal.clone(), sex)
+            .unwrap();
+        create_animal();
+        game.appear_creature(kind.id, body, animal).unwrap();
+        game.universe.get_creature_by_animal(animal).unwrap()
+    }
+
+    pub(crate) fn prepare_creatures(game: &mut Game) {
+        let corpse = CorpseKind {
+            id: CorpseKey(1),
+            name: "corpse".to_string(),
+            barrier: game.known.barriers.find("<drop>").unwrap(),
+            item: Shared::new(item_kind(1, "corpse", vec![])),
+        };
+        let creature = CreatureKind {
+            id: CreatureKey(1),
+            name: "lama".to_string(),
+            body: Shared::new(BodyKind {
+                id: BodyKey(2),
+                name: "lama".to_string(),
+                speed: 1.0,
+                radius: 0.25,
+                collision_layer: 0,
+                collision_mask: 0,
+                acceleration: 0.0,
+                damping: 4.0,
+            }),
+            animal: Shared::new(AnimalKind {
+                id: AnimalKey(1),
+                name: "lama".to_string(),
+                hunger_speed: 0.0,
+                thirst_speed: 0.0,
+                hunger_damage: 0.0,
+                thirst_damage: 0.0,
+                death_threshold: 0.0,
+                voracity: 0.0,
+                maturity: 0.0,
+                gestation: 0.0,
+                fertility: 1000.0,
+            }),
+            corpse: Shared::new(corpse),
+            pollinator: false,
+        };
+        game.known
+            .creatures
+            .insert(CreatureKey(1), "lama".to_string(), creature);
+    }
+
+    #[test]
+    fn test_broken_crop_quarantined_without_stopping_update() {
+        let (mut game, _farmer, _farmland) = prepare_game(vec![]);
+        let crop = Crop {
+            id: 1,
+            key: CropKey(1),
+            plant: PlantId(1),
+            barrier: BarrierId(42),
+            sensor: SensorId(42),
+        };
+        game.universe.crops.push(crop);
+
+        game.update(0.1);
+        let failures = game.take_update_failures();
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].subsystem, Subsystem::Crops);
+        assert_eq!(failures[0].kind(), "physics");
+        assert!(game.quarantine.contains(&Entity::Crop(crop)));
+
+        game.update(0.1);
+        assert!(game.take_update_failures().is_empty());
+    }
+
+    #[test]
+    fn test_vanished_crop_released_from_quarantine() {
+        let (mut game, _farmer, _farmland) = prepare_game(vec![]);
+        let crop = Crop {
+            id: 1,
+            key: CropKey(1),
+            plant: PlantId(1),
+            barrier: BarrierId(42),
+            sensor: SensorId(42),
+        };
+        game.universe.crops.push(crop);
+        game.update(0.1);
+        assert!(game.quarantine.contains(&Entity::Crop(crop)));
+
+        let events = occur![game.universe.vanish_crop(crop),];
+        game.prune_quarantine(&events);
+
+        assert!(game.quarantine.is_empty());
+    }
+
+    #[test]
+    fn test_broken_farmland_quarantined_without_stopping_update() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let broken = Farmland {
+            id: 42,
+            calendar: CalendarId(42),
+            ..farmland
+        };
+        game.universe.farmlands.push(broken);
+
+        game.update(0.1);
+        let failures = game.take_update_failures();
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].subsystem, Subsystem::Farmlands);
+        assert!(game.quarantine.contains(&Entity::Farmland(broken)));
+
+        game.update(0.1);
+        assert!(game.take_update_failures().is_empty());
+    }
+
+    #[test]
+    fn test_broken_crop_skipped_when_looking_around() {
+        let (mut game, farmer, _farmland) = prepare_game(vec![]);
+        game.players.push(Player {
+            id: farmer.player,
+            name: "farmer".to_string(),
+        });
+        let crop = Crop {
+            id: 1,
+            key: CropKey(1),
+            plant: PlantId(1),
+            barrier: BarrierId(42),
+            sensor: SensorId(42),
+        };
+        game.universe.crops.push(crop);
+
+        let events = game.look_around();
+        let failures = game.take_update_failures();
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].subsystem, Subsystem::Inspection);
+        assert!(game.quarantine.contains(&Entity::Crop(crop)));
+    }
+
+    #[test]
+    fn test_only_pollinators_counted_by_crop() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        prepare_creatures(&mut game);
+        let kind = game.known.creatures.get(CreatureKey(1)).unwrap();
+        kind.clone().borrow_mut().pollinator = true;
+        let creature = spawn_creature(&mut game, farmland, Sex::Female);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        for sensor in game.physics.sensors.get_mut(&farmland.space).unwrap() {
+            sensor.registered.extend([farmer.body, creature.body]);
+        }
+
+        let pollinators = game.get_pollinators();
+        game.integrate_crop(crop, &pollinators, 0.1).unwrap();
+
+        assert_eq!(pollinators.len(), 1);
+        let plant = game.planting.get_plant(crop.plant).unwrap();
+        assert_eq!(plant.pollinators, 1);
+    }
+
+    #[test]
+    fn test_creatures_nearby_give_birth_to_offspring() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        prepare_creatures(&mut game);
+        let female = spawn_creature(&mut game, farmland, Sex::Female);
+        spawn_creature(&mut game, farmland, Sex::Male);
+
+        game.update(0.1);
+        let pregnancy = game.raising.get_animal(female.animal).unwrap().pregnancy;
+        assert_eq!(pregnancy, Some(0.0));
+
+        game.update(0.1);
+        assert!(game.take_update_failures().is_empty());
+        assert_eq!(game.universe.creatures.len(), 3);
+        let offspring = game.universe.creatures[2];
+        let body = game.physics.get_body(offspring.body).unwrap();
+        assert_eq!(body.position, [4.5, 4.5]);
+    }
+
+    #[test]
+    fn test_broken_creature_does_not_stop_herd_breeding() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        prepare_creatures(&mut game);
+        let female = spawn_creature(&mut game, farmland, Sex::Female);
+        spawn_creature(&mut game, farmland, Sex::Male);
+        let broken = Creature {
+            id: 42,
+            body: BodyId(42),
+            ..female
+        };
+        game.universe.creatures.push(broken);
+
+        game.update(0.1);
+
+        let failures = game.take_update_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].entity, Some(Entity::Creature(broken)));
+        let pregnancy = game.raising.get_animal(female.animal).unwrap().pregnancy;
+        assert_eq!(pregnancy, Some(0.0));
+    }
+
+    #[test]
+    fn test_plant_integration_leaves_no_changes_if_calendar_missing() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [3.5, 2.5]);
+        let place = [3, 2].fit(128);
+        game.landscaping
+            .get_land_mut(farmland.land)
+            .unwrap()
+            .moisture[place] = 0.5;
+        let farmland = Farmland {
+            calendar: CalendarId(42),
+            ..farmland
+        };
+
+        let result = game.integrate_plant(crop.plant, farmland, [3, 2], 0.1);
+
+        assert!(result.is_err());
+        let land = game.landscaping.get_land(farmland.land).unwrap();
+        assert_eq!(land.moisture[place], 0.5);
+        let soil = game.planting.get_soil(farmland.soil).unwrap();
+        assert_eq!(soil.nutrients[Nutrient::NITROGEN][place], 0.5);
+    }
+
+    #[test]
+    fn test_dead_crop_turns_into_residue() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        assert!(game.decay_dead_crop(crop).unwrap().is_empty());
+
+        game.subscribe_sensor(crop.sensor, SensorTrigger::CROP);
+        game.planting.get_plant_mut(crop.plant).unwrap().stage = PlantStage::Dead;
+        game.decay_dead_crop(crop).unwrap();
+
+        assert!(game.universe.crops.is_empty());
+        assert!(!game.sensor_triggers.contains_key(&crop.sensor));
+        assert!(game.planting.get_plant(crop.plant).is_err());
+        assert!(game.physics.get_barrier(crop.barrier).is_err());
+        let stack = game.universe.stacks[0];
+        let residue = game.inventory.get_container(stack.container).unwrap();
+        assert_eq!(residue.items[0].kind.name, "residue");
+    }
+
+    #[test]
+    fn test_dead_crop_decays_into_seeds_stack_of_late_harvest() {
+        let (mut game, farmer, farmland) = prepare_game(vec![]);
+        let crop = plant_crop(&mut game, farmland, [2.5, 1.5]);
+        game.planting.get_plant_mut(crop.plant).unwrap().stage = PlantStage::Overripe;
+        game.harvest_crop(farmer, farmland, crop).unwrap();
+
+        game.planting.get_plant_mut(crop.plant).unwrap().stage = PlantStage::Dead;
+        game.decay_dead_crop(crop).unwrap();
+
+        assert!(game.universe.crops.is_empty());
+        assert_eq!(game.universe.stacks.len(), 1);
+        let stack = game.universe.stacks[0];
+        let drop = game.inventory.get_container(stack.container).unwrap();
+        let names: Vec<&str> = drop
+            .items
+            .iter()
+            .map(|item| item.kind.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["seeds", "residue"]);
+    }
+
+    #[test]
+    fn test_dead_tree_falls_into_wood() {
+        let sapling = item_kind(7, "sapling", vec![Function::Sapling(1)]);
+        let (mut game, farmer, farmland) = prepare_game(vec![sapling]);
+        prepare_tree_kind(&mut game);
+        game.universe
+            .farmers_activity
+            .insert(farmer, Activity::Usage);
+        game.landscaping
+            .plough_place(farmland.land, [2, 1])
+            .unwrap()();
+        game.plant_tree(farmer, farmland, [2, 1]).unwrap();
+        let tree = game.universe.trees[0];
+        assert!(game.fall_dead_tree(tree).unwrap().is_empty());
+
+        let plant = game.planting.get_plant_mut(tree.plant).unwrap();
+        plant.stage = PlantStage::Dead;
+        plant.growth = 2.5;
+        game.fall_dead_tree(tree).unwrap();
+
+        assert!(game.universe.trees.is_empty());
+        assert!(game.planting.get_plant(tree.plant).is_err());
+        assert!(game.physics.get_barrier(tree.barrier).is_err());
+        let stack = game.universe.stacks[0];
+        let drop = game.inventory.get_container(stack.container).unwrap();
+        assert_eq!(drop.items.len(), 1);
+        assert_eq!(drop.items[0].kind.name, "wood");
+        assert_eq!(drop.items[0].quantity, 10);
+    }
+
+    #[test]
+    fn test_plant_integrated_on_soil_smaller_than_land() {
+        let (mut game, _farmer, farmland) = prepare_game(vec![]);
+        let soil_kind = Shared::new(SoilKind {
+            id: SoilKey(2),
+            name: "small".to_string(),
+            width: 4,
+            height: 4,
+            nutrients: [0.5, 0.5, 0.5],
+        });
+        let (soil, create_soil) = game.planting.create_soil(&soil_kind).unwrap();
+        create_soil();
+        let farmland = Farmland { soil, ..farmland };
+        let crop = plant_crop(&mut game, farmland, [3.5, 2.5]);
+        let land = game.landscaping.get_land_mut(farmland.land).unwrap();
+        land.moisture[[3, 2].fit(128)] = 0.5;
+
+        game.integrate_plant(crop.plant, farmland, [3, 2], 0.1)
+            .unwrap();
+
+        let plant = game.planting.get_plant(crop.plant).unwrap();
+        assert!((plant.moisture - 0.4).abs() < 0.0001);
+        assert_eq!(plant.hunger, 0.0);
+        let soil = game.planting.get_soil(soil).unwrap();
+        assert!(soil.nutrients[Nutrient::NITROGEN][[3, 2].fit(4)] < 0.5);
+        let outside = game.integrate_plant(crop.plant, farmland, [6, 2], 0.1);
+        assert!(outside.is_err());
+    }
 }
@@ -277,6 +277,8 @@ impl TcpServer {
             }
             GameResponse::ActionError { .. } => {}
             GameResponse::Login { .. } => {}
+            GameResponse::View { .. } => {}
+            GameResponse::QueryError { .. } => {}
         }
     }
 
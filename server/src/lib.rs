@@ -9,12 +9,20 @@ use log::{error, info};
 
 use datamap::Storage;
 use game::api::{GameResponse, PlayerRequest};
+use game::journal::Journal;
 use game::Game;
 use network::{Configuration, TcpServer};
 
 lazy_static! {
     static ref HOST_FRAMES_TOTAL: prometheus::IntCounter =
         prometheus::register_int_counter!("host_frames_total", "host_frames_total").unwrap();
+    static ref GAME_UPDATE_FAILURES_TOTAL: prometheus::IntCounterVec =
+        prometheus::register_int_counter_vec!(
+            "game_update_failures_total",
+            "game_update_failures_total",
+            &["subsystem", "kind"]
+        )
+        .unwrap();
 }
 
 pub struct LocalServerThread {
@@ -39,6 +47,13 @@ impl LocalServerThread {
                 storage.setup_tracking().unwrap();
                 let mut game = Game::new(storage);
                 game.load_game_full();
+                if let Err(error) = game.save_randomness() {
+                    error!("Unable to save game randomness {save_file}, {error:?}");
+                }
+                let journal = Journal::path_of(&save_file);
+                if let Err(error) = game.start_journal(&journal) {
+                    error!("Unable to start game journal {journal}, {error:?}");
+                }
                 let mut tick = Instant::now();
                 notify_started.send(true).unwrap();
 
@@ -75,6 +90,13 @@ impl LocalServerThread {
                                     ),
                                 }
                             }
+                            PlayerRequest::Query { query, query_id } => {
+                                let response = match game.perform_query(query) {
+                                    Ok(view) => GameResponse::View { query_id, view },
+                                    Err(error) => GameResponse::QueryError { query_id, error },
+                                };
+                                server.send(request.player, response);
+                            }
                             _ => {
                                 info!("Request [{}]: {:?}", request.player, request.request);
                             }
@@ -84,6 +106,11 @@ impl LocalServerThread {
                     let time = tick.elapsed().as_secs_f32();
                     tick = Instant::now();
                     let events = game.update(time);
+                    for failure in game.take_update_failures() {
+                        GAME_UPDATE_FAILURES_TOTAL
+                            .with_label_values(&[failure.subsystem.name(), failure.kind()])
+                            .inc();
+                    }
                     if !events.is_empty() {
                         server.broadcast(GameResponse::Events { events });
                     }
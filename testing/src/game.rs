@@ -9,7 +9,7 @@ use datamap::Storage;
 use game::api::{ActionError, Event};
 use game::building::{Grid, GridId, Material, Structure, SurveyorId};
 use game::inventory::{ContainerId, Item, ItemId};
-use game::math::VectorMath;
+use game::math::{Rotation, VectorMath};
 use game::model::{Construction, Crop, Farmer, Farmland, Stack, Theodolite, Universe};
 
 use game::{occur, Game};
@@ -144,7 +144,7 @@ pub unsafe extern "C" fn add_items(
         let create_item = scenario
             .game
             .inventory
-            .create_item(id, &kind, container, 1)
+            .create_item(id, &kind, container, 1, 1.0)
             .expect("failed created_item");
         create_item();
     }
@@ -165,6 +165,7 @@ pub unsafe extern "C" fn add_stack(
             farmland.space,
             barrier_kind,
             position.to_slice(),
+            Rotation::A000,
             true,
             false,
         )?;
@@ -182,6 +183,7 @@ pub unsafe extern "C" fn add_stack(
                 kind: item_kind.clone(),
                 container,
                 quantity: 1,
+                quality: 1.0,
             })
             .collect();
         let create_container = game
@@ -223,9 +225,12 @@ pub unsafe extern "C" fn add_crop(
             position.to_slice(),
             false,
         )?;
-        let (plant, create_plant) = game
-            .planting
-            .create_plant(farmland.soil, &kind.plant, 0.0)?;
+        let (plant, create_plant) = game.planting.create_plant(
+            farmland.soil,
+            position.to_slice().to_tile(),
+            &kind.plant,
+            0.0,
+        )?;
         let events = occur![
             create_barrier_sensor(),
             create_plant(),
@@ -423,6 +428,20 @@ pub unsafe extern "C" fn add_building(
     }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn query(scenario: &mut Scenario, data: PyString) -> PyString {
+    // malformed query is reported to caller like failed one instead of crashing scenario
+    let view = match serde_json::from_str(data.to_str()) {
+        Ok(query) => scenario
+            .game
+            .perform_query(query)
+            .map_err(|error| format!("{error:?}")),
+        Err(error) => Err(format!("unable to parse query, {error}")),
+    };
+    let data = serde_json::to_string(&view).expect("unable to serialize");
+    CString::new(data).unwrap().into_raw()
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn take_events(scenario: &mut Scenario) -> PyString {
     let events = take(&mut scenario.events);
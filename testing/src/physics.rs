@@ -4,6 +4,7 @@ use std::ffi::CString;
 
 use serde_json::json;
 
+use game::math::Rotation;
 use game::physics::{Barrier, BarrierId, BodyId, Space, SpaceId};
 
 use crate::ffi::{PyString, PyStringToString, PyTuple, PyTupleToSlice};
@@ -19,6 +20,7 @@ pub unsafe extern "C" fn add_space(scenario: &mut Scenario, kind: PyString) -> S
         id,
         kind,
         holes: vec![],
+        paths: vec![],
     };
     physics.load_spaces(vec![space], id.0);
     id
@@ -57,6 +59,7 @@ pub unsafe extern "C" fn add_barrier(
         space: space.into(),
         position: position.to_slice(),
         active,
+        rotation: Rotation::A000,
     };
     physics.load_barriers(vec![barrier], id.0);
     id
@@ -683,6 +683,8 @@ impl PhysicsTestScenario {
                 name: body_kind.to_string(),
                 speed,
                 radius,
+                collision_layer: 0,
+                collision_mask: 0,
             },
         );
         self